@@ -0,0 +1,53 @@
+use DocId;
+use Score;
+use collector::Collector;
+
+/// A bucket, as returned by the aggregation collectors
+/// (`RangeCollector`, `HistogramCollector`, ...).
+///
+/// Each bucket has its own sub-collector, receiving all of the
+/// documents falling in the bucket.
+pub struct Bucket<TKey, TCollector: Collector> {
+    key: TKey,
+    doc_count: u64,
+    sub_collector: TCollector,
+}
+
+impl<TKey, TCollector: Collector> Bucket<TKey, TCollector> {
+    pub(crate) fn new(key: TKey, sub_collector: TCollector) -> Bucket<TKey, TCollector> {
+        Bucket {
+            key,
+            doc_count: 0u64,
+            sub_collector,
+        }
+    }
+
+    /// Returns the key identifying the bucket.
+    pub fn key(&self) -> &TKey {
+        &self.key
+    }
+
+    /// Returns the number of documents in the bucket.
+    pub fn doc_count(&self) -> u64 {
+        self.doc_count
+    }
+
+    /// Returns the sub-collector of the bucket.
+    pub fn sub_collector(&self) -> &TCollector {
+        &self.sub_collector
+    }
+
+    /// Consumes the bucket, and returns its sub-collector.
+    pub fn into_sub_collector(self) -> TCollector {
+        self.sub_collector
+    }
+
+    pub(crate) fn sub_collector_mut(&mut self) -> &mut TCollector {
+        &mut self.sub_collector
+    }
+
+    pub(crate) fn collect(&mut self, doc: DocId, score: Score) {
+        self.doc_count += 1;
+        self.sub_collector.collect(doc, score);
+    }
+}
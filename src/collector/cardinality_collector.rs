@@ -0,0 +1,201 @@
+use collector::Collector;
+use fastfield::{FastFieldReader, FastValue};
+use schema::Field;
+
+use DocId;
+use Result;
+use Score;
+use SegmentLocalId;
+use SegmentReader;
+
+/// Default number of bits used to address the registers
+/// of the HyperLogLog sketch.
+///
+/// With `2^14` registers, the standard error is about `0.8%`.
+const DEFAULT_PRECISION: u8 = 14;
+
+/// Mixes the bits of a `u64` (finalizer of `splitmix64`).
+///
+/// Fast field values are often small, dense integers,
+/// and need to be hashed before being fed to the sketch.
+fn hash_u64(val: u64) -> u64 {
+    let mut z = val.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// HyperLogLog sketch, estimating the number of distinct
+/// `u64` values that were inserted.
+struct HyperLogLog {
+    precision: u8,
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    fn with_precision(precision: u8) -> HyperLogLog {
+        HyperLogLog {
+            precision,
+            registers: vec![0u8; 1 << precision],
+        }
+    }
+
+    fn insert(&mut self, val: u64) {
+        let hash = hash_u64(val);
+        let register_id = (hash >> (64 - self.precision)) as usize;
+        // The remaining bits are shifted left so that the leading
+        // zeros count ignores the register bits. The sentinel bit
+        // bounds the rank to `64 - precision + 1`.
+        let remaining = (hash << self.precision) | (1u64 << (self.precision - 1));
+        let rank = (remaining.leading_zeros() + 1) as u8;
+        if rank > self.registers[register_id] {
+            self.registers[register_id] = rank;
+        }
+    }
+
+    fn estimate(&self) -> u64 {
+        let num_registers = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1f64 + 1.079 / num_registers),
+        };
+        let mut sum = 0f64;
+        let mut num_zeros = 0usize;
+        for &register in &self.registers {
+            sum += 1f64 / ((1u64 << register) as f64);
+            if register == 0 {
+                num_zeros += 1;
+            }
+        }
+        let raw_estimate = alpha * num_registers * num_registers / sum;
+        if raw_estimate <= 2.5 * num_registers && num_zeros > 0 {
+            // small range correction (linear counting)
+            (num_registers * (num_registers / (num_zeros as f64)).ln()).round() as u64
+        } else {
+            raw_estimate.round() as u64
+        }
+    }
+}
+
+/// Estimates the number of distinct values of a u64/i64/f64 fast field
+/// over the matching documents, using a HyperLogLog sketch.
+///
+/// The memory usage is fixed, `2^precision` bytes, regardless of the
+/// number of documents.
+/// The standard error of the estimation is about `1.04 / sqrt(2^precision)`.
+///
+/// ```rust,ignore
+/// let mut cardinality_collector = CardinalityCollector::<u64>::new(user_id);
+/// searcher.search(&query, &mut cardinality_collector)?;
+/// println!("{} distinct users", cardinality_collector.cardinality());
+/// ```
+pub struct CardinalityCollector<T: FastValue> {
+    field: Field,
+    sketch: HyperLogLog,
+    ff_reader: Option<T::FastFieldReader>,
+}
+
+impl<T: FastValue> CardinalityCollector<T> {
+    /// Creates a new cardinality collector for the given field,
+    /// with the default precision of 14 bits.
+    pub fn new(field: Field) -> CardinalityCollector<T> {
+        CardinalityCollector::with_precision(field, DEFAULT_PRECISION)
+    }
+
+    /// Creates a new cardinality collector for the given field.
+    ///
+    /// The sketch uses `2^precision` registers.
+    ///
+    /// # Panics
+    /// Panics if `precision` is not within `[4, 16]`.
+    pub fn with_precision(field: Field, precision: u8) -> CardinalityCollector<T> {
+        assert!(
+            precision >= 4 && precision <= 16,
+            "The precision of the cardinality collector must be within [4, 16]."
+        );
+        CardinalityCollector {
+            field,
+            sketch: HyperLogLog::with_precision(precision),
+            ff_reader: None,
+        }
+    }
+
+    /// Returns the estimated number of distinct values.
+    pub fn cardinality(&self) -> u64 {
+        self.sketch.estimate()
+    }
+}
+
+impl<T: FastValue> Collector for CardinalityCollector<T> {
+    fn set_segment(&mut self, _: SegmentLocalId, reader: &SegmentReader) -> Result<()> {
+        self.ff_reader = Some(reader.get_fast_field_reader(self.field)?);
+        Ok(())
+    }
+
+    fn collect(&mut self, doc: DocId, _: Score) {
+        let val = self.ff_reader
+            .as_ref()
+            .expect("collect() was called before set_segment. This should never happen.")
+            .get(doc);
+        self.sketch.insert(val.to_u64());
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{CardinalityCollector, HyperLogLog};
+    use Index;
+    use query::TermQuery;
+    use schema::{IndexRecordOption, SchemaBuilder, Term, FAST, STRING};
+
+    #[test]
+    fn test_hyperloglog() {
+        let mut sketch = HyperLogLog::with_precision(14);
+        assert_eq!(sketch.estimate(), 0);
+        for val in 0..10u64 {
+            sketch.insert(val);
+            sketch.insert(val);
+        }
+        let estimate = sketch.estimate();
+        assert!(estimate >= 9 && estimate <= 11);
+        for val in 0..100_000u64 {
+            sketch.insert(val * 7);
+        }
+        let estimate = sketch.estimate() as f64;
+        assert!((estimate - 100_000f64).abs() < 3_000f64);
+    }
+
+    #[test]
+    fn test_cardinality_collector() {
+        let mut schema_builder = SchemaBuilder::default();
+        let user_id = schema_builder.add_u64_field("user_id", FAST);
+        let text = schema_builder.add_text_field("text", STRING);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            for i in 0..1_000u64 {
+                index_writer.add_document(doc!(text => "a", user_id => i % 30));
+                if i == 500 {
+                    index_writer.commit().unwrap();
+                }
+            }
+            index_writer.add_document(doc!(text => "b", user_id => 1_000u64));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let query = TermQuery::new(Term::from_field_text(text, "a"), IndexRecordOption::Basic);
+        let mut cardinality_collector = CardinalityCollector::<u64>::new(user_id);
+        searcher.search(&query, &mut cardinality_collector).unwrap();
+        let cardinality = cardinality_collector.cardinality();
+        assert!(cardinality >= 29 && cardinality <= 31);
+    }
+}
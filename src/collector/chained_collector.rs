@@ -16,6 +16,10 @@ impl Collector for DoNothingCollector {
     }
     #[inline]
     fn collect(&mut self, _doc: DocId, _score: Score) {}
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
 }
 
 /// Zero-cost abstraction used to collect on multiple collectors.
@@ -52,6 +56,10 @@ impl<Left: Collector, Right: Collector> Collector for ChainedCollector<Left, Rig
         self.left.collect(doc, score);
         self.right.collect(doc, score);
     }
+
+    fn requires_scoring(&self) -> bool {
+        self.left.requires_scoring() || self.right.requires_scoring()
+    }
 }
 
 /// Creates a `ChainedCollector`
@@ -81,4 +89,18 @@ mod tests {
         assert_eq!(count_collector.count(), 3);
         assert!(top_collector.at_capacity());
     }
+
+    #[test]
+    fn test_chained_collector_requires_scoring() {
+        let mut top_collector = TopCollector::with_limit(2);
+        let mut count_collector = CountCollector::default();
+        assert!(!chain().requires_scoring());
+        assert!(!chain().push(&mut count_collector).requires_scoring());
+        assert!(
+            chain()
+                .push(&mut count_collector)
+                .push(&mut top_collector)
+                .requires_scoring()
+        );
+    }
 }
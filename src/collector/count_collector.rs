@@ -33,6 +33,10 @@ impl Collector for CountCollector {
     fn collect(&mut self, _: DocId, _: Score) {
         self.count += 1;
     }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(test)]
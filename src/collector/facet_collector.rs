@@ -52,6 +52,10 @@ where
             .get(doc);
         *(self.counters.entry(val).or_insert(0)) += 1;
     }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(test)]
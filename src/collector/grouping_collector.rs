@@ -0,0 +1,391 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use collector::Collector;
+use fastfield::{F64FastFieldReader, FastFieldReader, FastValue, I64FastFieldReader,
+                U64FastFieldReader};
+use schema::Field;
+
+use DocAddress;
+use DocId;
+use Result;
+use Score;
+use SegmentLocalId;
+use SegmentReader;
+
+/// Order in which documents are sorted by the `GroupingCollector`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Smallest values first.
+    Asc,
+    /// Largest values first.
+    Desc,
+}
+
+/// Reader for the u64, i64 or f64 fast field used to sort documents.
+enum SortFieldReader {
+    U64(U64FastFieldReader),
+    I64(I64FastFieldReader),
+    F64(F64FastFieldReader),
+}
+
+impl SortFieldReader {
+    fn open(reader: &SegmentReader, field: Field) -> Result<SortFieldReader> {
+        if let Ok(ff_reader) = reader.get_fast_field_reader::<I64FastFieldReader>(field) {
+            return Ok(SortFieldReader::I64(ff_reader));
+        }
+        if let Ok(ff_reader) = reader.get_fast_field_reader::<F64FastFieldReader>(field) {
+            return Ok(SortFieldReader::F64(ff_reader));
+        }
+        Ok(SortFieldReader::U64(reader.get_fast_field_reader(field)?))
+    }
+
+    /// Returns the order-preserving `u64` representation of the value.
+    fn get(&self, doc: DocId) -> u64 {
+        match *self {
+            SortFieldReader::U64(ref ff_reader) => ff_reader.get(doc),
+            SortFieldReader::I64(ref ff_reader) => ff_reader.get(doc).to_u64(),
+            SortFieldReader::F64(ref ff_reader) => ff_reader.get(doc).to_u64(),
+        }
+    }
+}
+
+/// Maps a score to a `u64`, preserving its order.
+fn score_to_rank(score: Score) -> u64 {
+    let bits = score.to_bits();
+    let ordered_bits = if bits & 0x8000_0000 != 0 {
+        !bits
+    } else {
+        bits | 0x8000_0000
+    };
+    u64::from(ordered_bits)
+}
+
+/// Document retained by the `GroupingCollector`.
+///
+/// The higher the rank, the better the document.
+/// Rust heap is a max-heap and we need a min heap,
+/// so `Ord` is defined so that the best documents come first.
+#[derive(Clone, Copy)]
+struct RankedDoc {
+    rank: u64,
+    score: Score,
+    doc_address: DocAddress,
+}
+
+impl PartialOrd for RankedDoc {
+    fn partial_cmp(&self, other: &RankedDoc) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RankedDoc {
+    fn cmp(&self, other: &RankedDoc) -> Ordering {
+        other
+            .rank
+            .cmp(&self.rank)
+            .then_with(|| self.doc_address.cmp(&other.doc_address))
+    }
+}
+
+impl PartialEq for RankedDoc {
+    fn eq(&self, other: &RankedDoc) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for RankedDoc {}
+
+struct GroupState {
+    doc_count: u64,
+    best_doc: RankedDoc,
+    heap: BinaryHeap<RankedDoc>,
+}
+
+/// Group of documents returned by the `GroupingCollector`.
+pub struct Group<T: FastValue> {
+    key: T,
+    doc_count: u64,
+    score_docs: Vec<(Score, DocAddress)>,
+}
+
+impl<T: FastValue> Group<T> {
+    /// Returns the value of the grouping field shared
+    /// by the documents of the group.
+    pub fn key(&self) -> T {
+        self.key
+    }
+
+    /// Returns the number of matching documents in the group.
+    pub fn doc_count(&self) -> u64 {
+        self.doc_count
+    }
+
+    /// Returns the top documents of the group, best first.
+    pub fn docs(&self) -> Vec<DocAddress> {
+        self.score_docs
+            .iter()
+            .map(|&(_, doc_address)| doc_address)
+            .collect()
+    }
+
+    /// Returns the top documents of the group with their score, best first.
+    pub fn score_docs(&self) -> &[(Score, DocAddress)] {
+        &self.score_docs[..]
+    }
+}
+
+/// The Grouping Collector groups the matching documents by
+/// the value of a u64/i64/f64 fast field (field collapsing).
+///
+/// It keeps track of the `num_groups` best groups, and of the
+/// `docs_per_group` best documents of each of them.
+/// Groups are ranked by their best document.
+///
+/// Documents are ranked by score, unless a sort field is
+/// set via `.order_by_field(...)`.
+///
+/// All of the groups encountered are kept in memory, with at most
+/// `docs_per_group` documents each.
+///
+/// ```rust,ignore
+/// // at most 2 offers per seller, for the 10 best sellers.
+/// let mut grouping_collector = GroupingCollector::<u64>::new(seller_id, 10, 2);
+/// searcher.search(&query, &mut grouping_collector)?;
+/// for group in grouping_collector.harvest() {
+///     println!("{} {:?}", group.key(), group.docs());
+/// }
+/// ```
+pub struct GroupingCollector<T: FastValue> {
+    group_field: Field,
+    num_groups: usize,
+    docs_per_group: usize,
+    sort_field: Option<(Field, SortOrder)>,
+    groups: HashMap<u64, GroupState>,
+    group_reader: Option<T::FastFieldReader>,
+    sort_reader: Option<SortFieldReader>,
+    segment_id: SegmentLocalId,
+}
+
+impl<T: FastValue> GroupingCollector<T> {
+    /// Creates a new grouping collector.
+    ///
+    /// # Panics
+    /// Panics if `num_groups` or `docs_per_group` is 0.
+    pub fn new(
+        group_field: Field,
+        num_groups: usize,
+        docs_per_group: usize,
+    ) -> GroupingCollector<T> {
+        if num_groups < 1 || docs_per_group < 1 {
+            panic!("The number of groups and of documents per group must be strictly positive.");
+        }
+        GroupingCollector {
+            group_field,
+            num_groups,
+            docs_per_group,
+            sort_field: None,
+            groups: HashMap::new(),
+            group_reader: None,
+            sort_reader: None,
+            segment_id: 0,
+        }
+    }
+
+    /// Ranks the documents by the value of a u64/i64/f64 fast field
+    /// rather than by score.
+    ///
+    /// Scores are then not computed, and the scores returned
+    /// with the documents are meaningless.
+    pub fn order_by_field(&mut self, sort_field: Field, order: SortOrder) {
+        self.sort_field = Some((sort_field, order));
+    }
+
+    /// Returns the best groups, best first.
+    ///
+    /// Ties are broken by document address.
+    pub fn harvest(self) -> Vec<Group<T>> {
+        let mut groups: Vec<(u64, GroupState)> = self.groups.into_iter().collect();
+        groups.sort_by(|&(_, ref left), &(_, ref right)| left.best_doc.cmp(&right.best_doc));
+        groups.truncate(self.num_groups);
+        groups
+            .into_iter()
+            .map(|(key, group_state)| {
+                let score_docs = group_state
+                    .heap
+                    .into_sorted_vec()
+                    .into_iter()
+                    .map(|ranked_doc| (ranked_doc.score, ranked_doc.doc_address))
+                    .collect();
+                Group {
+                    key: T::from_u64(key),
+                    doc_count: group_state.doc_count,
+                    score_docs,
+                }
+            })
+            .collect()
+    }
+}
+
+impl<T: FastValue> Collector for GroupingCollector<T> {
+    fn set_segment(&mut self, segment_id: SegmentLocalId, reader: &SegmentReader) -> Result<()> {
+        self.segment_id = segment_id;
+        self.group_reader = Some(reader.get_fast_field_reader(self.group_field)?);
+        if let Some((sort_field, _)) = self.sort_field {
+            self.sort_reader = Some(SortFieldReader::open(reader, sort_field)?);
+        }
+        Ok(())
+    }
+
+    fn collect(&mut self, doc: DocId, score: Score) {
+        let group_key = self.group_reader
+            .as_ref()
+            .expect("collect() was called before set_segment. This should never happen.")
+            .get(doc)
+            .to_u64();
+        let rank = match (self.sort_field, self.sort_reader.as_ref()) {
+            (Some((_, SortOrder::Desc)), Some(sort_reader)) => sort_reader.get(doc),
+            (Some((_, SortOrder::Asc)), Some(sort_reader)) => !sort_reader.get(doc),
+            _ => score_to_rank(score),
+        };
+        let ranked_doc = RankedDoc {
+            rank,
+            score,
+            doc_address: DocAddress(self.segment_id, doc),
+        };
+        let docs_per_group = self.docs_per_group;
+        let group_state = self.groups
+            .entry(group_key)
+            .or_insert_with(|| GroupState {
+                doc_count: 0,
+                best_doc: ranked_doc,
+                heap: BinaryHeap::with_capacity(docs_per_group),
+            });
+        group_state.doc_count += 1;
+        if ranked_doc < group_state.best_doc {
+            group_state.best_doc = ranked_doc;
+        }
+        if group_state.heap.len() < docs_per_group {
+            group_state.heap.push(ranked_doc);
+        } else {
+            let mut worst_doc = group_state
+                .heap
+                .peek_mut()
+                .expect("Groups with 0 documents are forbidden.");
+            if ranked_doc < *worst_doc {
+                *worst_doc = ranked_doc;
+            }
+        }
+    }
+
+    fn requires_scoring(&self) -> bool {
+        self.sort_field.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{GroupingCollector, SortOrder};
+    use DocAddress;
+    use Index;
+    use Score;
+    use collector::Collector;
+    use query::TermQuery;
+    use schema::{IndexRecordOption, SchemaBuilder, Term, FAST, INT_STORED, STRING};
+
+    #[test]
+    fn test_grouping_collector_scores() {
+        let mut schema_builder = SchemaBuilder::default();
+        let seller = schema_builder.add_u64_field("seller", FAST);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            for &seller_id in &[1u64, 2, 1, 3, 1, 2] {
+                index_writer.add_document(doc!(seller => seller_id));
+            }
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let segment_reader = &searcher.segment_readers()[0];
+        let mut grouping_collector = GroupingCollector::<u64>::new(seller, 2, 2);
+        grouping_collector.set_segment(0, segment_reader).unwrap();
+        let scores: [Score; 6] = [0.5, 0.7, 0.9, 0.8, 0.1, 0.2];
+        for (doc, &score) in scores.iter().enumerate() {
+            grouping_collector.collect(doc as u32, score);
+        }
+        let groups = grouping_collector.harvest();
+        assert_eq!(groups.len(), 2);
+        // seller 1 has the best document (0.9), then seller 3 (0.8).
+        assert_eq!(groups[0].key(), 1u64);
+        assert_eq!(groups[0].doc_count(), 3);
+        let docs: Vec<(Score, u32)> = groups[0]
+            .score_docs()
+            .iter()
+            .map(|&(score, doc_address)| (score, doc_address.doc()))
+            .collect();
+        assert_eq!(docs, vec![(0.9, 2), (0.5, 0)]);
+        assert_eq!(groups[1].key(), 3u64);
+        assert_eq!(groups[1].doc_count(), 1);
+        assert_eq!(groups[1].docs().len(), 1);
+    }
+
+    #[test]
+    fn test_grouping_collector_sort_field() {
+        let mut schema_builder = SchemaBuilder::default();
+        let seller = schema_builder.add_i64_field("seller", FAST);
+        let price = schema_builder.add_u64_field("price", FAST | INT_STORED);
+        let all = schema_builder.add_text_field("all", STRING);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(all => "all", seller => -1i64, price => 30u64));
+            index_writer.add_document(doc!(all => "all", seller => 2i64, price => 12u64));
+            index_writer.add_document(doc!(all => "all", seller => -1i64, price => 8u64));
+            index_writer.commit().unwrap();
+            index_writer.add_document(doc!(all => "all", seller => 2i64, price => 10u64));
+            index_writer.add_document(doc!(all => "all", seller => 7i64, price => 15u64));
+            index_writer.add_document(doc!(all => "all", seller => -1i64, price => 20u64));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let query = TermQuery::new(Term::from_field_text(all, "all"), IndexRecordOption::Basic);
+        let prices = |group_docs: Vec<DocAddress>| -> Vec<u64> {
+            group_docs
+                .into_iter()
+                .map(|doc_address| {
+                    let doc = searcher.doc(&doc_address).unwrap();
+                    doc.get_first(price).unwrap().u64_value()
+                })
+                .collect()
+        };
+        {
+            // cheapest offers first.
+            let mut grouping_collector = GroupingCollector::<i64>::new(seller, 10, 2);
+            grouping_collector.order_by_field(price, SortOrder::Asc);
+            searcher.search(&query, &mut grouping_collector).unwrap();
+            let groups = grouping_collector.harvest();
+            let keys: Vec<i64> = groups.iter().map(|group| group.key()).collect();
+            assert_eq!(keys, vec![-1i64, 2i64, 7i64]);
+            let counts: Vec<u64> = groups.iter().map(|group| group.doc_count()).collect();
+            assert_eq!(counts, vec![3, 2, 1]);
+            assert_eq!(prices(groups[0].docs()), vec![8, 20]);
+            assert_eq!(prices(groups[1].docs()), vec![10, 12]);
+        }
+        {
+            // most expensive offer first, one offer per seller.
+            let mut grouping_collector = GroupingCollector::<i64>::new(seller, 2, 1);
+            grouping_collector.order_by_field(price, SortOrder::Desc);
+            searcher.search(&query, &mut grouping_collector).unwrap();
+            let groups = grouping_collector.harvest();
+            let keys: Vec<i64> = groups.iter().map(|group| group.key()).collect();
+            assert_eq!(keys, vec![-1i64, 7i64]);
+            assert_eq!(prices(groups[0].docs()), vec![30]);
+            assert_eq!(prices(groups[1].docs()), vec![15]);
+        }
+    }
+}
@@ -0,0 +1,368 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use collector::Collector;
+use fastfield::FacetReader;
+use schema::{Facet, Field, Term};
+use schema::FACET_SEP_BYTE;
+use termdict::{TermDictionary, TermOrdinal, TermStreamer, TermStreamerBuilder};
+
+use DocId;
+use Result;
+use Score;
+use SegmentLocalId;
+use SegmentReader;
+
+/// Collector for hierarchical facet counts.
+///
+/// For each of the facets registered via `.add_facet(...)`,
+/// the collector counts the number of matching documents
+/// belonging to each of its direct children.
+///
+/// Facet counts are computed on term ordinals, using the
+/// facet fast field, so that the `Facet` themselves
+/// are only materialized once per segment.
+///
+/// ```rust,ignore
+/// let mut facet_collector = HierarchicalFacetCollector::for_field(category);
+/// facet_collector.add_facet("/electronics");
+/// searcher.search(&query, &mut facet_collector)?;
+/// let facet_counts = facet_collector.harvest();
+/// for (facet, count) in facet_counts.top_k("/electronics", 10) {
+///     println!("{} {}", facet, count);
+/// }
+/// ```
+///
+/// Drilling down into a facet is simply done by
+/// querying for `Term::from_facet(field, &facet)`.
+pub struct HierarchicalFacetCollector {
+    field: Field,
+    facets: BTreeSet<Facet>,
+    facet_reader: Option<FacetReader>,
+    ord_to_slot: HashMap<TermOrdinal, usize>,
+    segment_facets: Vec<Facet>,
+    segment_counts: Vec<u64>,
+    facet_counts: BTreeMap<Facet, u64>,
+    ords_buffer: Vec<u64>,
+}
+
+impl HierarchicalFacetCollector {
+    /// Creates a new hierarchical facet collector
+    /// for the given hierarchical facet field.
+    pub fn for_field(field: Field) -> HierarchicalFacetCollector {
+        HierarchicalFacetCollector {
+            field,
+            facets: BTreeSet::new(),
+            facet_reader: None,
+            ord_to_slot: HashMap::new(),
+            segment_facets: Vec::new(),
+            segment_counts: Vec::new(),
+            facet_counts: BTreeMap::new(),
+            ords_buffer: Vec::new(),
+        }
+    }
+
+    /// Requests the counts of the direct children of `facet`.
+    ///
+    /// Adding the root facet `/` returns the counts of the
+    /// top-level facets.
+    pub fn add_facet<T: Into<Facet>>(&mut self, facet: T) {
+        self.facets.insert(facet.into());
+    }
+
+    /// Adds the counts of the current segment to the overall counts.
+    fn flush_segment_counts(&mut self) {
+        for (facet, &count) in self.segment_facets.iter().zip(self.segment_counts.iter()) {
+            if count > 0 {
+                *self.facet_counts.entry(facet.clone()).or_insert(0) += count;
+            }
+        }
+        self.ord_to_slot.clear();
+        self.segment_facets.clear();
+        self.segment_counts.clear();
+    }
+
+    /// Registers the direct children of `facet` present in the segment
+    /// term dictionary.
+    fn register_children(&mut self, facet: &Facet) {
+        let facet_reader = self.facet_reader
+            .as_ref()
+            .expect("The facet reader should have been set.");
+        let facet_dict = facet_reader.facet_dict();
+        let facet_term = Term::from_facet(self.field, facet);
+        let prefix: &[u8] = facet_term.as_slice();
+        // the children of a facet are the terms starting by
+        // the facet followed by a separator.
+        let mut children_prefix = prefix.to_vec();
+        if !facet.is_root() {
+            children_prefix.push(FACET_SEP_BYTE);
+        }
+        let stream_builder = facet_dict.range().ge(&children_prefix);
+        let mut stream = if facet.is_root() {
+            // the dictionary only contains the terms of our field.
+            stream_builder.into_stream()
+        } else {
+            let mut children_upper_bound = prefix.to_vec();
+            children_upper_bound.push(FACET_SEP_BYTE + 1);
+            stream_builder.lt(&children_upper_bound).into_stream()
+        };
+        while stream.advance() {
+            let suffix = &stream.key()[children_prefix.len()..];
+            if suffix.contains(&FACET_SEP_BYTE) {
+                // deeper descendant.
+                continue;
+            }
+            // the first 4 bytes of the term encode the field.
+            let encoded = String::from_utf8_lossy(&stream.key()[4..]).into_owned();
+            self.ord_to_slot
+                .insert(stream.term_ord(), self.segment_facets.len());
+            self.segment_facets.push(Facet::from_encoded(encoded));
+            self.segment_counts.push(0);
+        }
+    }
+
+    /// Returns the facet counts collected.
+    pub fn harvest(mut self) -> FacetCounts {
+        self.flush_segment_counts();
+        FacetCounts {
+            facet_counts: self.facet_counts,
+        }
+    }
+}
+
+impl Collector for HierarchicalFacetCollector {
+    fn set_segment(&mut self, _: SegmentLocalId, reader: &SegmentReader) -> Result<()> {
+        self.flush_segment_counts();
+        self.facet_reader = Some(reader.facet_reader(self.field)?);
+        let facets: Vec<Facet> = self.facets.iter().cloned().collect();
+        for facet in &facets {
+            self.register_children(facet);
+        }
+        Ok(())
+    }
+
+    fn collect(&mut self, doc: DocId, _: Score) {
+        self.facet_reader
+            .as_ref()
+            .expect("collect() was called before set_segment. This should never happen.")
+            .facet_ords(doc, &mut self.ords_buffer);
+        for facet_ord in &self.ords_buffer {
+            if let Some(&slot) = self.ord_to_slot.get(facet_ord) {
+                self.segment_counts[slot] += 1;
+            }
+        }
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+}
+
+/// Facet counts harvested from a `HierarchicalFacetCollector`.
+pub struct FacetCounts {
+    facet_counts: BTreeMap<Facet, u64>,
+}
+
+impl FacetCounts {
+    /// Returns the direct children of `facet` with their
+    /// document count, in the facet order.
+    ///
+    /// Children with a count of 0 are omitted.
+    pub fn get<T: Into<Facet>>(&self, facet: T) -> Vec<(&Facet, u64)> {
+        let facet: Facet = facet.into();
+        self.facet_counts
+            .iter()
+            .filter(|&(child, _)| facet.is_parent_of(child))
+            .map(|(child, &count)| (child, count))
+            .collect()
+    }
+
+    /// Returns the `k` children of `facet` with the highest
+    /// document count, sorted by decreasing count.
+    ///
+    /// Ties are broken by facet order.
+    pub fn top_k<T: Into<Facet>>(&self, facet: T, k: usize) -> Vec<(&Facet, u64)> {
+        let mut children = self.get(facet);
+        children.sort_by(|&(left_facet, left_count), &(right_facet, right_count)| {
+            match right_count.cmp(&left_count) {
+                Ordering::Equal => left_facet.cmp(right_facet),
+                ordering => ordering,
+            }
+        });
+        children.truncate(k);
+        children
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::HierarchicalFacetCollector;
+    use Index;
+    use collector::{chain, CountCollector};
+    use futures::Future;
+    use query::TermQuery;
+    use schema::{Facet, IndexRecordOption, SchemaBuilder, Term, STRING};
+
+    #[test]
+    fn test_hierarchical_facet_collector() {
+        let mut schema_builder = SchemaBuilder::default();
+        let category = schema_builder.add_facet_field("category");
+        let all = schema_builder.add_text_field("all", STRING);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            let facets_per_segment = vec![
+                vec![
+                    vec!["/electronics/phones/android"],
+                    vec!["/electronics/phones/iphone"],
+                    vec!["/electronics/tv"],
+                ],
+                vec![
+                    vec!["/books/fiction"],
+                    vec!["/electronics/phones/android", "/books/scifi"],
+                    vec!["/electronics/tv"],
+                ],
+            ];
+            for segment_facets in facets_per_segment {
+                for doc_facets in segment_facets {
+                    let mut doc = doc!(all => "all");
+                    for facet in doc_facets {
+                        doc.add_facet(category, Facet::from_text(facet));
+                    }
+                    index_writer.add_document(doc);
+                }
+                index_writer.commit().unwrap();
+            }
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        {
+            let query = TermQuery::new(Term::from_field_text(all, "all"), IndexRecordOption::Basic);
+            let mut facet_collector = HierarchicalFacetCollector::for_field(category);
+            facet_collector.add_facet("/");
+            facet_collector.add_facet("/electronics");
+            searcher.search(&query, &mut facet_collector).unwrap();
+            let facet_counts = facet_collector.harvest();
+            let root_counts: Vec<(String, u64)> = facet_counts
+                .get("/")
+                .into_iter()
+                .map(|(facet, count)| (facet.to_string(), count))
+                .collect();
+            assert_eq!(
+                root_counts,
+                vec![("/books".to_string(), 2), ("/electronics".to_string(), 5)]
+            );
+            let electronics_counts: Vec<(String, u64)> = facet_counts
+                .get("/electronics")
+                .into_iter()
+                .map(|(facet, count)| (facet.to_string(), count))
+                .collect();
+            assert_eq!(
+                electronics_counts,
+                vec![
+                    ("/electronics/phones".to_string(), 3),
+                    ("/electronics/tv".to_string(), 2),
+                ]
+            );
+            let top_1: Vec<(String, u64)> = facet_counts
+                .top_k("/", 1)
+                .into_iter()
+                .map(|(facet, count)| (facet.to_string(), count))
+                .collect();
+            assert_eq!(top_1, vec![("/electronics".to_string(), 5)]);
+            // facets that were not requested are not counted.
+            assert!(facet_counts.get("/electronics/phones").is_empty());
+        }
+        {
+            // drill down
+            let phones = Facet::from_text("/electronics/phones");
+            let query = TermQuery::new(Term::from_facet(category, &phones), IndexRecordOption::Basic);
+            let mut count_collector = CountCollector::default();
+            let mut facet_collector = HierarchicalFacetCollector::for_field(category);
+            facet_collector.add_facet(phones);
+            {
+                let mut collectors = chain().push(&mut count_collector).push(&mut facet_collector);
+                searcher.search(&query, &mut collectors).unwrap();
+            }
+            assert_eq!(count_collector.count(), 3);
+            let facet_counts = facet_collector.harvest();
+            let top_k: Vec<(String, u64)> = facet_counts
+                .top_k("/electronics/phones", 10)
+                .into_iter()
+                .map(|(facet, count)| (facet.to_string(), count))
+                .collect();
+            assert_eq!(
+                top_k,
+                vec![
+                    ("/electronics/phones/android".to_string(), 2),
+                    ("/electronics/phones/iphone".to_string(), 1),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_hierarchical_facet_collector_after_merge() {
+        let mut schema_builder = SchemaBuilder::default();
+        let category = schema_builder.add_facet_field("category");
+        let all = schema_builder.add_text_field("all", STRING);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            let mut doc = doc!(all => "all");
+            doc.add_facet(category, Facet::from_text("/b/c"));
+            index_writer.add_document(doc);
+            let mut doc = doc!(all => "all");
+            doc.add_facet(category, Facet::from_text("/a/x"));
+            index_writer.add_document(doc);
+            index_writer.commit().unwrap();
+            let mut doc = doc!(all => "all");
+            doc.add_facet(category, Facet::from_text("/a/y"));
+            index_writer.add_document(doc);
+            let mut doc = doc!(all => "all");
+            doc.add_facet(category, Facet::from_text("/c"));
+            index_writer.add_document(doc);
+            index_writer.commit().unwrap();
+            index_writer.delete_term(Term::from_facet(category, &Facet::from_text("/b/c")));
+            index_writer.commit().unwrap();
+        }
+        {
+            let segment_ids = index
+                .searchable_segment_ids()
+                .expect("Searchable segments failed.");
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer
+                .merge(&segment_ids)
+                .wait()
+                .expect("Merging failed");
+            index_writer.wait_merging_threads().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        let query = TermQuery::new(Term::from_field_text(all, "all"), IndexRecordOption::Basic);
+        let mut facet_collector = HierarchicalFacetCollector::for_field(category);
+        facet_collector.add_facet("/");
+        facet_collector.add_facet("/a");
+        searcher.search(&query, &mut facet_collector).unwrap();
+        let facet_counts = facet_collector.harvest();
+        let counts: Vec<(String, u64)> = facet_counts
+            .get("/")
+            .into_iter()
+            .chain(facet_counts.get("/a").into_iter())
+            .map(|(facet, count)| (facet.to_string(), count))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                ("/a".to_string(), 2),
+                ("/c".to_string(), 1),
+                ("/a/x".to_string(), 1),
+                ("/a/y".to_string(), 1),
+            ]
+        );
+    }
+}
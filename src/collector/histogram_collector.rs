@@ -0,0 +1,452 @@
+use std::collections::BTreeMap;
+
+use collector::{Bucket, Collector, DoNothingCollector};
+use error::ErrorKind;
+use fastfield::{FastFieldReader, FastValue};
+use schema::Field;
+
+use DocId;
+use Result;
+use Score;
+use SegmentLocalId;
+use SegmentReader;
+
+/// Fast field value types that a `HistogramCollector` can aggregate.
+///
+/// Buckets are computed on the `u64` representation of the values,
+/// which is only meaningful for integers.
+pub trait HistogramValue: FastValue {}
+
+impl HistogramValue for u64 {}
+
+impl HistogramValue for i64 {}
+
+/// Default maximum number of buckets of a histogram.
+const DEFAULT_MAX_BUCKETS: u64 = 10_000;
+
+/// Histogram aggregation collector for u64/i64 fast fields.
+///
+/// Documents are dispatched into fixed-size buckets
+/// according to the value of their fast field.
+/// The bucket of a value `val` is identified by its lower bound,
+/// `floor((val - offset) / interval) * interval + offset`.
+///
+/// Each bucket has its own sub-collector, created
+/// by the factory given in `.with_sub_collector(...)`.
+///
+/// By default (`min_doc_count == 0`), empty buckets between the
+/// smallest and the largest bucket are returned as well.
+/// Extended bounds make it possible to force the histogram to cover
+/// a given range of values, even if no document falls there.
+/// The number of buckets is limited to `10_000` by default,
+/// see `.set_max_buckets(...)`.
+///
+/// ```rust,ignore
+/// let mut histogram_collector = HistogramCollector::<i64>::new(timestamp, 86_400);
+/// searcher.search(&query, &mut histogram_collector)?;
+/// for bucket in histogram_collector.into_buckets()? {
+///     println!("{} {}", bucket.key(), bucket.doc_count());
+/// }
+/// ```
+pub struct HistogramCollector<T: HistogramValue, TCollector: Collector = DoNothingCollector> {
+    field: Field,
+    interval: u64,
+    offset: u64,
+    min_doc_count: u64,
+    extended_bounds: Option<(u64, u64)>,
+    max_buckets: u64,
+    buckets: BTreeMap<u64, Bucket<T, TCollector>>,
+    ff_reader: Option<T::FastFieldReader>,
+    segment: Option<(SegmentLocalId, SegmentReader)>,
+    sub_collector_factory: Box<Fn() -> TCollector>,
+}
+
+impl<T: HistogramValue> HistogramCollector<T, DoNothingCollector> {
+    /// Creates a new histogram collector for aggregating a given field
+    /// into buckets of size `interval`.
+    ///
+    /// # Panics
+    /// Panics if `interval` is 0.
+    pub fn new(field: Field, interval: u64) -> HistogramCollector<T, DoNothingCollector> {
+        HistogramCollector::with_sub_collector(field, interval, || DoNothingCollector)
+    }
+}
+
+impl<T: HistogramValue, TCollector: Collector> HistogramCollector<T, TCollector> {
+    /// Creates a new histogram collector for aggregating a given field
+    /// into buckets of size `interval`.
+    ///
+    /// `sub_collector_factory` is called to create the sub-collector
+    /// of each of the buckets.
+    ///
+    /// # Panics
+    /// Panics if `interval` is 0.
+    pub fn with_sub_collector<F>(
+        field: Field,
+        interval: u64,
+        sub_collector_factory: F,
+    ) -> HistogramCollector<T, TCollector>
+    where
+        F: Fn() -> TCollector + 'static,
+    {
+        assert!(interval > 0, "The histogram interval must be strictly positive.");
+        HistogramCollector {
+            field,
+            interval,
+            offset: T::default().to_u64(),
+            min_doc_count: 0,
+            extended_bounds: None,
+            max_buckets: DEFAULT_MAX_BUCKETS,
+            buckets: BTreeMap::new(),
+            ff_reader: None,
+            segment: None,
+            sub_collector_factory: Box::new(sub_collector_factory),
+        }
+    }
+
+    /// Shifts the bucket boundaries by `offset`.
+    ///
+    /// Defaults to 0.
+    pub fn set_offset(&mut self, offset: T) {
+        self.offset = offset.to_u64();
+    }
+
+    /// Buckets with less than `min_doc_count` documents are
+    /// not returned.
+    ///
+    /// Defaults to 0.
+    pub fn set_min_doc_count(&mut self, min_doc_count: u64) {
+        self.min_doc_count = min_doc_count;
+    }
+
+    /// Forces the histogram to cover the values from `min` to `max`.
+    ///
+    /// Only relevant if `min_doc_count` is 0.
+    pub fn set_extended_bounds(&mut self, min: T, max: T) {
+        self.extended_bounds = Some((min.to_u64(), max.to_u64()));
+    }
+
+    /// Sets the maximum number of buckets of the histogram,
+    /// empty buckets included.
+    ///
+    /// Defaults to `10_000`.
+    pub fn set_max_buckets(&mut self, max_buckets: u64) {
+        self.max_buckets = max_buckets;
+    }
+
+    /// Returns the smallest bucket boundary in the `u64` representation.
+    fn first_boundary(&self) -> u64 {
+        self.offset % self.interval
+    }
+
+    /// Returns the index of the bucket containing the
+    /// given `u64` representation of a value.
+    ///
+    /// Buckets are numbered from the one containing `0`, so that
+    /// their indexes are ordered like their values.
+    fn bucket_idx(&self, val: u64) -> u64 {
+        let first_boundary = self.first_boundary();
+        if first_boundary == 0 {
+            val / self.interval
+        } else if val < first_boundary {
+            0
+        } else {
+            // `interval > first_boundary > 0`, so this cannot overflow.
+            (val - first_boundary) / self.interval + 1
+        }
+    }
+
+    /// Returns the lower bound of the bucket with the given index.
+    fn bucket_key(&self, bucket_idx: u64) -> T {
+        let first_boundary = self.first_boundary();
+        let val = if first_boundary == 0 {
+            bucket_idx * self.interval
+        } else {
+            // the lower bound of the first bucket is below `0`,
+            // and wraps around like the offset.
+            first_boundary
+                .wrapping_sub(self.interval)
+                .wrapping_add(bucket_idx.wrapping_mul(self.interval))
+        };
+        T::from_u64(val)
+    }
+
+    /// Consumes the collector, and returns its buckets,
+    /// sorted by increasing key.
+    ///
+    /// # Errors
+    /// Returns an error if the histogram has more buckets than
+    /// the limit set with `.set_max_buckets(...)`.
+    pub fn into_buckets(mut self) -> Result<Vec<Bucket<T, TCollector>>> {
+        if self.min_doc_count == 0 {
+            self.fill_empty_buckets()?;
+        }
+        if self.buckets.len() as u64 > self.max_buckets {
+            bail!(self.too_many_buckets_error(self.buckets.len() as u64));
+        }
+        let min_doc_count = self.min_doc_count;
+        Ok(self.buckets
+            .into_iter()
+            .map(|(_, bucket)| bucket)
+            .filter(|bucket| bucket.doc_count() >= min_doc_count)
+            .collect())
+    }
+
+    fn too_many_buckets_error(&self, num_buckets: u64) -> ErrorKind {
+        ErrorKind::InvalidArgument(format!(
+            "The histogram has {} buckets, which exceeds the limit of {} buckets.",
+            num_buckets, self.max_buckets
+        ))
+    }
+
+    /// Adds the empty buckets between the first and the last bucket,
+    /// extended to the extended bounds if any.
+    ///
+    /// Returns an error, before creating any bucket, if the
+    /// histogram would exceed the maximum number of buckets.
+    fn fill_empty_buckets(&mut self) -> Result<()> {
+        let mut bounds: Option<(u64, u64)> =
+            match (self.buckets.keys().next(), self.buckets.keys().next_back()) {
+                (Some(&first), Some(&last)) => Some((first, last)),
+                _ => None,
+            };
+        if let Some((min_val, max_val)) = self.extended_bounds {
+            let (min_idx, max_idx) = (self.bucket_idx(min_val), self.bucket_idx(max_val));
+            bounds = Some(match bounds {
+                Some((first, last)) => (first.min(min_idx), last.max(max_idx)),
+                None => (min_idx, max_idx),
+            });
+        }
+        if let Some((first, last)) = bounds {
+            let num_buckets = (last - first).saturating_add(1);
+            if num_buckets > self.max_buckets {
+                bail!(self.too_many_buckets_error(num_buckets));
+            }
+            for bucket_idx in (0..num_buckets).map(|ord| first + ord) {
+                if !self.buckets.contains_key(&bucket_idx) {
+                    let sub_collector = (self.sub_collector_factory)();
+                    let bucket = Bucket::new(self.bucket_key(bucket_idx), sub_collector);
+                    self.buckets.insert(bucket_idx, bucket);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: HistogramValue, TCollector: Collector> Collector for HistogramCollector<T, TCollector> {
+    fn set_segment(
+        &mut self,
+        segment_local_id: SegmentLocalId,
+        reader: &SegmentReader,
+    ) -> Result<()> {
+        self.ff_reader = Some(reader.get_fast_field_reader(self.field)?);
+        for bucket in self.buckets.values_mut() {
+            bucket
+                .sub_collector_mut()
+                .set_segment(segment_local_id, reader)?;
+        }
+        // Buckets are created lazily in `.collect(...)`, where errors cannot
+        // be reported. We make sure that a new sub-collector can be set on
+        // this segment beforehand.
+        (self.sub_collector_factory)().set_segment(segment_local_id, reader)?;
+        self.segment = Some((segment_local_id, reader.clone()));
+        Ok(())
+    }
+
+    fn collect(&mut self, doc: DocId, score: Score) {
+        let val = self.ff_reader
+            .as_ref()
+            .expect("collect() was called before set_segment. This should never happen.")
+            .get(doc)
+            .to_u64();
+        let bucket_idx = self.bucket_idx(val);
+        if !self.buckets.contains_key(&bucket_idx) {
+            let mut sub_collector = (self.sub_collector_factory)();
+            {
+                let &(segment_local_id, ref segment_reader) = self.segment
+                    .as_ref()
+                    .expect("collect() was called before set_segment. This should never happen.");
+                sub_collector
+                    .set_segment(segment_local_id, segment_reader)
+                    .expect("Failed to set the segment of a histogram sub-collector.");
+            }
+            let bucket = Bucket::new(self.bucket_key(bucket_idx), sub_collector);
+            self.buckets.insert(bucket_idx, bucket);
+        }
+        self.buckets
+            .get_mut(&bucket_idx)
+            .expect("The bucket was just created.")
+            .collect(doc, score);
+    }
+
+    fn requires_scoring(&self) -> bool {
+        // buckets are created lazily, so we need to ask a fresh sub-collector.
+        (self.sub_collector_factory)().requires_scoring()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::HistogramCollector;
+    use Index;
+    use collector::CountCollector;
+    use query::TermQuery;
+    use schema::{IndexRecordOption, SchemaBuilder, Term, FAST, STRING};
+
+    #[test]
+    fn test_histogram_collector() {
+        let mut schema_builder = SchemaBuilder::default();
+        let temperature = schema_builder.add_i64_field("temperature", FAST);
+        let all = schema_builder.add_text_field("all", STRING);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(all => "all", temperature => -5i64));
+            index_writer.add_document(doc!(all => "all", temperature => 3i64));
+            index_writer.commit().unwrap();
+            index_writer.add_document(doc!(all => "all", temperature => 7i64));
+            index_writer.add_document(doc!(all => "all", temperature => 12i64));
+            index_writer.add_document(doc!(all => "all", temperature => 31i64));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let query = TermQuery::new(Term::from_field_text(all, "all"), IndexRecordOption::Basic);
+        let histogram = |min_doc_count: u64, extended_bounds: Option<(i64, i64)>| {
+            let mut histogram_collector =
+                HistogramCollector::<i64, _>::with_sub_collector(temperature, 10, || {
+                    CountCollector::default()
+                });
+            histogram_collector.set_min_doc_count(min_doc_count);
+            if let Some((min, max)) = extended_bounds {
+                histogram_collector.set_extended_bounds(min, max);
+            }
+            searcher.search(&query, &mut histogram_collector).unwrap();
+            histogram_collector
+                .into_buckets()
+                .unwrap()
+                .into_iter()
+                .map(|bucket| {
+                    assert_eq!(bucket.sub_collector().count() as u64, bucket.doc_count());
+                    (*bucket.key(), bucket.doc_count())
+                })
+                .collect::<Vec<(i64, u64)>>()
+        };
+        assert_eq!(
+            histogram(0, None),
+            vec![(-10, 1), (0, 2), (10, 1), (20, 0), (30, 1)]
+        );
+        assert_eq!(histogram(1, None), vec![(-10, 1), (0, 2), (10, 1), (30, 1)]);
+        assert_eq!(
+            histogram(0, Some((-25, 45))),
+            vec![
+                (-30, 0),
+                (-20, 0),
+                (-10, 1),
+                (0, 2),
+                (10, 1),
+                (20, 0),
+                (30, 1),
+                (40, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_histogram_collector_offset() {
+        let mut schema_builder = SchemaBuilder::default();
+        let price = schema_builder.add_u64_field("price", FAST);
+        let all = schema_builder.add_text_field("all", STRING);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            for val in &[5u64, 6u64, 9u64, 14u64, 27u64] {
+                index_writer.add_document(doc!(all => "all", price => *val));
+            }
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let query = TermQuery::new(Term::from_field_text(all, "all"), IndexRecordOption::Basic);
+        let mut histogram_collector = HistogramCollector::<u64>::new(price, 5);
+        histogram_collector.set_offset(4);
+        histogram_collector.set_min_doc_count(1);
+        searcher.search(&query, &mut histogram_collector).unwrap();
+        let buckets: Vec<(u64, u64)> = histogram_collector
+            .into_buckets()
+            .unwrap()
+            .into_iter()
+            .map(|bucket| (*bucket.key(), bucket.doc_count()))
+            .collect();
+        assert_eq!(buckets, vec![(4, 2), (9, 1), (14, 1), (24, 1)]);
+    }
+
+    #[test]
+    fn test_histogram_collector_max_buckets() {
+        let mut schema_builder = SchemaBuilder::default();
+        let price = schema_builder.add_u64_field("price", FAST);
+        let all = schema_builder.add_text_field("all", STRING);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(all => "all", price => 0u64));
+            index_writer.add_document(doc!(all => "all", price => 1_000_000u64));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let query = TermQuery::new(Term::from_field_text(all, "all"), IndexRecordOption::Basic);
+        let histogram = |interval: u64, min_doc_count: u64, max_buckets: Option<u64>| {
+            let mut histogram_collector = HistogramCollector::<u64>::new(price, interval);
+            histogram_collector.set_min_doc_count(min_doc_count);
+            if let Some(max_buckets) = max_buckets {
+                histogram_collector.set_max_buckets(max_buckets);
+            }
+            searcher.search(&query, &mut histogram_collector).unwrap();
+            histogram_collector.into_buckets().map(|buckets| buckets.len())
+        };
+        assert!(histogram(1, 0, None).is_err());
+        assert_eq!(histogram(1, 1, None).unwrap(), 2);
+        assert!(histogram(1, 1, Some(1)).is_err());
+        assert_eq!(histogram(100_000, 0, None).unwrap(), 11);
+        assert!(histogram(100_000, 0, Some(10)).is_err());
+    }
+
+    #[test]
+    fn test_histogram_collector_large_values() {
+        let mut schema_builder = SchemaBuilder::default();
+        let price = schema_builder.add_u64_field("price", FAST);
+        let all = schema_builder.add_text_field("all", STRING);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(all => "all", price => u64::max_value()));
+            index_writer.add_document(doc!(all => "all", price => 1u64 << 63));
+            index_writer.add_document(doc!(all => "all", price => 7u64));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let query = TermQuery::new(Term::from_field_text(all, "all"), IndexRecordOption::Basic);
+        let bucket_keys = |interval: u64, offset: u64| {
+            let mut histogram_collector = HistogramCollector::<u64>::new(price, interval);
+            histogram_collector.set_offset(offset);
+            histogram_collector.set_min_doc_count(1);
+            searcher.search(&query, &mut histogram_collector).unwrap();
+            histogram_collector
+                .into_buckets()
+                .unwrap()
+                .into_iter()
+                .map(|bucket| *bucket.key())
+                .collect::<Vec<u64>>()
+        };
+        assert_eq!(bucket_keys(1, 0), vec![7, 1 << 63, u64::max_value()]);
+        assert_eq!(bucket_keys(10, 5), vec![5, (1 << 63) - 3, u64::max_value()]);
+    }
+}
@@ -20,8 +20,35 @@ pub use self::top_collector::TopCollector;
 mod facet_collector;
 pub use self::facet_collector::FacetCollector;
 
+mod hierarchical_facet_collector;
+pub use self::hierarchical_facet_collector::{FacetCounts, HierarchicalFacetCollector};
+
 mod chained_collector;
-pub use self::chained_collector::chain;
+pub use self::chained_collector::{chain, DoNothingCollector};
+
+mod bucket;
+pub use self::bucket::Bucket;
+
+mod range_collector;
+pub use self::range_collector::{BucketRange, RangeCollector};
+
+mod histogram_collector;
+pub use self::histogram_collector::{HistogramCollector, HistogramValue};
+
+mod stats_collector;
+pub use self::stats_collector::{Stats, StatsCollector};
+
+mod cardinality_collector;
+pub use self::cardinality_collector::CardinalityCollector;
+
+mod percentiles_collector;
+pub use self::percentiles_collector::PercentilesCollector;
+
+mod terms_collector;
+pub use self::terms_collector::{TermCounts, TermsCollector};
+
+mod grouping_collector;
+pub use self::grouping_collector::{Group, GroupingCollector, SortOrder};
 
 /// Collectors are in charge of collecting and retaining relevant
 /// information from the document found and scored by the query.
@@ -62,6 +89,17 @@ pub trait Collector {
     ) -> Result<()>;
     /// The query pushes the scored document to the collector via this method.
     fn collect(&mut self, doc: DocId, score: Score);
+
+    /// Returns true iff the collector makes use of the scores
+    /// of the documents.
+    ///
+    /// Collectors that ignore the scores (e.g. counting, facets,
+    /// aggregations) should return false. Queries can then skip
+    /// the computation of the scores, and push documents
+    /// with an arbitrary score.
+    fn requires_scoring(&self) -> bool {
+        true
+    }
 }
 
 impl<'a, C: Collector> Collector for &'a mut C {
@@ -77,6 +115,10 @@ impl<'a, C: Collector> Collector for &'a mut C {
     fn collect(&mut self, doc: DocId, score: Score) {
         (*self).collect(doc, score);
     }
+
+    fn requires_scoring(&self) -> bool {
+        (**self).requires_scoring()
+    }
 }
 
 #[cfg(test)]
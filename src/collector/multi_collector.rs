@@ -40,6 +40,12 @@ impl<'a> Collector for MultiCollector<'a> {
             collector.collect(doc, score);
         }
     }
+
+    fn requires_scoring(&self) -> bool {
+        self.collectors
+            .iter()
+            .any(|collector| collector.requires_scoring())
+    }
 }
 
 #[cfg(test)]
@@ -62,4 +68,15 @@ mod tests {
         assert_eq!(count_collector.count(), 3);
         assert!(top_collector.at_capacity());
     }
+
+    #[test]
+    fn test_multi_collector_requires_scoring() {
+        let mut top_collector = TopCollector::with_limit(2);
+        let mut count_collector = CountCollector::default();
+        assert!(!MultiCollector::from(vec![&mut count_collector]).requires_scoring());
+        assert!(
+            MultiCollector::from(vec![&mut count_collector, &mut top_collector])
+                .requires_scoring()
+        );
+    }
 }
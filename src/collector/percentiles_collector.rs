@@ -0,0 +1,282 @@
+use std::cmp::Ordering;
+
+use collector::Collector;
+use fastfield::{FastFieldReader, FastValue};
+use schema::Field;
+
+use DocId;
+use Result;
+use Score;
+use SegmentLocalId;
+use SegmentReader;
+
+/// Default compression of the t-digest.
+///
+/// The digest keeps in the order of `compression` centroids.
+const DEFAULT_COMPRESSION: f64 = 100f64;
+
+#[derive(Clone, Copy, Debug)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Merging t-digest, as described in
+/// *Computing Extremely Accurate Quantiles Using t-Digests*
+/// by Ted Dunning and Otmar Ertl.
+///
+/// Incoming values are buffered, and periodically merged
+/// into a sorted list of centroids. The size of the centroids is bounded
+/// by `4 * total_weight * q * (1 - q) / compression`, which keeps the
+/// digest very accurate for extreme quantiles.
+struct TDigest {
+    compression: f64,
+    centroids: Vec<Centroid>,
+    buffer: Vec<f64>,
+    total_weight: f64,
+    min: f64,
+    max: f64,
+}
+
+impl TDigest {
+    fn with_compression(compression: f64) -> TDigest {
+        TDigest {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            total_weight: 0f64,
+            min: 0f64,
+            max: 0f64,
+        }
+    }
+
+    fn insert(&mut self, val: f64) {
+        self.buffer.push(val);
+        if self.buffer.len() >= 5 * (self.compression as usize) {
+            self.compress();
+        }
+    }
+
+    fn compress(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        let mut all_centroids: Vec<Centroid> = self.buffer
+            .drain(..)
+            .map(|val| Centroid {
+                mean: val,
+                weight: 1f64,
+            })
+            .collect();
+        all_centroids.extend_from_slice(&self.centroids);
+        all_centroids.sort_by(|left, right| {
+            left.mean
+                .partial_cmp(&right.mean)
+                .unwrap_or(Ordering::Equal)
+        });
+        let total_weight: f64 = all_centroids.iter().map(|centroid| centroid.weight).sum();
+        if self.centroids.is_empty() {
+            self.min = all_centroids[0].mean;
+            self.max = all_centroids[all_centroids.len() - 1].mean;
+        } else {
+            self.min = self.min.min(all_centroids[0].mean);
+            self.max = self.max.max(all_centroids[all_centroids.len() - 1].mean);
+        }
+        self.total_weight = total_weight;
+
+        let mut merged: Vec<Centroid> = Vec::with_capacity(self.centroids.len());
+        let mut current = all_centroids[0];
+        let mut weight_so_far = 0f64;
+        for &centroid in &all_centroids[1..] {
+            let proposed_weight = current.weight + centroid.weight;
+            let q_left = weight_so_far / total_weight;
+            let q_right = (weight_so_far + proposed_weight) / total_weight;
+            let max_weight = 4f64 * total_weight
+                * (q_left * (1f64 - q_left)).min(q_right * (1f64 - q_right))
+                / self.compression;
+            if proposed_weight <= max_weight {
+                current.mean += (centroid.mean - current.mean) * centroid.weight / proposed_weight;
+                current.weight = proposed_weight;
+            } else {
+                weight_so_far += current.weight;
+                merged.push(current);
+                current = centroid;
+            }
+        }
+        merged.push(current);
+        self.centroids = merged;
+    }
+
+    /// Returns the estimated value at the given quantile, within `[0, 1]`.
+    fn quantile(&mut self, q: f64) -> Option<f64> {
+        self.compress();
+        if self.centroids.is_empty() {
+            return None;
+        }
+        let index = q * self.total_weight;
+        let mut weight_so_far = 0f64;
+        let mut prev_center = 0f64;
+        let mut prev_mean = self.min;
+        for centroid in &self.centroids {
+            let center = weight_so_far + centroid.weight / 2f64;
+            if index < center {
+                return Some(interpolate(
+                    (prev_center, prev_mean),
+                    (center, centroid.mean),
+                    index,
+                ));
+            }
+            prev_center = center;
+            prev_mean = centroid.mean;
+            weight_so_far += centroid.weight;
+        }
+        Some(interpolate(
+            (prev_center, prev_mean),
+            (self.total_weight, self.max),
+            index,
+        ))
+    }
+}
+
+/// Linear interpolation between two points.
+fn interpolate(left: (f64, f64), right: (f64, f64), x: f64) -> f64 {
+    let (left_x, left_y) = left;
+    let (right_x, right_y) = right;
+    if right_x <= left_x {
+        return right_y;
+    }
+    let t = ((x - left_x) / (right_x - left_x)).max(0f64).min(1f64);
+    left_y + t * (right_y - left_y)
+}
+
+/// Estimates percentiles of a u64/i64/f64 fast field
+/// over the matching documents.
+///
+/// The values are summarized in a t-digest, whose size
+/// does not depend on the number of documents.
+/// Extreme percentiles (e.g. 99.9) are estimated more accurately
+/// than the median.
+///
+/// ```rust,ignore
+/// let mut percentiles_collector = PercentilesCollector::<u64>::new(latency);
+/// searcher.search(&query, &mut percentiles_collector)?;
+/// println!("p99: {:?}", percentiles_collector.percentile(99f64));
+/// ```
+pub struct PercentilesCollector<T: FastValue> {
+    field: Field,
+    digest: TDigest,
+    ff_reader: Option<T::FastFieldReader>,
+}
+
+impl<T: FastValue> PercentilesCollector<T> {
+    /// Creates a new percentiles collector for the given field,
+    /// with the default compression of 100.
+    pub fn new(field: Field) -> PercentilesCollector<T> {
+        PercentilesCollector::with_compression(field, DEFAULT_COMPRESSION)
+    }
+
+    /// Creates a new percentiles collector for the given field.
+    ///
+    /// A higher compression yields more accurate percentiles,
+    /// at the cost of memory.
+    ///
+    /// # Panics
+    /// Panics if `compression` is lower than 1.
+    pub fn with_compression(field: Field, compression: f64) -> PercentilesCollector<T> {
+        assert!(
+            compression >= 1f64,
+            "The compression of the percentiles collector must be at least 1."
+        );
+        PercentilesCollector {
+            field,
+            digest: TDigest::with_compression(compression),
+            ff_reader: None,
+        }
+    }
+
+    /// Returns the estimated value at the given percentile,
+    /// or `None` if no document was collected.
+    ///
+    /// # Panics
+    /// Panics if `percentile` is not within `[0, 100]`.
+    pub fn percentile(&mut self, percentile: f64) -> Option<f64> {
+        assert!(
+            percentile >= 0f64 && percentile <= 100f64,
+            "Percentiles must be within [0, 100]."
+        );
+        self.digest.quantile(percentile / 100f64)
+    }
+}
+
+impl<T: FastValue> Collector for PercentilesCollector<T> {
+    fn set_segment(&mut self, _: SegmentLocalId, reader: &SegmentReader) -> Result<()> {
+        self.ff_reader = Some(reader.get_fast_field_reader(self.field)?);
+        Ok(())
+    }
+
+    fn collect(&mut self, doc: DocId, _: Score) {
+        let val = self.ff_reader
+            .as_ref()
+            .expect("collect() was called before set_segment. This should never happen.")
+            .get(doc);
+        self.digest.insert(val.as_f64());
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{PercentilesCollector, TDigest};
+    use Index;
+    use query::TermQuery;
+    use schema::{IndexRecordOption, SchemaBuilder, Term, FAST, STRING};
+
+    #[test]
+    fn test_tdigest() {
+        let mut digest = TDigest::with_compression(100f64);
+        assert_eq!(digest.quantile(0.5), None);
+        for val in 1..10_001 {
+            digest.insert(val as f64);
+        }
+        assert_eq!(digest.quantile(0f64), Some(1f64));
+        assert_eq!(digest.quantile(1f64), Some(10_000f64));
+        assert!((digest.quantile(0.5).unwrap() - 5_000f64).abs() < 100f64);
+        assert!((digest.quantile(0.99).unwrap() - 9_900f64).abs() < 10f64);
+        assert!((digest.quantile(0.001).unwrap() - 10f64).abs() < 2f64);
+    }
+
+    #[test]
+    fn test_percentiles_collector() {
+        let mut schema_builder = SchemaBuilder::default();
+        let latency = schema_builder.add_u64_field("latency", FAST);
+        let text = schema_builder.add_text_field("text", STRING);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            for i in 1..101u64 {
+                index_writer.add_document(doc!(text => "a", latency => i));
+                if i == 50 {
+                    index_writer.commit().unwrap();
+                }
+            }
+            index_writer.add_document(doc!(text => "b", latency => 1_000_000u64));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let query = TermQuery::new(Term::from_field_text(text, "a"), IndexRecordOption::Basic);
+        let mut percentiles_collector = PercentilesCollector::<u64>::new(latency);
+        searcher.search(&query, &mut percentiles_collector).unwrap();
+        assert_eq!(percentiles_collector.percentile(0f64), Some(1f64));
+        assert_eq!(percentiles_collector.percentile(100f64), Some(100f64));
+        let median = percentiles_collector.percentile(50f64).unwrap();
+        assert!((median - 50.5f64).abs() < 1f64);
+        let p90 = percentiles_collector.percentile(90f64).unwrap();
+        assert!((p90 - 90.5f64).abs() < 1f64);
+    }
+}
@@ -0,0 +1,259 @@
+use std::ops::{Range, RangeFrom, RangeTo};
+
+use collector::{Bucket, Collector, DoNothingCollector};
+use fastfield::{FastFieldReader, FastValue};
+use schema::Field;
+
+use DocId;
+use Result;
+use Score;
+use SegmentLocalId;
+use SegmentReader;
+
+/// Range of values defining a bucket of the `RangeCollector`.
+///
+/// The lower bound is inclusive, the upper bound is exclusive.
+/// A missing bound means that the range is unbounded on that side.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BucketRange<T: FastValue> {
+    from: Option<T>,
+    to: Option<T>,
+}
+
+impl<T: FastValue> BucketRange<T> {
+    /// Creates a new range.
+    pub fn new(from: Option<T>, to: Option<T>) -> BucketRange<T> {
+        BucketRange { from, to }
+    }
+
+    /// Returns the inclusive lower bound of the range, if any.
+    pub fn from(&self) -> Option<T> {
+        self.from
+    }
+
+    /// Returns the exclusive upper bound of the range, if any.
+    pub fn to(&self) -> Option<T> {
+        self.to
+    }
+
+    /// Returns true iff the `u64` representation of a value
+    /// belongs to the range.
+    fn contains_u64(&self, val: u64) -> bool {
+        if let Some(from) = self.from {
+            if val < from.to_u64() {
+                return false;
+            }
+        }
+        if let Some(to) = self.to {
+            if val >= to.to_u64() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl<T: FastValue> From<Range<T>> for BucketRange<T> {
+    fn from(range: Range<T>) -> BucketRange<T> {
+        BucketRange::new(Some(range.start), Some(range.end))
+    }
+}
+
+impl<T: FastValue> From<RangeFrom<T>> for BucketRange<T> {
+    fn from(range: RangeFrom<T>) -> BucketRange<T> {
+        BucketRange::new(Some(range.start), None)
+    }
+}
+
+impl<T: FastValue> From<RangeTo<T>> for BucketRange<T> {
+    fn from(range: RangeTo<T>) -> BucketRange<T> {
+        BucketRange::new(None, Some(range.end))
+    }
+}
+
+/// Range aggregation collector for u64/i64/f64 fast fields.
+///
+/// Documents are dispatched into user-defined ranges,
+/// according to the value of their fast field.
+/// Ranges may overlap, in which case a document
+/// is counted in all of the ranges it belongs to.
+///
+/// Each bucket has its own sub-collector, created
+/// by the factory given in `.with_sub_collector(...)`.
+///
+/// ```rust,ignore
+/// let mut range_collector = RangeCollector::<u64>::new(price);
+/// range_collector.add_range(..100);
+/// range_collector.add_range(100..500);
+/// range_collector.add_range(500..);
+/// searcher.search(&query, &mut range_collector)?;
+/// for bucket in range_collector.buckets() {
+///     println!("{:?} {}", bucket.key(), bucket.doc_count());
+/// }
+/// ```
+pub struct RangeCollector<T: FastValue, TCollector: Collector = DoNothingCollector> {
+    field: Field,
+    buckets: Vec<Bucket<BucketRange<T>, TCollector>>,
+    ff_reader: Option<T::FastFieldReader>,
+    sub_collector_factory: Box<Fn() -> TCollector>,
+}
+
+impl<T: FastValue> RangeCollector<T, DoNothingCollector> {
+    /// Creates a new range collector for aggregating a given field.
+    pub fn new(field: Field) -> RangeCollector<T, DoNothingCollector> {
+        RangeCollector::with_sub_collector(field, || DoNothingCollector)
+    }
+}
+
+impl<T: FastValue, TCollector: Collector> RangeCollector<T, TCollector> {
+    /// Creates a new range collector for aggregating a given field.
+    ///
+    /// `sub_collector_factory` is called to create the sub-collector
+    /// of each of the buckets.
+    pub fn with_sub_collector<F>(
+        field: Field,
+        sub_collector_factory: F,
+    ) -> RangeCollector<T, TCollector>
+    where
+        F: Fn() -> TCollector + 'static,
+    {
+        RangeCollector {
+            field,
+            buckets: Vec::new(),
+            ff_reader: None,
+            sub_collector_factory: Box::new(sub_collector_factory),
+        }
+    }
+
+    /// Adds a bucket for the given range.
+    ///
+    /// Ranges must be added before running the search.
+    /// e.g. `.add_range(10..20)`, `.add_range(..10)` or `.add_range(20..)`.
+    pub fn add_range<R: Into<BucketRange<T>>>(&mut self, range: R) {
+        let sub_collector = (self.sub_collector_factory)();
+        self.buckets.push(Bucket::new(range.into(), sub_collector));
+    }
+
+    /// Returns the buckets, in the order the ranges were added.
+    pub fn buckets(&self) -> &[Bucket<BucketRange<T>, TCollector>] {
+        &self.buckets[..]
+    }
+
+    /// Consumes the collector, and returns its buckets,
+    /// in the order the ranges were added.
+    pub fn into_buckets(self) -> Vec<Bucket<BucketRange<T>, TCollector>> {
+        self.buckets
+    }
+}
+
+impl<T: FastValue, TCollector: Collector> Collector for RangeCollector<T, TCollector> {
+    fn set_segment(
+        &mut self,
+        segment_local_id: SegmentLocalId,
+        reader: &SegmentReader,
+    ) -> Result<()> {
+        self.ff_reader = Some(reader.get_fast_field_reader(self.field)?);
+        for bucket in &mut self.buckets {
+            bucket
+                .sub_collector_mut()
+                .set_segment(segment_local_id, reader)?;
+        }
+        Ok(())
+    }
+
+    fn collect(&mut self, doc: DocId, score: Score) {
+        let val = self.ff_reader
+            .as_ref()
+            .expect("collect() was called before set_segment. This should never happen.")
+            .get(doc)
+            .to_u64();
+        for bucket in &mut self.buckets {
+            if bucket.key().contains_u64(val) {
+                bucket.collect(doc, score);
+            }
+        }
+    }
+
+    fn requires_scoring(&self) -> bool {
+        self.buckets
+            .iter()
+            .any(|bucket| bucket.sub_collector().requires_scoring())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{BucketRange, RangeCollector};
+    use Index;
+    use collector::TopCollector;
+    use query::TermQuery;
+    use schema::{IndexRecordOption, SchemaBuilder, Term, FAST, STRING};
+
+    #[test]
+    fn test_range_collector() {
+        let mut schema_builder = SchemaBuilder::default();
+        let price = schema_builder.add_u64_field("price", FAST);
+        let temperature = schema_builder.add_i64_field("temperature", FAST);
+        let all = schema_builder.add_text_field("all", STRING);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            let docs: Vec<(u64, i64)> = vec![(5, -10), (10, 3), (15, -1), (150, 0), (35, 20)];
+            for (i, &(price_val, temperature_val)) in docs.iter().enumerate() {
+                index_writer.add_document(doc!(
+                    all => "all",
+                    price => price_val,
+                    temperature => temperature_val
+                ));
+                if i == 2 {
+                    index_writer.commit().unwrap();
+                }
+            }
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let query = TermQuery::new(Term::from_field_text(all, "all"), IndexRecordOption::Basic);
+        {
+            let mut range_collector = RangeCollector::<u64>::new(price);
+            range_collector.add_range(..10);
+            range_collector.add_range(10..40);
+            range_collector.add_range(40..);
+            range_collector.add_range(0..1000);
+            searcher.search(&query, &mut range_collector).unwrap();
+            let counts: Vec<(BucketRange<u64>, u64)> = range_collector
+                .buckets()
+                .iter()
+                .map(|bucket| (*bucket.key(), bucket.doc_count()))
+                .collect();
+            assert_eq!(
+                counts,
+                vec![
+                    (BucketRange::new(None, Some(10)), 1),
+                    (BucketRange::new(Some(10), Some(40)), 3),
+                    (BucketRange::new(Some(40), None), 1),
+                    (BucketRange::new(Some(0), Some(1000)), 5),
+                ]
+            );
+        }
+        {
+            let mut range_collector =
+                RangeCollector::<i64, _>::with_sub_collector(temperature, || {
+                    TopCollector::with_limit(10)
+                });
+            range_collector.add_range(..0);
+            range_collector.add_range(0..);
+            searcher.search(&query, &mut range_collector).unwrap();
+            let buckets = range_collector.into_buckets();
+            assert_eq!(buckets.len(), 2);
+            assert_eq!(buckets[0].doc_count(), 2);
+            assert_eq!(buckets[1].doc_count(), 3);
+            for bucket in buckets {
+                let doc_count = bucket.doc_count() as usize;
+                assert_eq!(bucket.into_sub_collector().docs().len(), doc_count);
+            }
+        }
+    }
+}
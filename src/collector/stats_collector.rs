@@ -0,0 +1,257 @@
+use collector::Collector;
+use fastfield::{FastFieldReader, FastValue, MultiValueFastFieldReader};
+use schema::Field;
+
+use DocId;
+use Result;
+use Score;
+use SegmentLocalId;
+use SegmentReader;
+
+/// Statistics computed by the `StatsCollector`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats<T: FastValue> {
+    count: u64,
+    sum: f64,
+    min: Option<T>,
+    max: Option<T>,
+}
+
+impl<T: FastValue> Default for Stats<T> {
+    fn default() -> Stats<T> {
+        Stats {
+            count: 0,
+            sum: 0f64,
+            min: None,
+            max: None,
+        }
+    }
+}
+
+impl<T: FastValue> Stats<T> {
+    /// Returns the number of values.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the sum of the values.
+    ///
+    /// The sum is computed over `f64`, and may therefore
+    /// lose precision on very large values.
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Returns the smallest value, or `None` if there was no value.
+    pub fn min(&self) -> Option<T> {
+        self.min
+    }
+
+    /// Returns the largest value, or `None` if there was no value.
+    pub fn max(&self) -> Option<T> {
+        self.max
+    }
+
+    /// Returns the average of the values, or `None` if there was no value.
+    pub fn avg(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.sum / (self.count as f64))
+        }
+    }
+
+    fn record(&mut self, val: T) {
+        self.count += 1;
+        self.sum += val.as_f64();
+        if self.min.map(|min| val < min).unwrap_or(true) {
+            self.min = Some(val);
+        }
+        if self.max.map(|max| val > max).unwrap_or(true) {
+            self.max = Some(val);
+        }
+    }
+}
+
+/// Fast field reader of the `StatsCollector`.
+enum StatsFieldReader<T: FastValue> {
+    SingleValue(T::FastFieldReader),
+    MultiValues(MultiValueFastFieldReader<T::FastFieldReader>, Vec<T>),
+}
+
+impl<T: FastValue> StatsFieldReader<T> {
+    fn open(reader: &SegmentReader, field: Field) -> Result<StatsFieldReader<T>> {
+        match reader.get_fast_field_reader(field) {
+            Ok(ff_reader) => Ok(StatsFieldReader::SingleValue(ff_reader)),
+            Err(err) => match reader.multi_fast_field_reader(field) {
+                Ok(ff_reader) => Ok(StatsFieldReader::MultiValues(ff_reader, Vec::new())),
+                Err(_) => Err(err.into()),
+            },
+        }
+    }
+
+    fn record(&mut self, doc: DocId, stats: &mut Stats<T>) {
+        match *self {
+            StatsFieldReader::SingleValue(ref ff_reader) => stats.record(ff_reader.get(doc)),
+            StatsFieldReader::MultiValues(ref ff_reader, ref mut vals) => {
+                ff_reader.get_vals(doc, vals);
+                for &val in vals.iter() {
+                    stats.record(val);
+                }
+            }
+        }
+    }
+}
+
+/// Computes the count, sum, min, max and average
+/// of a u64/i64/f64 fast field over the matching documents.
+///
+/// For multi-valued fast fields, all of the values of the matching
+/// documents are taken into account, and `count` is the number of values.
+///
+/// ```rust,ignore
+/// let mut stats_collector = StatsCollector::<u64>::new(price);
+/// searcher.search(&query, &mut stats_collector)?;
+/// let stats = stats_collector.stats();
+/// println!("{:?} {:?}", stats.min(), stats.avg());
+/// ```
+pub struct StatsCollector<T: FastValue> {
+    field: Field,
+    stats: Stats<T>,
+    ff_reader: Option<StatsFieldReader<T>>,
+}
+
+impl<T: FastValue> StatsCollector<T> {
+    /// Creates a new stats collector for the given field.
+    pub fn new(field: Field) -> StatsCollector<T> {
+        StatsCollector {
+            field,
+            stats: Stats::default(),
+            ff_reader: None,
+        }
+    }
+
+    /// Returns the statistics of the documents collected so far.
+    pub fn stats(&self) -> Stats<T> {
+        self.stats
+    }
+}
+
+impl<T: FastValue> Collector for StatsCollector<T> {
+    fn set_segment(&mut self, _: SegmentLocalId, reader: &SegmentReader) -> Result<()> {
+        self.ff_reader = Some(StatsFieldReader::open(reader, self.field)?);
+        Ok(())
+    }
+
+    fn collect(&mut self, doc: DocId, _: Score) {
+        self.ff_reader
+            .as_mut()
+            .expect("collect() was called before set_segment. This should never happen.")
+            .record(doc, &mut self.stats);
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::StatsCollector;
+    use Index;
+    use collector::{chain, CountCollector, RangeCollector};
+    use query::TermQuery;
+    use schema::{IndexRecordOption, IntOptions, SchemaBuilder, Term, FAST, STRING};
+
+    #[test]
+    fn test_stats_collector() {
+        let mut schema_builder = SchemaBuilder::default();
+        let temperature = schema_builder.add_i64_field("temperature", FAST);
+        let text = schema_builder.add_text_field("text", STRING);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(text => "a", temperature => -7i64));
+            index_writer.add_document(doc!(text => "a", temperature => 3i64));
+            index_writer.add_document(doc!(text => "b", temperature => 100i64));
+            index_writer.commit().unwrap();
+            index_writer.add_document(doc!(text => "a", temperature => 12i64));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        {
+            let query = TermQuery::new(Term::from_field_text(text, "a"), IndexRecordOption::Basic);
+            let mut stats_collector = StatsCollector::<i64>::new(temperature);
+            let mut count_collector = CountCollector::default();
+            searcher
+                .search(
+                    &query,
+                    &mut chain()
+                        .push(&mut stats_collector)
+                        .push(&mut count_collector),
+                )
+                .unwrap();
+            let stats = stats_collector.stats();
+            assert_eq!(count_collector.count(), 3);
+            assert_eq!(stats.count(), 3);
+            assert_eq!(stats.min(), Some(-7i64));
+            assert_eq!(stats.max(), Some(12i64));
+            assert_eq!(stats.sum(), 8f64);
+            assert!((stats.avg().unwrap() - 8f64 / 3f64).abs() < 1e-9);
+        }
+        {
+            let query = TermQuery::new(Term::from_field_text(text, "c"), IndexRecordOption::Basic);
+            let mut stats_collector = StatsCollector::<i64>::new(temperature);
+            searcher.search(&query, &mut stats_collector).unwrap();
+            let stats = stats_collector.stats();
+            assert_eq!(stats.count(), 0);
+            assert_eq!(stats.min(), None);
+            assert_eq!(stats.avg(), None);
+        }
+        {
+            // stats as a sub-collector of a bucket aggregation.
+            let query = TermQuery::new(Term::from_field_text(text, "a"), IndexRecordOption::Basic);
+            let mut range_collector =
+                RangeCollector::<i64, _>::with_sub_collector(temperature, move || {
+                    StatsCollector::<i64>::new(temperature)
+                });
+            range_collector.add_range(..0);
+            range_collector.add_range(0..);
+            searcher.search(&query, &mut range_collector).unwrap();
+            let buckets = range_collector.into_buckets();
+            assert_eq!(buckets[0].sub_collector().stats().max(), Some(-7i64));
+            assert_eq!(buckets[1].sub_collector().stats().sum(), 15f64);
+        }
+    }
+
+    #[test]
+    fn test_stats_collector_multivalued() {
+        let mut schema_builder = SchemaBuilder::default();
+        let sizes = schema_builder
+            .add_u64_field("sizes", IntOptions::default().set_multivalued_fast());
+        let text = schema_builder.add_text_field("text", STRING);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(text => "a", sizes => 4u64, sizes => 2u64));
+            index_writer.add_document(doc!(text => "a"));
+            index_writer.add_document(doc!(text => "b", sizes => 100u64));
+            index_writer.add_document(doc!(text => "a", sizes => 9u64));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let query = TermQuery::new(Term::from_field_text(text, "a"), IndexRecordOption::Basic);
+        let mut stats_collector = StatsCollector::<u64>::new(sizes);
+        searcher.search(&query, &mut stats_collector).unwrap();
+        let stats = stats_collector.stats();
+        assert_eq!(stats.count(), 3);
+        assert_eq!(stats.min(), Some(2u64));
+        assert_eq!(stats.max(), Some(9u64));
+        assert_eq!(stats.sum(), 15f64);
+    }
+}
@@ -0,0 +1,206 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use collector::Collector;
+use fastfield::TermOrdinalsReader;
+use schema::Field;
+use termdict::TermOrdinal;
+
+use DocId;
+use Result;
+use Score;
+use SegmentLocalId;
+use SegmentReader;
+
+/// Terms aggregation collector for fast text fields.
+///
+/// Counts, for each term of the field, the number of matching
+/// documents containing it.
+/// The field must be indexed and set as fast
+/// (see `TextOptions::set_fast`), typically with the `raw` tokenizer.
+///
+/// Within a segment, documents are counted per term ordinal.
+/// The terms themselves are only materialized when switching segment,
+/// and the counts are merged across segments by term.
+///
+/// ```rust,ignore
+/// let mut terms_collector = TermsCollector::for_field(brand);
+/// searcher.search(&query, &mut terms_collector)?;
+/// for (brand, count) in terms_collector.harvest().top_k(20) {
+///     println!("{} {}", brand, count);
+/// }
+/// ```
+pub struct TermsCollector {
+    field: Field,
+    term_ords_reader: Option<TermOrdinalsReader>,
+    segment_counts: HashMap<TermOrdinal, u64>,
+    term_counts: HashMap<String, u64>,
+    ords_buffer: Vec<u64>,
+}
+
+impl TermsCollector {
+    /// Creates a new terms collector for the given fast text field.
+    pub fn for_field(field: Field) -> TermsCollector {
+        TermsCollector {
+            field,
+            term_ords_reader: None,
+            segment_counts: HashMap::new(),
+            term_counts: HashMap::new(),
+            ords_buffer: Vec::new(),
+        }
+    }
+
+    /// Adds the counts of the current segment to the overall counts.
+    fn flush_segment_counts(&mut self) {
+        if let Some(ref term_ords_reader) = self.term_ords_reader {
+            let mut term_bytes = Vec::new();
+            for (&term_ord, &count) in &self.segment_counts {
+                if term_ords_reader.ord_to_bytes(term_ord, &mut term_bytes) {
+                    let term = String::from_utf8_lossy(&term_bytes).into_owned();
+                    *self.term_counts.entry(term).or_insert(0) += count;
+                }
+            }
+        }
+        self.segment_counts.clear();
+    }
+
+    /// Returns the term counts collected.
+    pub fn harvest(mut self) -> TermCounts {
+        self.flush_segment_counts();
+        TermCounts {
+            term_counts: self.term_counts,
+        }
+    }
+}
+
+impl Collector for TermsCollector {
+    fn set_segment(&mut self, _: SegmentLocalId, reader: &SegmentReader) -> Result<()> {
+        self.flush_segment_counts();
+        self.term_ords_reader = Some(reader.term_ordinals_reader(self.field)?);
+        Ok(())
+    }
+
+    fn collect(&mut self, doc: DocId, _: Score) {
+        self.term_ords_reader
+            .as_ref()
+            .expect("collect() was called before set_segment. This should never happen.")
+            .term_ords(doc, &mut self.ords_buffer);
+        for &term_ord in &self.ords_buffer {
+            *self.segment_counts.entry(term_ord).or_insert(0) += 1;
+        }
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+}
+
+/// Term counts harvested from a `TermsCollector`.
+pub struct TermCounts {
+    term_counts: HashMap<String, u64>,
+}
+
+impl TermCounts {
+    /// Returns the number of matching documents containing `term`.
+    pub fn get(&self, term: &str) -> u64 {
+        self.term_counts.get(term).cloned().unwrap_or(0)
+    }
+
+    /// Returns the number of distinct terms counted.
+    pub fn len(&self) -> usize {
+        self.term_counts.len()
+    }
+
+    /// Returns true iff no term was counted.
+    pub fn is_empty(&self) -> bool {
+        self.term_counts.is_empty()
+    }
+
+    /// Returns the `k` terms with the highest document count,
+    /// sorted by decreasing count.
+    ///
+    /// Ties are broken by term order.
+    pub fn top_k(&self, k: usize) -> Vec<(&str, u64)> {
+        let mut terms: Vec<(&str, u64)> = self.term_counts
+            .iter()
+            .map(|(term, &count)| (term.as_str(), count))
+            .collect();
+        terms.sort_by(|&(left_term, left_count), &(right_term, right_count)| {
+            match right_count.cmp(&left_count) {
+                Ordering::Equal => left_term.cmp(right_term),
+                ordering => ordering,
+            }
+        });
+        terms.truncate(k);
+        terms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::TermsCollector;
+    use Index;
+    use collector::CountCollector;
+    use futures::Future;
+    use query::TermQuery;
+    use schema::{IndexRecordOption, SchemaBuilder, Term, STRING};
+
+    #[test]
+    fn test_terms_collector() {
+        let mut schema_builder = SchemaBuilder::default();
+        let brand = schema_builder.add_text_field("brand", STRING.set_fast());
+        let all = schema_builder.add_text_field("all", STRING);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(all => "all", brand => "acme"));
+            index_writer.add_document(doc!(all => "all", brand => "globex"));
+            index_writer.add_document(doc!(all => "all", brand => "initech"));
+            index_writer.commit().unwrap();
+            index_writer.add_document(doc!(all => "all", brand => "acme", brand => "globex"));
+            index_writer.add_document(doc!(all => "all", brand => "acme"));
+            index_writer.add_document(doc!(all => "all"));
+            index_writer.commit().unwrap();
+            index_writer.delete_term(Term::from_field_text(brand, "initech"));
+            index_writer.commit().unwrap();
+        }
+        let check = |index: &Index| {
+            index.load_searchers().unwrap();
+            let searcher = index.searcher();
+            let query = TermQuery::new(Term::from_field_text(all, "all"), IndexRecordOption::Basic);
+            let mut terms_collector = TermsCollector::for_field(brand);
+            searcher.search(&query, &mut terms_collector).unwrap();
+            let term_counts = terms_collector.harvest();
+            assert_eq!(term_counts.len(), 2);
+            assert_eq!(term_counts.get("acme"), 3);
+            assert_eq!(term_counts.get("initech"), 0);
+            assert_eq!(term_counts.top_k(10), vec![("acme", 3), ("globex", 2)]);
+            assert_eq!(term_counts.top_k(1), vec![("acme", 3)]);
+        };
+        check(&index);
+        {
+            let segment_ids = index
+                .searchable_segment_ids()
+                .expect("Searchable segments failed.");
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer
+                .merge(&segment_ids)
+                .wait()
+                .expect("Merging failed");
+            index_writer.wait_merging_threads().unwrap();
+        }
+        check(&index);
+        {
+            // fields that are not fast are rejected.
+            let searcher = index.searcher();
+            let query = TermQuery::new(Term::from_field_text(all, "all"), IndexRecordOption::Basic);
+            let mut terms_collector = TermsCollector::for_field(all);
+            let mut count_collector = CountCollector::default();
+            assert!(searcher.search(&query, &mut terms_collector).is_err());
+            searcher.search(&query, &mut count_collector).unwrap();
+            assert_eq!(count_collector.count(), 5);
+        }
+    }
+}
@@ -5,13 +5,59 @@ use directory::ReadOnlySource;
 use directory::WritePtr;
 use schema::Field;
 use std::collections::HashMap;
-use std::io;
-use std::io::Write;
+use std::io::{self, Read, Write};
+
+/// Address of a file within a `CompositeFile`.
+///
+/// Most of the time, one field is associated with a single
+/// file, but some datastructures (e.g. multivalued fast fields)
+/// require several files for the same field.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+struct FileAddr {
+    field: Field,
+    idx: usize,
+}
+
+impl FileAddr {
+    fn new(field: Field, idx: usize) -> FileAddr {
+        FileAddr { field, idx }
+    }
+}
+
+/// Flag set on the serialized field of a `FileAddr` with a non-zero `idx`.
+const HAS_IDX_FLAG: u32 = 1u32 << 31;
+
+/// The `FileAddr` of the first file of a field is serialized as
+/// the field itself, as in the footer of the files written before
+/// several files per field were supported.
+/// Otherwise, the serialized field carries the `HAS_IDX_FLAG`
+/// and is followed by the `idx`.
+impl BinarySerializable for FileAddr {
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        if self.idx == 0 {
+            return self.field.serialize(writer);
+        }
+        (self.field.0 | HAS_IDX_FLAG).serialize(writer)?;
+        VInt(self.idx as u64).serialize(writer)?;
+        Ok(())
+    }
+
+    fn deserialize<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let field_with_flag = u32::deserialize(reader)?;
+        let field = Field(field_with_flag & !HAS_IDX_FLAG);
+        let idx = if field_with_flag & HAS_IDX_FLAG == 0 {
+            0
+        } else {
+            VInt::deserialize(reader)?.0 as usize
+        };
+        Ok(FileAddr { field, idx })
+    }
+}
 
 /// A `CompositeWrite` is used to write a `CompositeFile`.
 pub struct CompositeWrite<W = WritePtr> {
     write: CountingWriter<W>,
-    offsets: HashMap<Field, usize>,
+    offsets: HashMap<FileAddr, usize>,
 }
 
 impl<W: Write> CompositeWrite<W> {
@@ -26,9 +72,18 @@ impl<W: Write> CompositeWrite<W> {
 
     /// Start writing a new field.
     pub fn for_field(&mut self, field: Field) -> &mut CountingWriter<W> {
+        self.for_field_with_idx(field, 0)
+    }
+
+    /// Start writing a new file for the given field.
+    ///
+    /// The `idx` makes it possible to store several
+    /// files for the same field.
+    pub fn for_field_with_idx(&mut self, field: Field, idx: usize) -> &mut CountingWriter<W> {
         let offset = self.write.written_bytes();
-        assert!(!self.offsets.contains_key(&field));
-        self.offsets.insert(field, offset);
+        let file_addr = FileAddr::new(field, idx);
+        assert!(!self.offsets.contains_key(&file_addr));
+        self.offsets.insert(file_addr, offset);
         &mut self.write
     }
 
@@ -42,15 +97,15 @@ impl<W: Write> CompositeWrite<W> {
 
         let mut offset_fields: Vec<_> = self.offsets
             .iter()
-            .map(|(field, offset)| (offset, field))
+            .map(|(file_addr, offset)| (offset, file_addr))
             .collect();
 
         offset_fields.sort();
 
         let mut prev_offset = 0;
-        for (offset, field) in offset_fields {
+        for (offset, file_addr) in offset_fields {
             VInt((offset - prev_offset) as u64).serialize(&mut self.write)?;
-            field.serialize(&mut self.write)?;
+            file_addr.serialize(&mut self.write)?;
             prev_offset = *offset;
         }
 
@@ -70,7 +125,7 @@ impl<W: Write> CompositeWrite<W> {
 #[derive(Clone)]
 pub struct CompositeFile {
     data: ReadOnlySource,
-    offsets_index: HashMap<Field, (usize, usize)>,
+    offsets_index: HashMap<FileAddr, (usize, usize)>,
 }
 
 impl CompositeFile {
@@ -86,7 +141,7 @@ impl CompositeFile {
         let mut footer_buffer = footer_data.as_slice();
         let num_fields = VInt::deserialize(&mut footer_buffer)?.0 as usize;
 
-        let mut fields = vec![];
+        let mut file_addrs = vec![];
         let mut offsets = vec![];
 
         let mut field_index = HashMap::new();
@@ -94,16 +149,16 @@ impl CompositeFile {
         let mut offset = 0;
         for _ in 0..num_fields {
             offset += VInt::deserialize(&mut footer_buffer)?.0 as usize;
-            let field = Field::deserialize(&mut footer_buffer)?;
+            let file_addr = FileAddr::deserialize(&mut footer_buffer)?;
             offsets.push(offset);
-            fields.push(field);
+            file_addrs.push(file_addr);
         }
         offsets.push(footer_start);
         for i in 0..num_fields {
-            let field = fields[i];
+            let file_addr = file_addrs[i];
             let start_offset = offsets[i];
             let end_offset = offsets[i + 1];
-            field_index.insert(field, (start_offset, end_offset));
+            field_index.insert(file_addr, (start_offset, end_offset));
         }
 
         Ok(CompositeFile {
@@ -124,8 +179,14 @@ impl CompositeFile {
     /// Returns the `ReadOnlySource` associated
     /// to a given `Field` and stored in a `CompositeFile`.
     pub fn open_read(&self, field: Field) -> Option<ReadOnlySource> {
+        self.open_read_with_idx(field, 0)
+    }
+
+    /// Returns the `ReadOnlySource` associated
+    /// to a given `Field` and index, and stored in a `CompositeFile`.
+    pub fn open_read_with_idx(&self, field: Field, idx: usize) -> Option<ReadOnlySource> {
         self.offsets_index
-            .get(&field)
+            .get(&FileAddr::new(field, idx))
             .map(|&(from, to)| self.data.slice(from, to))
     }
 }
@@ -133,7 +194,7 @@ impl CompositeFile {
 #[cfg(test)]
 mod test {
 
-    use super::{CompositeFile, CompositeWrite};
+    use super::{CompositeFile, CompositeWrite, FileAddr};
     use common::BinarySerializable;
     use common::VInt;
     use directory::{Directory, RAMDirectory};
@@ -159,6 +220,11 @@ mod test {
                 VInt(2).serialize(&mut write_4).unwrap();
                 write_4.flush().unwrap();
             }
+            {
+                let mut write_4_1 = composite_write.for_field_with_idx(Field(4u32), 1);
+                VInt(17).serialize(&mut write_4_1).unwrap();
+                write_4_1.flush().unwrap();
+            }
             composite_write.close().unwrap();
         }
         {
@@ -178,7 +244,30 @@ mod test {
                 assert_eq!(file4_buf.len(), 0);
                 assert_eq!(payload_4, 2u64);
             }
+            {
+                let file4_1 = composite_file.open_read_with_idx(Field(4u32), 1).unwrap();
+                let mut file4_1_buf = file4_1.as_slice();
+                let payload_4_1 = VInt::deserialize(&mut file4_1_buf).unwrap().0;
+                assert_eq!(file4_1_buf.len(), 0);
+                assert_eq!(payload_4_1, 17u64);
+            }
         }
     }
 
+    #[test]
+    fn test_file_addr_serialization() {
+        // the first file of a field is serialized as in
+        // the previous format of the footer.
+        let mut buffer = vec![];
+        FileAddr::new(Field(4u32), 0).serialize(&mut buffer).unwrap();
+        let mut field_buffer = vec![];
+        Field(4u32).serialize(&mut field_buffer).unwrap();
+        assert_eq!(buffer, field_buffer);
+        for &idx in &[0, 1, 300] {
+            let file_addr = FileAddr::new(Field(4u32), idx);
+            let mut buffer = vec![];
+            file_addr.serialize(&mut buffer).unwrap();
+            assert_eq!(FileAddr::deserialize(&mut &buffer[..]).unwrap(), file_addr);
+        }
+    }
 }
@@ -64,10 +64,42 @@ pub fn u64_to_i64(val: u64) -> i64 {
     (val ^ HIGHEST_BIT) as i64
 }
 
+/// Maps a `f64` to `u64`
+///
+/// For simplicity, tantivy internally handles `f64` as `u64`.
+/// The mapping preserves the order of the values :
+/// the sign bit is flipped for positive values, and all of
+/// the bits are flipped for negative values, so that the
+/// resulting `u64` compare like the original `f64`.
+///
+/// # See also
+/// The [reverse mapping is `u64_to_f64`](./fn.u64_to_f64.html).
+#[inline(always)]
+pub fn f64_to_u64(val: f64) -> u64 {
+    let bits = val.to_bits();
+    if bits & HIGHEST_BIT == 0 {
+        bits ^ HIGHEST_BIT
+    } else {
+        !bits
+    }
+}
+
+/// Reverse the mapping given by [`f64_to_u64`](./fn.f64_to_u64.html).
+#[inline(always)]
+pub fn u64_to_f64(val: u64) -> f64 {
+    let bits = if val & HIGHEST_BIT != 0 {
+        val ^ HIGHEST_BIT
+    } else {
+        !val
+    };
+    f64::from_bits(bits)
+}
+
 #[cfg(test)]
 mod test {
 
-    use super::{i64_to_u64, u64_to_i64};
+    use super::{f64_to_u64, i64_to_u64, u64_to_f64, u64_to_i64};
+    use std::f64;
 
     fn test_i64_converter_helper(val: i64) {
         assert_eq!(u64_to_i64(i64_to_u64(val)), val);
@@ -84,4 +116,26 @@ mod test {
             test_i64_converter_helper(i);
         }
     }
+
+    fn test_f64_converter_helper(val: f64) {
+        assert_eq!(u64_to_f64(f64_to_u64(val)), val);
+    }
+
+    #[test]
+    fn test_f64_converter() {
+        test_f64_converter_helper(0f64);
+        test_f64_converter_helper(-0.5f64);
+        test_f64_converter_helper(f64::INFINITY);
+        test_f64_converter_helper(f64::NEG_INFINITY);
+        test_f64_converter_helper(f64::MIN);
+        test_f64_converter_helper(f64::MAX);
+        let mut vals: Vec<f64> = (-1000..1000).map(|i| i as f64 / 7f64).collect();
+        vals.push(f64::NEG_INFINITY);
+        vals.push(f64::INFINITY);
+        for pair in vals.windows(2) {
+            if pair[0] < pair[1] {
+                assert!(f64_to_u64(pair[0]) < f64_to_u64(pair[1]));
+            }
+        }
+    }
 }
@@ -15,16 +15,16 @@ use directory::ManagedDirectory;
 use error::{ErrorKind, ResultExt};
 use indexer::DirectoryLock;
 use indexer::index_writer::open_index_writer;
-use indexer::segment_updater::save_new_metas;
+use indexer::segment_updater::{save_metas, save_new_metas};
 use num_cpus;
-use schema::Schema;
+use schema::{Field, FieldEntry, FieldType, Schema};
 use serde_json;
 use std::borrow::BorrowMut;
 use std::convert::From;
 use std::fmt;
 use std::path::Path;
 use std::sync::Arc;
-use tokenizer::TokenizerManager;
+use tokenizer::{Analysis, TokenizerManager};
 
 const NUM_SEARCHERS: usize = 12;
 
@@ -34,6 +34,54 @@ fn load_metas(directory: &Directory) -> Result<IndexMeta> {
     serde_json::from_str(&meta_string).chain_err(|| ErrorKind::CorruptedFile(META_FILEPATH.clone()))
 }
 
+/// Checks that `new_schema` is a valid evolution of `schema`.
+///
+/// Fields may be appended, and existing fields may be marked as deprecated.
+/// Existing fields may not be removed, modified, or reverted from deprecated.
+fn check_schema_evolution(schema: &Schema, new_schema: &Schema) -> Result<()> {
+    if new_schema.fields().len() < schema.fields().len() {
+        bail!(ErrorKind::InvalidArgument(String::from(
+            "Fields cannot be removed from the schema of an index. Deprecate them instead."
+        )));
+    }
+    for (field_entry, new_field_entry) in schema.fields().iter().zip(new_schema.fields()) {
+        let is_same_field = field_entry.name() == new_field_entry.name()
+            && field_entry.field_type() == new_field_entry.field_type();
+        if !is_same_field || (field_entry.is_deprecated() && !new_field_entry.is_deprecated()) {
+            bail!(ErrorKind::InvalidArgument(format!(
+                "The field {:?} cannot be modified, it can only be deprecated.",
+                field_entry.name()
+            )));
+        }
+        if field_entry.is_unique_key() && new_field_entry.is_deprecated() {
+            bail!(ErrorKind::InvalidArgument(format!(
+                "The unique key field {:?} cannot be deprecated.",
+                field_entry.name()
+            )));
+        }
+    }
+    // existing documents may hold duplicate values for another field.
+    if schema.unique_key_field() != new_schema.unique_key_field() {
+        bail!(ErrorKind::InvalidArgument(String::from(
+            "The unique key of the schema of an index cannot be changed."
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the name of the tokenizer of a field, if it
+/// is an indexed text field.
+fn tokenizer_name(field_entry: &FieldEntry) -> Option<&str> {
+    let indexing_options_opt = match *field_entry.field_type() {
+        FieldType::Str(ref text_options) => text_options.get_indexing_options(),
+        FieldType::JsonObject(ref json_object_options) => {
+            json_object_options.get_text_indexing_options()
+        }
+        _ => None,
+    };
+    indexing_options_opt.map(|indexing_options| indexing_options.tokenizer())
+}
+
 /// Search Index
 pub struct Index {
     directory: ManagedDirectory,
@@ -67,10 +115,41 @@ impl Index {
     }
 
     /// Accessor for the tokenizer manager.
+    ///
+    /// The definitions of the tokenizers registered with
+    /// `TokenizerManager::register_definition` are saved
+    /// with the index on the next commit.
     pub fn tokenizers(&self) -> &TokenizerManager {
         &self.tokenizers
     }
 
+    /// Runs the tokenizer of `field` on `text`, and returns the resulting tokens,
+    /// as they would be indexed.
+    ///
+    /// If `with_stages` is true, the tokens emitted by each stage of
+    /// the tokenizer are returned as well.
+    ///
+    /// Returns an error if the field is not an indexed text field,
+    /// or if its tokenizer is not registered.
+    pub fn analyze(&self, field: Field, text: &str, with_stages: bool) -> Result<Analysis> {
+        let field_entry = self.schema.get_field_entry(field);
+        let tokenizer_name = match tokenizer_name(field_entry) {
+            Some(tokenizer_name) => tokenizer_name,
+            None => bail!(ErrorKind::InvalidArgument(format!(
+                "The field {:?} is not a tokenized text field.",
+                field_entry.name()
+            ))),
+        };
+        match self.tokenizers.analyze(tokenizer_name, text, with_stages) {
+            Some(analysis) => Ok(analysis),
+            None => bail!(ErrorKind::InvalidArgument(format!(
+                "The tokenizer {:?} of the field {:?} is not registered.",
+                tokenizer_name,
+                field_entry.name()
+            ))),
+        }
+    }
+
     /// Creates a new index in a temp directory.
     ///
     /// The index will use the `MMapDirectory` in a newly created directory.
@@ -86,13 +165,19 @@ impl Index {
     }
 
     /// Creates a new index given a directory and an `IndexMeta`.
+    ///
+    /// The tokenizers defined in the `IndexMeta` are registered.
     fn create_from_metas(directory: ManagedDirectory, metas: &IndexMeta) -> Result<Index> {
         let schema = metas.schema.clone();
+        let tokenizers = TokenizerManager::default();
+        for (tokenizer_name, definition) in &metas.tokenizers {
+            tokenizers.register_definition(tokenizer_name, definition.clone())?;
+        }
         let index = Index {
             directory,
             schema,
             searcher_pool: Arc::new(Pool::new()),
-            tokenizers: TokenizerManager::default(),
+            tokenizers,
         };
         index.load_searchers()?;
         Ok(index)
@@ -130,6 +215,10 @@ impl Index {
     ///
     /// # Errors
     /// If the lockfile already exists, returns `Error::FileAlreadyExists`.
+    /// If a field of the schema references a tokenizer that is not registered,
+    /// returns `Error::InvalidArgument`.
+    /// If the schema of the index was updated by another `Index` object,
+    /// returns `Error::InvalidArgument`. The index then needs to be reopened.
     /// # Panics
     /// If the heap size per thread is too small, panics.
     pub fn writer_with_num_threads(
@@ -138,10 +227,37 @@ impl Index {
         heap_size_in_bytes: usize,
     ) -> Result<IndexWriter>
     {
+        self.check_tokenizers()?;
         let directory_lock = DirectoryLock::lock(self.directory().box_clone())?;
+        // the index writer saves `self.schema` on commit, which would
+        // otherwise revert a more recent schema.
+        if self.load_metas()?.schema.fields() != self.schema.fields() {
+            bail!(ErrorKind::InvalidArgument(String::from(
+                "The schema of the index was updated by another `Index`. Reopen the index."
+            )));
+        }
         open_index_writer(self, num_threads, heap_size_in_bytes, directory_lock)
     }
 
+    /// Checks that the tokenizers of the indexed text fields are registered.
+    fn check_tokenizers(&self) -> Result<()> {
+        for field_entry in self.schema.fields() {
+            if field_entry.is_deprecated() {
+                continue;
+            }
+            if let Some(tokenizer_name) = tokenizer_name(field_entry) {
+                if self.tokenizers.get(tokenizer_name).is_none() {
+                    bail!(ErrorKind::InvalidArgument(format!(
+                        "The field {:?} references the unknown tokenizer {:?}.",
+                        field_entry.name(),
+                        tokenizer_name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Creates a multithreaded writer
     /// It just calls `writer_with_num_threads` with the number of cores as `num_threads`
     ///
@@ -160,6 +276,50 @@ impl Index {
         self.schema.clone()
     }
 
+    /// Updates the schema of the index.
+    ///
+    /// The new schema is typically built by appending fields to the
+    /// current schema, or by marking some of its fields as deprecated:
+    ///
+    /// ```rust,ignore
+    /// let mut schema_builder = SchemaBuilder::from(index.schema());
+    /// let tags = schema_builder.add_text_field("tags", STRING);
+    /// schema_builder.deprecate_field(legacy_field);
+    /// index.update_schema(schema_builder.build())?;
+    /// ```
+    ///
+    /// Segments written under a previous version of the schema treat the
+    /// fields they do not know about as absent: their inverted index is empty,
+    /// their fast fields are not available, and their stored documents do not
+    /// contain them. Merging such segments upgrades them to the new schema,
+    /// the missing fast field values being replaced by default values.
+    ///
+    /// The values of deprecated fields are ignored when indexing new documents.
+    ///
+    /// # Errors
+    /// Returns an error if the new schema removes or modifies some of the
+    /// existing fields, changes the unique key or deprecates it,
+    /// or if an `IndexWriter` is currently opened on the index.
+    /// Other `Index` objects opened on the same directory need to be reopened
+    /// to see the new schema.
+    pub fn update_schema(&mut self, schema: Schema) -> Result<()> {
+        // prevents an `IndexWriter` from being opened while the schema is updated.
+        let _directory_lock = DirectoryLock::lock(self.directory().box_clone())?;
+        // the schema on disk may be more recent than `self.schema`.
+        let metas = self.load_metas()?;
+        check_schema_evolution(&metas.schema, &schema)?;
+        save_metas(
+            metas.segments,
+            schema.clone(),
+            metas.opstamp,
+            metas.payload,
+            self.tokenizers.definitions(),
+            self.directory_mut(),
+        )?;
+        self.schema = schema;
+        self.load_searchers()
+    }
+
     /// Returns the list of segments that are searchable
     pub fn searchable_segments(&self) -> Result<Vec<Segment>> {
         Ok(self.searchable_segment_metas()?
@@ -253,3 +413,217 @@ impl Clone for Index {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use Index;
+    use collector::CountCollector;
+    use fastfield::{FastFieldReader, U64FastFieldReader};
+    use futures::Future;
+    use query::QueryParser;
+    use schema::*;
+    use serde_json;
+    use tokenizer::{BaseTokenizerDefinition, Language, TokenFilterDefinition,
+                    TokenizerDefinition};
+
+    #[test]
+    fn test_update_schema() {
+        let mut schema_builder = SchemaBuilder::default();
+        let title = schema_builder.add_text_field("title", TEXT | STORED);
+        let legacy = schema_builder.add_text_field("legacy", TEXT);
+        let mut index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(title => "first", legacy => "old"));
+            index_writer.commit().unwrap();
+        }
+        let mut schema_builder = SchemaBuilder::from(index.schema());
+        let tags = schema_builder.add_text_field("tags", STRING | STORED);
+        let price = schema_builder.add_u64_field("price", FAST);
+        schema_builder.deprecate_field(legacy);
+        index.update_schema(schema_builder.build()).unwrap();
+        assert!(index.load_metas().unwrap().schema.get_field("tags").is_some());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(
+                title => "second",
+                legacy => "old",
+                tags => "new",
+                price => 3u64
+            ));
+            index_writer.commit().unwrap();
+        }
+        let count = |index: &Index, query_str: &str| {
+            let searcher = index.searcher();
+            let query_parser = QueryParser::for_index(index, vec![title]);
+            let query = query_parser.parse_query(query_str).unwrap();
+            let mut count_collector = CountCollector::default();
+            query.search(&*searcher, &mut count_collector).unwrap();
+            count_collector.count()
+        };
+        index.load_searchers().unwrap();
+        {
+            let searcher = index.searcher();
+            assert_eq!(searcher.segment_readers().len(), 2);
+            assert_eq!(count(&index, "tags:new"), 1);
+            // the deprecated field was ignored for the second document.
+            assert_eq!(count(&index, "legacy:old"), 1);
+            let num_price_readers = searcher
+                .segment_readers()
+                .iter()
+                .filter(|segment_reader| {
+                    segment_reader
+                        .get_fast_field_reader::<U64FastFieldReader>(price)
+                        .is_ok()
+                })
+                .count();
+            assert_eq!(num_price_readers, 1);
+        }
+        {
+            let segment_ids = index.searchable_segment_ids().unwrap();
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer
+                .merge(&segment_ids)
+                .wait()
+                .expect("Merging failed");
+            index_writer.wait_merging_threads().unwrap();
+        }
+        index.load_searchers().unwrap();
+        {
+            let searcher = index.searcher();
+            assert_eq!(searcher.segment_readers().len(), 1);
+            assert_eq!(count(&index, "tags:new"), 1);
+            assert_eq!(count(&index, "legacy:old"), 1);
+            assert_eq!(count(&index, "first OR second"), 2);
+            let price_reader = searcher.segment_readers()[0]
+                .get_fast_field_reader::<U64FastFieldReader>(price)
+                .unwrap();
+            let mut prices = vec![price_reader.get(0), price_reader.get(1)];
+            prices.sort();
+            assert_eq!(prices, vec![0u64, 3u64]);
+        }
+        {
+            // existing fields cannot be modified.
+            let mut schema_builder = SchemaBuilder::default();
+            schema_builder.add_text_field("title", STRING);
+            assert!(index.update_schema(schema_builder.build()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_update_schema_unique_key() {
+        let mut schema_builder = SchemaBuilder::default();
+        let id = schema_builder.add_text_field("id", STRING);
+        let title = schema_builder.add_text_field("title", STRING);
+        schema_builder.set_unique_key(id);
+        let mut index = Index::create_in_ram(schema_builder.build());
+        {
+            // the unique key cannot be moved to another field.
+            let mut schema_builder = SchemaBuilder::from(index.schema());
+            schema_builder.set_unique_key(title);
+            assert!(index.update_schema(schema_builder.build()).is_err());
+        }
+        {
+            // nor to a new field.
+            let mut schema_builder = SchemaBuilder::from(index.schema());
+            let uuid = schema_builder.add_text_field("uuid", STRING);
+            schema_builder.set_unique_key(uuid);
+            assert!(index.update_schema(schema_builder.build()).is_err());
+        }
+        {
+            // the unique key cannot be deprecated.
+            let mut schema_builder = SchemaBuilder::from(index.schema());
+            schema_builder.deprecate_field(id);
+            assert!(index.update_schema(schema_builder.build()).is_err());
+        }
+        let mut schema_builder = SchemaBuilder::from(index.schema());
+        schema_builder.deprecate_field(title);
+        index.update_schema(schema_builder.build()).unwrap();
+        assert_eq!(index.schema().unique_key_field(), Some(id));
+    }
+
+    #[test]
+    fn test_update_schema_concurrent_index() {
+        let mut schema_builder = SchemaBuilder::default();
+        schema_builder.add_text_field("title", TEXT);
+        let mut index = Index::create_in_ram(schema_builder.build());
+        let mut stale_index = index.clone();
+        let mut schema_builder = SchemaBuilder::from(index.schema());
+        schema_builder.add_text_field("tags", STRING);
+        index.update_schema(schema_builder.build()).unwrap();
+        // committing with the stale schema would drop the `tags` field.
+        assert!(stale_index.writer_with_num_threads(1, 40_000_000).is_err());
+        // the schema evolution is checked against the schema on disk.
+        let mut schema_builder = SchemaBuilder::from(stale_index.schema());
+        schema_builder.add_u64_field("price", FAST);
+        assert!(stale_index.update_schema(schema_builder.build()).is_err());
+        assert!(index.writer_with_num_threads(1, 40_000_000).is_ok());
+    }
+
+    #[test]
+    fn test_analyze() {
+        let mut schema_builder = SchemaBuilder::default();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let id = schema_builder.add_u64_field("id", INT_INDEXED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let analysis = index.analyze(title, "Hello, World", false).unwrap();
+        assert_eq!(
+            serde_json::to_string(&analysis).unwrap(),
+            "{\"tokens\":[\
+             {\"offset_from\":0,\"offset_to\":5,\"position\":0,\"text\":\"hello\"},\
+             {\"offset_from\":7,\"offset_to\":12,\"position\":1,\"text\":\"world\"}]}"
+        );
+        let analysis = index.analyze(title, "Hello", true).unwrap();
+        let stage_names: Vec<String> = analysis
+            .stages
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(stage_names, vec!["simple", "remove_long", "lower_caser"]);
+        assert!(index.analyze(id, "1", false).is_err());
+    }
+
+    #[test]
+    fn test_tokenizer_definitions() {
+        let mut schema_builder = SchemaBuilder::default();
+        let text_field_indexing = TextFieldIndexing::default()
+            .set_tokenizer("custom_fr")
+            .set_index_option(IndexRecordOption::WithFreqsAndPositions);
+        let text_options = TextOptions::default().set_indexing_options(text_field_indexing);
+        let text = schema_builder.add_text_field("text", text_options);
+        let index = Index::create_from_tempdir(schema_builder.build()).unwrap();
+        // the tokenizer is not registered yet.
+        assert!(index.writer_with_num_threads(1, 40_000_000).is_err());
+        let definition = TokenizerDefinition {
+            tokenizer: BaseTokenizerDefinition::Simple,
+            filters: vec![
+                TokenFilterDefinition::LowerCaser,
+                TokenFilterDefinition::Stemmer {
+                    language: Language::French,
+                },
+            ],
+        };
+        index
+            .tokenizers()
+            .register_definition("custom_fr", definition.clone())
+            .unwrap();
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(text => "Les chevaux"));
+            index_writer.commit().unwrap();
+        }
+        let metas = index.load_metas().unwrap();
+        assert_eq!(metas.tokenizers.get("custom_fr"), Some(&definition));
+        let reopened_index =
+            Index::create_from_metas(index.directory().clone(), &metas).unwrap();
+        assert!(reopened_index.tokenizers().get("custom_fr").is_some());
+        let searcher = reopened_index.searcher();
+        let query_parser = QueryParser::for_index(&reopened_index, vec![text]);
+        let query = query_parser.parse_query("cheval").unwrap();
+        let mut count_collector = CountCollector::default();
+        query.search(&*searcher, &mut count_collector).unwrap();
+        assert_eq!(count_collector.count(), 1);
+    }
+}
@@ -1,5 +1,7 @@
 use core::SegmentMeta;
 use schema::Schema;
+use std::collections::BTreeMap;
+use tokenizer::TokenizerDefinition;
 
 /// Meta information about the `Index`.
 ///
@@ -8,6 +10,8 @@ use schema::Schema;
 /// * the searchable segments,
 /// * the index `docstamp`
 /// * the schema
+/// * the definitions of the tokenizers registered
+/// with `TokenizerManager::register_definition`
 /// 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct IndexMeta {
@@ -16,6 +20,8 @@ pub struct IndexMeta {
     pub opstamp: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub payload: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tokenizers: BTreeMap<String, TokenizerDefinition>,
 }
 
 impl IndexMeta {
@@ -25,6 +31,7 @@ impl IndexMeta {
             schema,
             opstamp: 0u64,
             payload: None,
+            tokenizers: BTreeMap::new(),
         }
     }
 }
@@ -35,6 +42,7 @@ mod tests {
     use super::IndexMeta;
     use schema::{SchemaBuilder, TEXT};
     use serde_json;
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_serialize_metas() {
@@ -48,6 +56,7 @@ mod tests {
             schema: schema,
             opstamp: 0u64,
             payload: None,
+            tokenizers: BTreeMap::new(),
         };
         let json = serde_json::ser::to_string(&index_metas).expect("serialization failed");
         assert_eq!(json, r#"{"segments":[],"schema":[{"name":"text","type":"text","options":{"indexing":{"record":"position","tokenizer":"default"},"stored":false}}],"opstamp":0}"#);
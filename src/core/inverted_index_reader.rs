@@ -3,6 +3,7 @@ use directory::{ReadOnlySource, SourceRead};
 use fastfield::DeleteBitSet;
 use postings::{BlockSegmentPostings, SegmentPostings};
 use postings::TermInfo;
+use schema::FieldType;
 use schema::IndexRecordOption;
 use schema::Schema;
 use schema::Term;
@@ -48,6 +49,21 @@ impl InvertedIndexReader {
         }
     }
 
+    /// Creates an empty `InvertedIndexReader`, which contains no terms.
+    ///
+    /// It is used for the fields that are missing in a segment,
+    /// e.g. because the segment was written before the field
+    /// was added to the schema.
+    pub(crate) fn empty(field_type: FieldType, schema: Schema) -> InvertedIndexReader {
+        InvertedIndexReader {
+            termdict: TermDictionaryImpl::empty(field_type),
+            postings_source: ReadOnlySource::empty(),
+            positions_source: ReadOnlySource::empty(),
+            delete_bitset: DeleteBitSet::empty(),
+            schema,
+        }
+    }
+
     /// Returns the term info associated with the term.
     pub fn get_term_info(&self, term: &Term) -> Option<TermInfo> {
         self.termdict.get(term.as_slice())
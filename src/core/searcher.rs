@@ -5,7 +5,9 @@ use collector::Collector;
 use common::TimerTree;
 use core::InvertedIndexReader;
 use core::SegmentReader;
+use postings::DocSet;
 use query::Query;
+use schema::IndexRecordOption;
 use schema::{Field, Term};
 use schema::Document;
 use std::fmt;
@@ -32,6 +34,28 @@ impl Searcher {
         segment_reader.doc(doc_id)
     }
 
+    /// Fetches the document bearing the given unique key.
+    ///
+    /// The term of the key can be built via `Schema::unique_key_term`.
+    /// The lookup only goes through the term dictionaries, and does
+    /// not require running a query.
+    ///
+    /// Returns `None` if no document bears the key.
+    pub fn doc_by_key(&self, key_term: &Term) -> Result<Option<Document>> {
+        for segment_reader in &self.segment_readers {
+            let inverted_index = segment_reader.inverted_index(key_term.field());
+            if let Some(mut postings) =
+                inverted_index.read_postings(key_term, IndexRecordOption::Basic)
+            {
+                // deleted documents are skipped by the postings.
+                if postings.advance() {
+                    return segment_reader.doc(postings.doc()).map(Some);
+                }
+            }
+        }
+        Ok(None)
+    }
+
     /// Returns the overall number of documents in the index.
     pub fn num_docs(&self) -> DocId {
         self.segment_readers
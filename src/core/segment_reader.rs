@@ -1,5 +1,4 @@
 use DocId;
-use Result;
 use common::CompositeFile;
 use common::HasLen;
 use core::InvertedIndexReader;
@@ -7,12 +6,15 @@ use core::Segment;
 use core::SegmentComponent;
 use core::SegmentId;
 use core::SegmentMeta;
-use directory::ReadOnlySource;
+use error::{ErrorKind, Result};
 use fastfield::{self, FastFieldNotAvailableError};
 use fastfield::{FastFieldReader, U64FastFieldReader};
 use fastfield::DeleteBitSet;
+use fastfield::{BytesFastFieldReader, IpFastFieldReader};
+use fastfield::{FacetReader, MultiValueFastFieldReader, TermOrdinalsReader};
 use schema::Document;
 use schema::Field;
+use schema::FieldType;
 use schema::Schema;
 use std::collections::HashMap;
 use std::fmt;
@@ -79,7 +81,8 @@ impl SegmentReader {
     /// is a u64 field indexed as "fast".
     ///
     /// Return a FastFieldNotAvailableError if the field is not
-    /// declared as a fast field in the schema.
+    /// declared as a fast field in the schema, or if the segment
+    /// was written before the field was added to the schema.
     ///
     /// # Panics
     /// May panic if the index is corrupted.
@@ -99,6 +102,171 @@ impl SegmentReader {
         }
     }
 
+    /// Opens the fast field associated to a given `Field`,
+    /// without checking the schema.
+    ///
+    /// This makes it possible to read the `u64` representation of
+    /// the values of any single-valued fast field, regardless of its type.
+    pub(crate) fn open_fast_field_reader<TFastFieldReader: FastFieldReader>(
+        &self,
+        field: Field,
+    ) -> Option<TFastFieldReader>
+    {
+        self.fast_fields_composite
+            .open_read(field)
+            .map(TFastFieldReader::open)
+    }
+
+    /// Accessor to the `MultiValueFastFieldReader` associated to a given `Field`.
+    ///
+    /// Return a FastFieldNotAvailableError if the field is not
+    /// declared as a multi-valued fast field in the schema.
+    ///
+    /// # Panics
+    /// May panic if the index is corrupted.
+    pub fn multi_fast_field_reader<TFastFieldReader: FastFieldReader>(
+        &self,
+        field: Field,
+    ) -> fastfield::Result<MultiValueFastFieldReader<TFastFieldReader>>
+    where
+        TFastFieldReader::ValueType: Default + Clone,
+    {
+        let field_entry = self.schema.get_field_entry(field);
+        if !TFastFieldReader::is_multivalued_enabled(field_entry.field_type()) {
+            Err(FastFieldNotAvailableError::new(field_entry))
+        } else {
+            self.open_multi_fast_field_reader(field)
+                .ok_or_else(|| FastFieldNotAvailableError::new(field_entry))
+        }
+    }
+
+    /// Opens the two columns of a multi-valued fast field,
+    /// without checking the schema.
+    ///
+    /// Returns `None` if the field does not have a multi-valued fast field.
+    pub(crate) fn open_multi_fast_field_reader<TFastFieldReader: FastFieldReader>(
+        &self,
+        field: Field,
+    ) -> Option<MultiValueFastFieldReader<TFastFieldReader>>
+    where
+        TFastFieldReader::ValueType: Default + Clone,
+    {
+        let idx_source = self.fast_fields_composite.open_read_with_idx(field, 0)?;
+        let vals_source = self.fast_fields_composite.open_read_with_idx(field, 1)?;
+        Some(MultiValueFastFieldReader::open(
+            U64FastFieldReader::open(idx_source),
+            TFastFieldReader::open(vals_source),
+        ))
+    }
+
+    /// Accessor to the `BytesFastFieldReader` associated to a given `Field`.
+    ///
+    /// Return a FastFieldNotAvailableError if the field is not
+    /// declared as a bytes fast field in the schema.
+    ///
+    /// # Panics
+    /// May panic if the index is corrupted.
+    pub fn bytes_fast_field_reader(
+        &self,
+        field: Field,
+    ) -> fastfield::Result<BytesFastFieldReader>
+    {
+        let field_entry = self.schema.get_field_entry(field);
+        if !field_entry.is_bytes_fast() {
+            Err(FastFieldNotAvailableError::new(field_entry))
+        } else {
+            self.open_bytes_fast_field_reader(field)
+                .ok_or_else(|| FastFieldNotAvailableError::new(field_entry))
+        }
+    }
+
+    /// Accessor to the `IpFastFieldReader` associated to a given `Field`.
+    ///
+    /// Return a FastFieldNotAvailableError if the field is not
+    /// declared as an IP fast field in the schema.
+    ///
+    /// # Panics
+    /// May panic if the index is corrupted.
+    pub fn ip_fast_field_reader(&self, field: Field) -> fastfield::Result<IpFastFieldReader> {
+        let field_entry = self.schema.get_field_entry(field);
+        match *field_entry.field_type() {
+            FieldType::Ip(ref ip_options) if ip_options.is_fast() => self
+                .open_bytes_fast_field_reader(field)
+                .map(IpFastFieldReader::open)
+                .ok_or_else(|| FastFieldNotAvailableError::new(field_entry)),
+            _ => Err(FastFieldNotAvailableError::new(field_entry)),
+        }
+    }
+
+    /// Opens the two columns of a bytes fast field,
+    /// without checking the schema.
+    ///
+    /// Returns `None` if the field does not have a bytes fast field.
+    pub(crate) fn open_bytes_fast_field_reader(
+        &self,
+        field: Field,
+    ) -> Option<BytesFastFieldReader>
+    {
+        let idx_source = self.fast_fields_composite.open_read_with_idx(field, 0)?;
+        let values_source = self.fast_fields_composite.open_read_with_idx(field, 1)?;
+        Some(BytesFastFieldReader::open(
+            U64FastFieldReader::open(idx_source),
+            values_source,
+        ))
+    }
+
+    /// Accessor to the `FacetReader` associated to a given `Field`.
+    ///
+    /// Returns an error if the field is not a hierarchical facet field.
+    pub fn facet_reader(&self, field: Field) -> Result<FacetReader> {
+        let field_entry = self.schema.get_field_entry(field);
+        match *field_entry.field_type() {
+            FieldType::HierarchicalFacet => {}
+            _ => {
+                bail!(ErrorKind::InvalidArgument(format!(
+                    "The field {:?} is not a hierarchical facet.",
+                    field_entry.name()
+                )));
+            }
+        }
+        let term_ords_reader = self.open_multi_fast_field_reader::<U64FastFieldReader>(field)
+            .ok_or_else(|| {
+                ErrorKind::InvalidArgument(format!(
+                    "The field {:?} does not have a facet fast field.",
+                    field_entry.name()
+                ))
+            })?;
+        Ok(FacetReader::new(term_ords_reader, self.inverted_index(field)))
+    }
+
+    /// Accessor to the `TermOrdinalsReader` associated to a given `Field`.
+    ///
+    /// Returns an error if the field is not a fast text field.
+    pub fn term_ordinals_reader(&self, field: Field) -> Result<TermOrdinalsReader> {
+        let field_entry = self.schema.get_field_entry(field);
+        let is_fast_text = match *field_entry.field_type() {
+            FieldType::Str(_) => field_entry.is_term_ord_fast(),
+            _ => false,
+        };
+        if !is_fast_text {
+            bail!(ErrorKind::InvalidArgument(format!(
+                "The field {:?} is not a fast text field.",
+                field_entry.name()
+            )));
+        }
+        let term_ords_reader = self.open_multi_fast_field_reader::<U64FastFieldReader>(field)
+            .ok_or_else(|| {
+                ErrorKind::InvalidArgument(format!(
+                    "The field {:?} does not have a term ordinals fast field.",
+                    field_entry.name()
+                ))
+            })?;
+        Ok(TermOrdinalsReader::new(
+            term_ords_reader,
+            self.inverted_index(field),
+        ))
+    }
+
     /// Accessor to the segment's `Field norms`'s reader.
     ///
     /// Field norms are the length (in tokens) of the fields.
@@ -171,6 +339,10 @@ impl SegmentReader {
     /// The field reader is in charge of iterating through the
     /// term dictionary associated to a specific field,
     /// and opening the posting list associated to any term.
+    ///
+    /// If the field does not have any term in the segment (e.g. the
+    /// segment was written before the field was added to the schema),
+    /// the returned reader is empty.
     pub fn inverted_index(&self, field: Field) -> Arc<InvertedIndexReader> {
         if let Some(inv_idx_reader) = self.inv_idx_reader_cache
             .read()
@@ -180,25 +352,30 @@ impl SegmentReader {
             Arc::clone(inv_idx_reader);
         }
 
-        let termdict_source: ReadOnlySource = self.termdict_composite
-            .open_read(field)
-            .expect("Index corrupted. Failed to open field term dictionary in composite file.");
+        let termdict_source_opt = self.termdict_composite.open_read(field);
+        let inv_idx_reader = if let Some(termdict_source) = termdict_source_opt {
+            let postings_source = self.postings_composite
+                .open_read(field)
+                .expect("Index corrupted. Failed to open field postings in composite file.");
 
-        let postings_source = self.postings_composite
-            .open_read(field)
-            .expect("Index corrupted. Failed to open field postings in composite file.");
+            let positions_source = self.positions_composite
+                .open_read(field)
+                .expect("Index corrupted. Failed to open field positions in composite file.");
 
-        let positions_source = self.positions_composite
-            .open_read(field)
-            .expect("Index corrupted. Failed to open field positions in composite file.");
-
-        let inv_idx_reader = Arc::new(InvertedIndexReader::new(
-            termdict_source,
-            postings_source,
-            positions_source,
-            self.delete_bitset.clone(),
-            self.schema.clone(),
-        ));
+            Arc::new(InvertedIndexReader::new(
+                termdict_source,
+                postings_source,
+                positions_source,
+                self.delete_bitset.clone(),
+                self.schema.clone(),
+            ))
+        } else {
+            // the field does not have any term in this segment, for instance
+            // because the segment was written before the field was added to
+            // the schema.
+            let field_type = self.schema.get_field_entry(field).field_type().clone();
+            Arc::new(InvertedIndexReader::empty(field_type, self.schema.clone()))
+        };
 
         // by releasing the lock in between, we may end up opening the inverting index
         // twice, but this is fine.
@@ -169,6 +169,18 @@ impl<'a> HashMap<'a> {
     }
 
     pub fn get_or_create<S: AsRef<[u8]>, V: HeapAllocable>(&mut self, key: S) -> &mut V {
+        self.get_or_create_with_addr(key).1
+    }
+
+    /// Same as `get_or_create`, but also returns the address of the
+    /// value within the heap.
+    ///
+    /// This address is the same as the one returned by `.iter()`, and
+    /// can be used as a (non-sorted) unique identifier of the key.
+    pub fn get_or_create_with_addr<S: AsRef<[u8]>, V: HeapAllocable>(
+        &mut self,
+        key: S,
+    ) -> (u32, &mut V) {
         let key_bytes: &[u8] = key.as_ref();
         let hash = murmurhash2::murmurhash2(key.as_ref());
         let mut probe = self.probe(hash);
@@ -180,11 +192,11 @@ impl<'a> HashMap<'a> {
                 let (addr, val): (u32, &mut V) = self.heap.allocate_object();
                 assert_eq!(addr, key_bytes_ref.addr() + 2 + key_bytes.len() as u32);
                 self.set_bucket(hash, key_bytes_ref, bucket);
-                return val;
+                return (addr, val);
             } else if kv.hash == hash {
                 let (stored_key, expull_addr): (&[u8], u32) = self.get_key_value(kv.key_value_addr);
                 if stored_key == key_bytes {
-                    return self.heap.get_mut_ref(expull_addr);
+                    return (expull_addr, self.heap.get_mut_ref(expull_addr));
                 }
             }
         }
@@ -0,0 +1,34 @@
+use DocId;
+use fastfield::BytesFastFieldReader;
+use std::net::Ipv6Addr;
+
+/// Reader for an IP fast field.
+///
+/// The addresses are stored as the 16 bytes of their IPv6
+/// representation in a bytes fast field.
+///
+/// `IpFastFieldReader`s are obtained via
+/// `SegmentReader::ip_fast_field_reader`.
+pub struct IpFastFieldReader {
+    bytes_reader: BytesFastFieldReader,
+}
+
+impl IpFastFieldReader {
+    pub(crate) fn open(bytes_reader: BytesFastFieldReader) -> IpFastFieldReader {
+        IpFastFieldReader { bytes_reader }
+    }
+
+    /// Returns the IP address associated to the document `doc`,
+    /// IPv4 addresses being mapped into IPv6.
+    ///
+    /// Returns `None` if the document does not have any address.
+    pub fn get(&self, doc: DocId) -> Option<Ipv6Addr> {
+        let bytes = self.bytes_reader.get_val(doc);
+        if bytes.len() != 16 {
+            return None;
+        }
+        let mut octets = [0u8; 16];
+        octets.copy_from_slice(bytes);
+        Some(Ipv6Addr::from(octets))
+    }
+}
@@ -0,0 +1,136 @@
+mod writer;
+mod reader;
+mod ip_reader;
+
+pub use self::ip_reader::IpFastFieldReader;
+pub use self::reader::BytesFastFieldReader;
+pub use self::writer::BytesFastFieldWriter;
+
+#[cfg(test)]
+mod tests {
+
+    use Index;
+    use collector::CountCollector;
+    use futures::Future;
+    use query::{Query, QueryParser, TermQuery};
+    use schema::{Document, IndexRecordOption, SchemaBuilder, Term};
+    use schema::{FAST, INT_INDEXED, INT_STORED};
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn test_bytes_fastfield() {
+        let mut schema_builder = SchemaBuilder::default();
+        let bytes_field =
+            schema_builder.add_bytes_field("bytesfield", FAST | INT_INDEXED | INT_STORED);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            {
+                let mut doc = Document::default();
+                doc.add_bytes(bytes_field, vec![0u8, 1u8, 2u8, 3u8]);
+                index_writer.add_document(doc);
+            }
+            index_writer.add_document(Document::default());
+            index_writer.commit().unwrap();
+            {
+                let mut doc = Document::default();
+                doc.add_bytes(bytes_field, vec![255u8]);
+                doc.add_bytes(bytes_field, vec![0u8, 1u8, 2u8, 3u8]);
+                index_writer.add_document(doc);
+            }
+            index_writer.commit().unwrap();
+        }
+        let check = |index: &Index| {
+            index.load_searchers().unwrap();
+            let searcher = index.searcher();
+            let mut all_vals: Vec<Vec<u8>> = Vec::new();
+            for segment_reader in searcher.segment_readers() {
+                let bytes_reader = segment_reader.bytes_fast_field_reader(bytes_field).unwrap();
+                for doc in 0..segment_reader.max_doc() {
+                    all_vals.push(bytes_reader.get_val(doc).to_owned());
+                }
+            }
+            // segments are not guaranteed to be visited in any specific order.
+            all_vals.sort();
+            assert_eq!(all_vals, vec![vec![], vec![0u8, 1u8, 2u8, 3u8], vec![255u8]]);
+            let term = Term::from_field_bytes(bytes_field, &[0u8, 1u8, 2u8, 3u8]);
+            let query = TermQuery::new(term, IndexRecordOption::Basic);
+            let mut count_collector = CountCollector::default();
+            query.search(&*searcher, &mut count_collector).unwrap();
+            assert_eq!(count_collector.count(), 2);
+        };
+        check(&index);
+        {
+            let segment_ids = index
+                .searchable_segment_ids()
+                .expect("Searchable segments failed.");
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer
+                .merge(&segment_ids)
+                .wait()
+                .expect("Merging failed");
+            index_writer.wait_merging_threads().unwrap();
+        }
+        check(&index);
+    }
+
+    #[test]
+    fn test_bytes_fastfield_not_available() {
+        let mut schema_builder = SchemaBuilder::default();
+        let bytes_field = schema_builder.add_bytes_field("bytesfield", INT_STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            let mut doc = Document::default();
+            doc.add_bytes(bytes_field, vec![1u8]);
+            index_writer.add_document(doc);
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let segment_reader = &searcher.segment_readers()[0];
+        assert!(segment_reader.bytes_fast_field_reader(bytes_field).is_err());
+    }
+
+    #[test]
+    fn test_ip_fastfield() {
+        let mut schema_builder = SchemaBuilder::default();
+        let ip_field = schema_builder.add_ip_field("src_ip", FAST | INT_INDEXED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let ips = vec!["10.0.0.1", "10.200.3.4", "192.168.0.1", "2001:db8::1"];
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            for ip in &ips {
+                let mut doc = Document::default();
+                doc.add_ip(ip_field, ip.parse().unwrap());
+                index_writer.add_document(doc);
+            }
+            index_writer.add_document(Document::default());
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let segment_reader = &searcher.segment_readers()[0];
+        let ip_reader = segment_reader.ip_fast_field_reader(ip_field).unwrap();
+        let ipv4 = Ipv4Addr::new(10, 200, 3, 4);
+        assert_eq!(ip_reader.get(1), Some(ipv4.to_ipv6_mapped()));
+        assert_eq!(
+            ip_reader.get(3).map(IpAddr::V6),
+            Some("2001:db8::1".parse().unwrap())
+        );
+        assert_eq!(ip_reader.get(4), None);
+        let query_parser = QueryParser::for_index(&index, vec![]);
+        let count = |query_str: &str| {
+            let query = query_parser.parse_query(query_str).unwrap();
+            let mut count_collector = CountCollector::default();
+            query.search(&*searcher, &mut count_collector).unwrap();
+            count_collector.count()
+        };
+        assert_eq!(count("src_ip:10.0.0.0/8"), 2);
+        assert_eq!(count("src_ip:10.0.0.0/16"), 1);
+        assert_eq!(count("src_ip:192.168.0.1"), 1);
+        assert_eq!(count("src_ip:[10.0.0.2 TO 192.168.0.1]"), 2);
+        assert_eq!(count("src_ip:\"2001:db8::/32\""), 1);
+    }
+}
@@ -0,0 +1,34 @@
+use DocId;
+use directory::ReadOnlySource;
+use fastfield::{FastFieldReader, U64FastFieldReader};
+
+/// Reader for a bytes fast field.
+///
+/// The reader is implemented as a `u64` fast field,
+/// associating each document to the offset of its bytes,
+/// and the concatenation of the bytes of all of the documents.
+///
+/// `BytesFastFieldReader`s are obtained via
+/// `SegmentReader::bytes_fast_field_reader`.
+pub struct BytesFastFieldReader {
+    idx_reader: U64FastFieldReader,
+    values: ReadOnlySource,
+}
+
+impl BytesFastFieldReader {
+    pub(crate) fn open(
+        idx_reader: U64FastFieldReader,
+        values: ReadOnlySource,
+    ) -> BytesFastFieldReader {
+        BytesFastFieldReader { idx_reader, values }
+    }
+
+    /// Returns the bytes associated to the document `doc`.
+    ///
+    /// Documents without any value are associated to an empty slice.
+    pub fn get_val(&self, doc: DocId) -> &[u8] {
+        let start = self.idx_reader.get(doc) as usize;
+        let stop = self.idx_reader.get(doc + 1) as usize;
+        &self.values.as_slice()[start..stop]
+    }
+}
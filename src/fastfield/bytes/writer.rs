@@ -0,0 +1,75 @@
+use fastfield::FastFieldSerializer;
+use schema::{Document, Field, Value};
+use std::io;
+
+/// Writer for bytes fast fields.
+///
+/// The bytes are stored in two columns:
+/// - the `idx` column (index 0) is a `u64` fast field storing, for each
+/// document, the offset of its bytes within the `vals` column. It contains
+/// `max_doc + 1` values, so that the bytes of the document `doc` are
+/// `vals[idx[doc]..idx[doc + 1]]`.
+/// - the `vals` column (index 1) stores all of the bytes, concatenated.
+///
+/// Only the first value of each document is recorded.
+/// Documents without any value are associated to an empty slice.
+///
+/// The writer is also used for IP fields, in which case
+/// the 16 bytes of the addresses are recorded.
+pub struct BytesFastFieldWriter {
+    field: Field,
+    vals: Vec<u8>,
+    doc_index: Vec<u64>,
+}
+
+impl BytesFastFieldWriter {
+    /// Creates a new `BytesFastFieldWriter`
+    pub fn new(field: Field) -> BytesFastFieldWriter {
+        BytesFastFieldWriter {
+            field,
+            vals: Vec::new(),
+            doc_index: Vec::new(),
+        }
+    }
+
+    /// Returns the field this writer is associated to.
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// Records the bytes of a new document.
+    pub fn add_document_val(&mut self, val: &[u8]) {
+        self.doc_index.push(self.vals.len() as u64);
+        self.vals.extend_from_slice(val);
+    }
+
+    /// Extracts the bytes of the document, and records them.
+    pub fn add_document(&mut self, doc: &Document) {
+        self.doc_index.push(self.vals.len() as u64);
+        match doc.get_first(self.field) {
+            Some(&Value::Bytes(ref bytes)) => {
+                self.vals.extend_from_slice(bytes);
+            }
+            Some(&Value::Ip(ref ip)) => {
+                self.vals.extend_from_slice(&ip.octets());
+            }
+            Some(value) => panic!("Expected a bytes field, got {:?} ", value),
+            None => {}
+        }
+    }
+
+    /// Serializes the bytes fast field.
+    pub fn serialize(&self, serializer: &mut FastFieldSerializer) -> io::Result<()> {
+        {
+            let num_bytes = self.vals.len() as u64;
+            let mut idx_serializer =
+                serializer.new_u64_fast_field_with_idx(self.field, 0, num_bytes, 0)?;
+            for &offset in &self.doc_index {
+                idx_serializer.add_val(offset)?;
+            }
+            idx_serializer.add_val(num_bytes)?;
+            idx_serializer.close_field()?;
+        }
+        serializer.write_bytes_with_idx(self.field, 1, &self.vals)
+    }
+}
@@ -0,0 +1,69 @@
+use DocId;
+use core::InvertedIndexReader;
+use fastfield::MultiValueFastFieldReader;
+use schema::Facet;
+use std::str;
+use std::sync::Arc;
+use termdict::{TermDictionary, TermDictionaryImpl, TermOrdinal};
+
+/// The facet reader makes it possible to access the list of
+/// facets associated to a given document in a specific
+/// segment.
+///
+/// Rather than manipulating facets directly, the facet
+/// reader manipulates term ordinals relative to this
+/// segment's term dictionary. For instance, during collection,
+/// it is sufficient to collect facet term ordinals.
+///
+/// Note that the facet ordinals of a document include
+/// the ordinals of all of the ancestors of its facets.
+pub struct FacetReader {
+    term_ords: MultiValueFastFieldReader,
+    inverted_index: Arc<InvertedIndexReader>,
+    buffer: Vec<u8>,
+}
+
+impl FacetReader {
+    /// Creates a new `FacetReader`.
+    ///
+    /// A facet reader just wraps :
+    /// - a `MultiValueFastFieldReader` that makes it possible to
+    /// access the list of facet ords for a given document.
+    /// - the inverted index of the field, whose term dictionary
+    /// associates each facet ordinal to its facet.
+    pub fn new(
+        term_ords: MultiValueFastFieldReader,
+        inverted_index: Arc<InvertedIndexReader>,
+    ) -> FacetReader {
+        FacetReader {
+            term_ords,
+            inverted_index,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Accessor for the facet term dictionary.
+    pub fn facet_dict(&self) -> &TermDictionaryImpl {
+        self.inverted_index.terms()
+    }
+
+    /// Returns the facet associated to a given term ordinal,
+    /// or `None` if the ordinal is out of bounds.
+    pub fn facet_from_ord(&mut self, facet_ord: TermOrdinal) -> Option<Facet> {
+        if !self.inverted_index
+            .terms()
+            .ord_to_term(facet_ord, &mut self.buffer)
+        {
+            return None;
+        }
+        // the first 4 bytes of the term encode the field.
+        let encoded = str::from_utf8(&self.buffer[4..]).expect("Facet term is not valid utf-8.");
+        Some(Facet::from_encoded(encoded.to_string()))
+    }
+
+    /// Fills `output` with the sorted list of facet ordinals
+    /// associated to the document `doc`.
+    pub fn facet_ords(&self, doc: DocId, output: &mut Vec<u64>) {
+        self.term_ords.get_vals(doc, output);
+    }
+}
@@ -13,8 +13,7 @@
 //
 //
 // Fields have to be declared as `FAST` in the  schema.
-// Currently only 64-bits integers (signed or unsigned) are
-// supported.
+// Numerical fields, dates, bytes and IP addresses are supported.
 //
 // They are stored in a bit-packed fashion so that their
 // memory usage is directly linear with the amplitude of the
@@ -28,14 +27,23 @@ mod writer;
 mod serializer;
 mod error;
 mod delete;
+mod multivalued;
+mod bytes;
+mod facet_reader;
+mod term_ordinals_reader;
 
+pub use self::bytes::{BytesFastFieldReader, BytesFastFieldWriter, IpFastFieldReader};
 pub use self::delete::DeleteBitSet;
 pub use self::delete::write_delete_bitset;
 pub use self::error::{FastFieldNotAvailableError, Result};
-pub use self::reader::{I64FastFieldReader, U64FastFieldReader};
-pub use self::reader::FastFieldReader;
+pub use self::facet_reader::FacetReader;
+pub use self::multivalued::{MultiValueFastFieldReader, MultiValueFastFieldWriter};
+pub use self::reader::{F64FastFieldReader, I64FastFieldReader, U64FastFieldReader};
+pub use self::reader::{FastFieldReader, FastValue};
 pub use self::serializer::FastFieldSerializer;
+pub use self::term_ordinals_reader::TermOrdinalsReader;
 pub use self::writer::{FastFieldsWriter, IntFastFieldWriter};
+pub(crate) use self::writer::val_if_missing;
 
 #[cfg(test)]
 mod tests {
@@ -50,6 +58,7 @@ mod tests {
     use schema::Document;
     use schema::FAST;
     use schema::Field;
+    use std::collections::HashMap;
     use std::path::Path;
     use test;
     use test::Bencher;
@@ -90,7 +99,9 @@ mod tests {
             add_single_field_doc(&mut fast_field_writers, *FIELD, 13u64);
             add_single_field_doc(&mut fast_field_writers, *FIELD, 14u64);
             add_single_field_doc(&mut fast_field_writers, *FIELD, 2u64);
-            fast_field_writers.serialize(&mut serializer).unwrap();
+            fast_field_writers
+                .serialize(&mut serializer, &HashMap::new())
+                .unwrap();
             serializer.close().unwrap();
         }
         let source = directory.open_read(&path).unwrap();
@@ -124,7 +135,9 @@ mod tests {
             add_single_field_doc(&mut fast_field_writers, *FIELD, 1_002u64);
             add_single_field_doc(&mut fast_field_writers, *FIELD, 1_501u64);
             add_single_field_doc(&mut fast_field_writers, *FIELD, 215u64);
-            fast_field_writers.serialize(&mut serializer).unwrap();
+            fast_field_writers
+                .serialize(&mut serializer, &HashMap::new())
+                .unwrap();
             serializer.close().unwrap();
         }
         let source = directory.open_read(&path).unwrap();
@@ -159,7 +172,9 @@ mod tests {
             for _ in 0..10_000 {
                 add_single_field_doc(&mut fast_field_writers, *FIELD, 100_000u64);
             }
-            fast_field_writers.serialize(&mut serializer).unwrap();
+            fast_field_writers
+                .serialize(&mut serializer, &HashMap::new())
+                .unwrap();
             serializer.close().unwrap();
         }
         let source = directory.open_read(&path).unwrap();
@@ -194,7 +209,9 @@ mod tests {
                     5_000_000_000_000_000_000u64 + i,
                 );
             }
-            fast_field_writers.serialize(&mut serializer).unwrap();
+            fast_field_writers
+                .serialize(&mut serializer, &HashMap::new())
+                .unwrap();
             serializer.close().unwrap();
         }
         let source = directory.open_read(&path).unwrap();
@@ -233,7 +250,9 @@ mod tests {
                 doc.add_i64(i64_field, i);
                 fast_field_writers.add_document(&doc);
             }
-            fast_field_writers.serialize(&mut serializer).unwrap();
+            fast_field_writers
+                .serialize(&mut serializer, &HashMap::new())
+                .unwrap();
             serializer.close().unwrap();
         }
         let source = directory.open_read(&path).unwrap();
@@ -272,7 +291,9 @@ mod tests {
             let mut fast_field_writers = FastFieldsWriter::from_schema(&schema);
             let doc = Document::default();
             fast_field_writers.add_document(&doc);
-            fast_field_writers.serialize(&mut serializer).unwrap();
+            fast_field_writers
+                .serialize(&mut serializer, &HashMap::new())
+                .unwrap();
             serializer.close().unwrap();
         }
 
@@ -285,6 +306,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_f64_fastfield() {
+        let path = Path::new("test");
+        let mut directory: RAMDirectory = RAMDirectory::create();
+        let mut schema_builder = SchemaBuilder::new();
+        let f64_field = schema_builder.add_f64_field("field", FAST);
+        let schema = schema_builder.build();
+        let vals: Vec<f64> = (-100i64..1_000i64).map(|i| i as f64 / 4f64).collect();
+        {
+            let write: WritePtr = directory.open_write(Path::new("test")).unwrap();
+            let mut serializer = FastFieldSerializer::from_write(write).unwrap();
+            let mut fast_field_writers = FastFieldsWriter::from_schema(&schema);
+            for &val in &vals {
+                let mut doc = Document::default();
+                doc.add_f64(f64_field, val);
+                fast_field_writers.add_document(&doc);
+            }
+            fast_field_writers.add_document(&Document::default());
+            fast_field_writers
+                .serialize(&mut serializer, &HashMap::new())
+                .unwrap();
+            serializer.close().unwrap();
+        }
+        let source = directory.open_read(&path).unwrap();
+        {
+            let fast_fields_composite = CompositeFile::open(&source).unwrap();
+            let fast_field_reader: F64FastFieldReader =
+                F64FastFieldReader::open(fast_fields_composite.open_read(f64_field).unwrap());
+            assert_eq!(fast_field_reader.min_value(), -25f64);
+            assert_eq!(fast_field_reader.max_value(), 249.75f64);
+            for (doc, &val) in vals.iter().enumerate() {
+                assert_eq!(fast_field_reader.get(doc as u32), val);
+            }
+            // missing values default to 0.
+            assert_eq!(fast_field_reader.get(vals.len() as u32), 0f64);
+            let mut buffer = vec![0f64; 10];
+            fast_field_reader.get_range(3, &mut buffer[..]);
+            assert_eq!(&buffer[..], &vals[3..13]);
+        }
+    }
+
     fn generate_permutation() -> Vec<u64> {
         let seed: &[u32; 4] = &[1, 2, 3, 4];
         let mut rng = XorShiftRng::from_seed(*seed);
@@ -306,7 +368,9 @@ mod tests {
             for x in &permutation {
                 add_single_field_doc(&mut fast_field_writers, *FIELD, *x);
             }
-            fast_field_writers.serialize(&mut serializer).unwrap();
+            fast_field_writers
+                .serialize(&mut serializer, &HashMap::new())
+                .unwrap();
             serializer.close().unwrap();
         }
         let source = directory.open_read(&path).unwrap();
@@ -361,7 +425,9 @@ mod tests {
             for x in &permutation {
                 add_single_field_doc(&mut fast_field_writers, *FIELD, *x);
             }
-            fast_field_writers.serialize(&mut serializer).unwrap();
+            fast_field_writers
+                .serialize(&mut serializer, &HashMap::new())
+                .unwrap();
             serializer.close().unwrap();
         }
         let source = directory.open_read(&path).unwrap();
@@ -393,7 +459,9 @@ mod tests {
             for x in &permutation {
                 add_single_field_doc(&mut fast_field_writers, *FIELD, *x);
             }
-            fast_field_writers.serialize(&mut serializer).unwrap();
+            fast_field_writers
+                .serialize(&mut serializer, &HashMap::new())
+                .unwrap();
             serializer.close().unwrap();
         }
         let source = directory.open_read(&path).unwrap();
@@ -0,0 +1,107 @@
+mod writer;
+mod reader;
+
+pub use self::reader::MultiValueFastFieldReader;
+pub use self::writer::MultiValueFastFieldWriter;
+
+#[cfg(test)]
+mod tests {
+
+    use Index;
+    use fastfield::{I64FastFieldReader, U64FastFieldReader};
+    use futures::Future;
+    use schema::{Document, IntOptions, SchemaBuilder};
+
+    #[test]
+    fn test_multivalued_u64_and_i64() {
+        let mut schema_builder = SchemaBuilder::default();
+        let u64_field = schema_builder.add_u64_field(
+            "multifield_u64",
+            IntOptions::default().set_multivalued_fast(),
+        );
+        let i64_field = schema_builder.add_i64_field(
+            "multifield_i64",
+            IntOptions::default().set_multivalued_fast(),
+        );
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            {
+                let mut doc = Document::default();
+                doc.add_u64(u64_field, 3);
+                doc.add_u64(u64_field, 1);
+                doc.add_i64(i64_field, -2);
+                index_writer.add_document(doc);
+            }
+            index_writer.add_document(Document::default());
+            index_writer.commit().unwrap();
+            {
+                let mut doc = Document::default();
+                doc.add_u64(u64_field, 17);
+                doc.add_i64(i64_field, 5);
+                doc.add_i64(i64_field, -7);
+                doc.add_i64(i64_field, 5);
+                index_writer.add_document(doc);
+            }
+            index_writer.commit().unwrap();
+        }
+        let check = |index: &Index, expected_u64: &[Vec<u64>], expected_i64: &[Vec<i64>]| {
+            index.load_searchers().unwrap();
+            let searcher = index.searcher();
+            let mut u64_vals = Vec::new();
+            let mut i64_vals = Vec::new();
+            let mut all_u64_vals = Vec::new();
+            let mut all_i64_vals = Vec::new();
+            for segment_reader in searcher.segment_readers() {
+                let u64_reader = segment_reader
+                    .multi_fast_field_reader::<U64FastFieldReader>(u64_field)
+                    .unwrap();
+                let i64_reader = segment_reader
+                    .multi_fast_field_reader::<I64FastFieldReader>(i64_field)
+                    .unwrap();
+                for doc in 0..segment_reader.max_doc() {
+                    u64_reader.get_vals(doc, &mut u64_vals);
+                    assert_eq!(u64_reader.num_vals(doc), u64_vals.len());
+                    all_u64_vals.push(u64_vals.clone());
+                    i64_reader.get_vals(doc, &mut i64_vals);
+                    all_i64_vals.push(i64_vals.clone());
+                }
+            }
+            // segments are not guaranteed to be visited in any specific order.
+            all_u64_vals.sort();
+            all_i64_vals.sort();
+            assert_eq!(&all_u64_vals[..], expected_u64);
+            assert_eq!(&all_i64_vals[..], expected_i64);
+        };
+        let expected_u64 = vec![vec![], vec![3, 1], vec![17]];
+        let expected_i64 = vec![vec![], vec![-2], vec![5, -7, 5]];
+        check(&index, &expected_u64, &expected_i64);
+        {
+            let searcher = index.searcher();
+            let segment_reader = &searcher.segment_readers()[0];
+            assert!(
+                segment_reader
+                    .get_fast_field_reader::<U64FastFieldReader>(u64_field)
+                    .is_err()
+            );
+            assert!(
+                segment_reader
+                    .multi_fast_field_reader::<I64FastFieldReader>(u64_field)
+                    .is_err()
+            );
+        }
+        {
+            let segment_ids = index
+                .searchable_segment_ids()
+                .expect("Searchable segments failed.");
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer
+                .merge(&segment_ids)
+                .wait()
+                .expect("Merging failed");
+            index_writer.wait_merging_threads().unwrap();
+        }
+        check(&index, &expected_u64, &expected_i64);
+    }
+}
@@ -0,0 +1,61 @@
+use DocId;
+use fastfield::{FastFieldReader, U64FastFieldReader};
+
+/// Reader for a multivalued `u64` or `i64` fast field.
+///
+/// The reader is implemented as two `u64` fast field.
+///
+/// The `vals_reader` will access the concatenated list of all
+/// values for all reader.
+/// The `idx_reader` associated, for each document, the index of its first value.
+///
+/// `MultiValueFastFieldReader`s are obtained via
+/// `SegmentReader::multi_fast_field_reader`, and are typically
+/// opened by collectors in their `.set_segment(...)` method.
+pub struct MultiValueFastFieldReader<TFastFieldReader = U64FastFieldReader>
+where
+    TFastFieldReader: FastFieldReader,
+{
+    idx_reader: U64FastFieldReader,
+    vals_reader: TFastFieldReader,
+}
+
+impl<TFastFieldReader> MultiValueFastFieldReader<TFastFieldReader>
+where
+    TFastFieldReader: FastFieldReader,
+    TFastFieldReader::ValueType: Default + Clone,
+{
+    pub(crate) fn open(
+        idx_reader: U64FastFieldReader,
+        vals_reader: TFastFieldReader,
+    ) -> MultiValueFastFieldReader<TFastFieldReader> {
+        MultiValueFastFieldReader {
+            idx_reader,
+            vals_reader,
+        }
+    }
+
+    /// Returns `(start, stop)`, such that the values associated
+    /// to the given document are `start..stop`.
+    fn range(&self, doc: DocId) -> (u64, u64) {
+        let start = self.idx_reader.get(doc);
+        let stop = self.idx_reader.get(doc + 1);
+        (start, stop)
+    }
+
+    /// Returns the number of values associated to the document `doc`.
+    pub fn num_vals(&self, doc: DocId) -> usize {
+        let (start, stop) = self.range(doc);
+        (stop - start) as usize
+    }
+
+    /// Fills `vals` with the values associated to the document `doc`.
+    ///
+    /// The content of `vals` is overwritten.
+    pub fn get_vals(&self, doc: DocId, vals: &mut Vec<TFastFieldReader::ValueType>) {
+        let (start, stop) = self.range(doc);
+        let len = (stop - start) as usize;
+        vals.resize(len, TFastFieldReader::ValueType::default());
+        self.vals_reader.get_range(start as u32, &mut vals[..]);
+    }
+}
@@ -0,0 +1,166 @@
+use common;
+use fastfield::FastFieldSerializer;
+use itertools::Itertools;
+use postings::UnorderedTermId;
+use schema::{DatePrecision, Document, Field, Value};
+use std::collections::HashMap;
+use std::io;
+use termdict::TermOrdinal;
+
+/// Writer for multi-valued (as in, more than one value per document)
+/// int fast field.
+///
+/// The values are stored in two columns:
+/// - the `idx` column (index 0) stores, for each document, the offset
+/// of its first value within the `vals` column. It contains `max_doc + 1`
+/// values, so that the values of the document `doc` are
+/// `vals[idx[doc]..idx[doc + 1]]`.
+/// - the `vals` column (index 1) stores all of the values, concatenated.
+///
+/// For numerical fields, the values are extracted from the document,
+/// i64 and f64 being remapped to u64 using `common::i64_to_u64`
+/// and `common::f64_to_u64`. Dates are recorded as their `i64`
+/// timestamp, in the unit of the precision of the field.
+///
+/// For hierarchical facets and fast text fields, the values recorded
+/// are `UnorderedTermId`s, pushed by the `SegmentWriter`.
+/// They are remapped to `TermOrdinal`s at serialization, and
+/// the values of each document are then sorted and deduplicated.
+pub struct MultiValueFastFieldWriter {
+    field: Field,
+    vals: Vec<u64>,
+    doc_index: Vec<u64>,
+    is_term_ord: bool,
+    date_precision: DatePrecision,
+}
+
+impl MultiValueFastFieldWriter {
+    /// Creates a new `MultiValueFastFieldWriter`
+    pub(crate) fn new(field: Field, is_term_ord: bool) -> MultiValueFastFieldWriter {
+        MultiValueFastFieldWriter {
+            field,
+            vals: Vec::new(),
+            doc_index: Vec::new(),
+            is_term_ord,
+            date_precision: DatePrecision::default(),
+        }
+    }
+
+    /// Sets the precision used to record the values of a date field.
+    pub(crate) fn set_date_precision(&mut self, date_precision: DatePrecision) {
+        self.date_precision = date_precision;
+    }
+
+    /// Returns the field this writer is associated to.
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// Opens a new document.
+    ///
+    /// All of the values added until the next call to
+    /// `.next_doc()` are associated to this document.
+    pub fn next_doc(&mut self) {
+        self.doc_index.push(self.vals.len() as u64);
+    }
+
+    /// Records a new value for the current document.
+    pub fn add_val(&mut self, val: u64) {
+        self.vals.push(val);
+    }
+
+    /// Opens a new document, and records all of its values.
+    ///
+    /// For term ordinal fields, values are not extracted from the document
+    /// as they need to be converted to term ids first.
+    pub fn add_document(&mut self, doc: &Document) {
+        self.next_doc();
+        if self.is_term_ord {
+            return;
+        }
+        for value in doc.get_all(self.field) {
+            let val = match *value {
+                Value::U64(ref val) => *val,
+                Value::I64(ref val) => common::i64_to_u64(*val),
+                Value::F64(ref val) => common::f64_to_u64(*val),
+                Value::Date(ref date) => {
+                    common::i64_to_u64(self.date_precision.date_to_timestamp(date))
+                }
+                _ => panic!("Expected a int field, got {:?} ", value),
+            };
+            self.vals.push(val);
+        }
+    }
+
+    /// Serializes the multi-valued fast field.
+    ///
+    /// If a mapping is given, values are considered as `UnorderedTermId`s
+    /// and are remapped to their `TermOrdinal`.
+    pub fn serialize(
+        &self,
+        serializer: &mut FastFieldSerializer,
+        mapping_opt: Option<&HashMap<UnorderedTermId, TermOrdinal>>,
+    ) -> io::Result<()> {
+        let (doc_index, vals): (Vec<u64>, Vec<u64>) = match mapping_opt {
+            Some(mapping) => self.remap_vals(mapping),
+            None => {
+                let mut doc_index = self.doc_index.clone();
+                doc_index.push(self.vals.len() as u64);
+                (doc_index, self.vals.clone())
+            }
+        };
+        {
+            let num_vals = vals.len() as u64;
+            let mut idx_serializer =
+                serializer.new_u64_fast_field_with_idx(self.field, 0, num_vals, 0)?;
+            for &offset in &doc_index {
+                idx_serializer.add_val(offset)?;
+            }
+            idx_serializer.close_field()?;
+        }
+        {
+            let (min_val, max_val) = vals.iter()
+                .cloned()
+                .minmax()
+                .into_option()
+                .unwrap_or((0u64, 0u64));
+            let mut vals_serializer =
+                serializer.new_u64_fast_field_with_idx(self.field, min_val, max_val, 1)?;
+            for &val in &vals {
+                vals_serializer.add_val(val)?;
+            }
+            vals_serializer.close_field()?;
+        }
+        Ok(())
+    }
+
+    /// Remaps the `UnorderedTermId`s to `TermOrdinal`s.
+    ///
+    /// Returns the new `idx` and `vals` columns.
+    fn remap_vals(&self, mapping: &HashMap<UnorderedTermId, TermOrdinal>) -> (Vec<u64>, Vec<u64>) {
+        let mut doc_index: Vec<u64> = Vec::with_capacity(self.doc_index.len() + 1);
+        let mut vals: Vec<u64> = Vec::with_capacity(self.vals.len());
+        let mut doc_vals: Vec<u64> = Vec::new();
+        let num_docs = self.doc_index.len();
+        for doc in 0..num_docs {
+            let start = self.doc_index[doc] as usize;
+            let stop = if doc + 1 < num_docs {
+                self.doc_index[doc + 1] as usize
+            } else {
+                self.vals.len()
+            };
+            doc_vals.clear();
+            doc_vals.extend(self.vals[start..stop].iter().map(|&unordered_term_id| {
+                *mapping
+                    .get(&(unordered_term_id as UnorderedTermId))
+                    .expect("Missing term ordinal for a term.")
+            }));
+            doc_vals.sort();
+            doc_vals.dedup();
+            doc_index.push(vals.len() as u64);
+            vals.extend_from_slice(&doc_vals);
+        }
+        doc_index.push(vals.len() as u64);
+        (doc_index, vals)
+    }
+}
@@ -6,9 +6,12 @@ use directory::{Directory, RAMDirectory, WritePtr};
 use directory::ReadOnlySource;
 use fastfield::{FastFieldSerializer, FastFieldsWriter};
 use owning_ref::OwningRef;
+use schema::Cardinality;
 use schema::FAST;
 use schema::FieldType;
 use schema::SchemaBuilder;
+use std::collections::HashMap;
+use std::fmt;
 use std::mem;
 use std::path::Path;
 
@@ -47,6 +50,78 @@ pub trait FastFieldReader: Sized {
     /// it possible to access the field values via a
     /// fastfield.
     fn is_enabled(field_type: &FieldType) -> bool;
+
+    /// Returns true iff the given field_type makes
+    /// it possible to access the field values via a
+    /// multi-valued fastfield.
+    fn is_multivalued_enabled(field_type: &FieldType) -> bool;
+}
+
+/// Types of the values that can be stored in a fast field.
+///
+/// Fast fields store their values as `u64`. `FastValue` defines
+/// the order-preserving mapping between the value type and its `u64`
+/// representation.
+pub trait FastValue: Copy + Default + PartialOrd + fmt::Debug {
+    /// `FastFieldReader` returning values of this type.
+    type FastFieldReader: FastFieldReader<ValueType = Self>;
+
+    /// Converts a value from its `u64` representation.
+    fn from_u64(val: u64) -> Self;
+
+    /// Converts a value to its `u64` representation.
+    fn to_u64(&self) -> u64;
+
+    /// Casts the value to a `f64`, possibly losing precision.
+    fn as_f64(&self) -> f64;
+}
+
+impl FastValue for u64 {
+    type FastFieldReader = U64FastFieldReader;
+
+    fn from_u64(val: u64) -> u64 {
+        val
+    }
+
+    fn to_u64(&self) -> u64 {
+        *self
+    }
+
+    fn as_f64(&self) -> f64 {
+        *self as f64
+    }
+}
+
+impl FastValue for i64 {
+    type FastFieldReader = I64FastFieldReader;
+
+    fn from_u64(val: u64) -> i64 {
+        common::u64_to_i64(val)
+    }
+
+    fn to_u64(&self) -> u64 {
+        common::i64_to_u64(*self)
+    }
+
+    fn as_f64(&self) -> f64 {
+        *self as f64
+    }
+}
+
+impl FastValue for f64 {
+    type FastFieldReader = F64FastFieldReader;
+
+    fn from_u64(val: u64) -> f64 {
+        common::u64_to_f64(val)
+    }
+
+    fn to_u64(&self) -> u64 {
+        common::f64_to_u64(*self)
+    }
+
+    fn as_f64(&self) -> f64 {
+        *self
+    }
 }
 
 /// `FastFieldReader` for unsigned 64-bits integers.
@@ -85,7 +160,18 @@ impl FastFieldReader for U64FastFieldReader {
 
     fn is_enabled(field_type: &FieldType) -> bool {
         match *field_type {
-            FieldType::U64(ref integer_options) => integer_options.is_fast(),
+            FieldType::U64(ref integer_options) => {
+                integer_options.get_fastfield_cardinality() == Some(Cardinality::SingleValue)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_multivalued_enabled(field_type: &FieldType) -> bool {
+        match *field_type {
+            FieldType::U64(ref integer_options) => {
+                integer_options.get_fastfield_cardinality() == Some(Cardinality::MultiValues)
+            }
             _ => false,
         }
     }
@@ -145,7 +231,9 @@ impl From<Vec<u64>> for U64FastFieldReader {
                     fast_field_writer.add_val(val);
                 }
             }
-            fast_field_writers.serialize(&mut serializer).unwrap();
+            fast_field_writers
+                .serialize(&mut serializer, &HashMap::new())
+                .unwrap();
             serializer.close().unwrap();
         }
 
@@ -161,6 +249,8 @@ impl From<Vec<u64>> for U64FastFieldReader {
 }
 
 /// `FastFieldReader` for signed 64-bits integers.
+///
+/// It can also be used to read the timestamps of a date field.
 pub struct I64FastFieldReader {
     underlying: U64FastFieldReader,
 }
@@ -220,7 +310,101 @@ impl FastFieldReader for I64FastFieldReader {
 
     fn is_enabled(field_type: &FieldType) -> bool {
         match *field_type {
-            FieldType::I64(ref integer_options) => integer_options.is_fast(),
+            FieldType::I64(ref integer_options) => {
+                integer_options.get_fastfield_cardinality() == Some(Cardinality::SingleValue)
+            }
+            FieldType::Date(ref date_options) => {
+                date_options.get_fastfield_cardinality() == Some(Cardinality::SingleValue)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_multivalued_enabled(field_type: &FieldType) -> bool {
+        match *field_type {
+            FieldType::I64(ref integer_options) => {
+                integer_options.get_fastfield_cardinality() == Some(Cardinality::MultiValues)
+            }
+            FieldType::Date(ref date_options) => {
+                date_options.get_fastfield_cardinality() == Some(Cardinality::MultiValues)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// `FastFieldReader` for 64-bits floats.
+pub struct F64FastFieldReader {
+    underlying: U64FastFieldReader,
+}
+
+impl F64FastFieldReader {
+    /// Returns the minimum value for this fast field.
+    ///
+    /// The min value does not take in account of possible
+    /// deleted document, and should be considered as a lower bound
+    /// of the actual minimum value.
+    pub fn min_value(&self) -> f64 {
+        common::u64_to_f64(self.underlying.min_value())
+    }
+
+    /// Returns the maximum value for this fast field.
+    ///
+    /// The max value does not take in account of possible
+    /// deleted document, and should be considered as an upper bound
+    /// of the actual maximum value.
+    pub fn max_value(&self) -> f64 {
+        common::u64_to_f64(self.underlying.max_value())
+    }
+}
+
+impl FastFieldReader for F64FastFieldReader {
+    type ValueType = f64;
+
+    /// # Panics
+    ///
+    /// May panic or return wrong random result if `doc`
+    /// is greater or equal to the segment's `maxdoc`.
+    fn get(&self, doc: DocId) -> f64 {
+        common::u64_to_f64(self.underlying.get(doc))
+    }
+
+    /// # Panics
+    ///
+    /// May panic or return wrong random result if `doc`
+    /// is greater or equal to the segment's `maxdoc`.
+    fn get_range(&self, start: u32, output: &mut [Self::ValueType]) {
+        let output_u64: &mut [u64] = unsafe { mem::transmute(output) };
+        self.underlying.get_range(start, output_u64);
+        for mut_val in output_u64.iter_mut() {
+            *mut_val = common::u64_to_f64(*mut_val).to_bits();
+        }
+    }
+
+    /// Opens a new fast field reader given a read only source.
+    ///
+    /// # Panics
+    /// Panics if the data is corrupted.
+    fn open(data: ReadOnlySource) -> F64FastFieldReader {
+        F64FastFieldReader {
+            underlying: U64FastFieldReader::open(data),
+        }
+    }
+
+    fn is_enabled(field_type: &FieldType) -> bool {
+        match *field_type {
+            FieldType::F64(ref integer_options) => {
+                integer_options.get_fastfield_cardinality() == Some(Cardinality::SingleValue)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_multivalued_enabled(field_type: &FieldType) -> bool {
+        match *field_type {
+            FieldType::F64(ref integer_options) => {
+                integer_options.get_fastfield_cardinality() == Some(Cardinality::MultiValues)
+            }
             _ => false,
         }
     }
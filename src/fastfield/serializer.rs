@@ -46,10 +46,41 @@ impl FastFieldSerializer {
         max_value: u64,
     ) -> io::Result<FastSingleFieldSerializer<CountingWriter<WritePtr>>>
     {
-        let field_write = self.composite_write.for_field(field);
+        self.new_u64_fast_field_with_idx(field, min_value, max_value, 0)
+    }
+
+    /// Start serializing a new u64 fast field, given an index.
+    ///
+    /// Several columns can be stored for a single field,
+    /// as long as they have different indexes.
+    /// (e.g. multivalued fast fields)
+    pub fn new_u64_fast_field_with_idx(
+        &mut self,
+        field: Field,
+        min_value: u64,
+        max_value: u64,
+        idx: usize,
+    ) -> io::Result<FastSingleFieldSerializer<CountingWriter<WritePtr>>>
+    {
+        let field_write = self.composite_write.for_field_with_idx(field, idx);
         FastSingleFieldSerializer::open(field_write, min_value, max_value)
     }
 
+    /// Writes raw bytes as the file of the given field and index.
+    ///
+    /// This is used to store the values of bytes fast fields.
+    pub fn write_bytes_with_idx(
+        &mut self,
+        field: Field,
+        idx: usize,
+        data: &[u8],
+    ) -> io::Result<()>
+    {
+        let field_write = self.composite_write.for_field_with_idx(field, idx);
+        field_write.write_all(data)?;
+        field_write.flush()
+    }
+
     /// Closes the serializer
     ///
     /// After this call the data must be persistently save on disk.
@@ -0,0 +1,60 @@
+use DocId;
+use core::InvertedIndexReader;
+use fastfield::MultiValueFastFieldReader;
+use std::sync::Arc;
+use termdict::{TermDictionary, TermDictionaryImpl, TermOrdinal};
+
+/// The term ordinals reader makes it possible to access the
+/// terms of a fast text field for a given document in a
+/// specific segment.
+///
+/// Like the `FacetReader`, it manipulates term ordinals relative
+/// to the segment's term dictionary, so that
+/// the terms themselves only need to be materialized once per segment.
+pub struct TermOrdinalsReader {
+    term_ords: MultiValueFastFieldReader,
+    inverted_index: Arc<InvertedIndexReader>,
+}
+
+impl TermOrdinalsReader {
+    /// Creates a new `TermOrdinalsReader`.
+    ///
+    /// It wraps :
+    /// - a `MultiValueFastFieldReader` that makes it possible to
+    /// access the list of term ords for a given document.
+    /// - the inverted index of the field, whose term dictionary
+    /// associates each term ordinal to its term.
+    pub fn new(
+        term_ords: MultiValueFastFieldReader,
+        inverted_index: Arc<InvertedIndexReader>,
+    ) -> TermOrdinalsReader {
+        TermOrdinalsReader {
+            term_ords,
+            inverted_index,
+        }
+    }
+
+    /// Accessor for the term dictionary of the field.
+    pub fn term_dict(&self) -> &TermDictionaryImpl {
+        self.inverted_index.terms()
+    }
+
+    /// Writes the bytes of the term associated to the given term
+    /// ordinal into `bytes`, without the field prefix.
+    ///
+    /// Returns false if the ordinal is out of bounds.
+    pub fn ord_to_bytes(&self, term_ord: TermOrdinal, bytes: &mut Vec<u8>) -> bool {
+        if !self.inverted_index.terms().ord_to_term(term_ord, bytes) {
+            return false;
+        }
+        // the first 4 bytes of the term encode the field.
+        bytes.drain(..4);
+        true
+    }
+
+    /// Fills `output` with the sorted list of term ordinals
+    /// associated to the document `doc`.
+    pub fn term_ords(&self, doc: DocId, output: &mut Vec<u64>) {
+        self.term_ords.get_vals(doc, output);
+    }
+}
@@ -2,27 +2,74 @@ use DocId;
 use common;
 use common::BinarySerializable;
 use common::VInt;
+use fastfield::BytesFastFieldWriter;
 use fastfield::FastFieldSerializer;
-use schema::{Document, Field, Schema};
+use fastfield::MultiValueFastFieldWriter;
+use postings::TermOrdinalMapping;
+use schema::{DatePrecision, Document, Field, Schema};
 use schema::FieldType;
 use schema::Value;
 use std::io;
 
+/// Returns the `u64` representation of the value of a single-valued
+/// fast field, for the documents that do not have any value.
+pub(crate) fn val_if_missing(field_type: &FieldType) -> u64 {
+    match *field_type {
+        FieldType::I64(_) | FieldType::Date(_) => common::i64_to_u64(0i64),
+        FieldType::F64(_) => common::f64_to_u64(0f64),
+        _ => 0u64,
+    }
+}
+
 /// The fastfieldswriter regroup all of the fast field writers.
 pub struct FastFieldsWriter {
     field_writers: Vec<IntFastFieldWriter>,
+    multi_values_writers: Vec<MultiValueFastFieldWriter>,
+    bytes_value_writers: Vec<BytesFastFieldWriter>,
 }
 
 impl FastFieldsWriter {
     /// Create all `FastFieldWriter` required by the schema.
     pub fn from_schema(schema: &Schema) -> FastFieldsWriter {
+        let mut multi_values_writers = Vec::new();
+        let mut bytes_value_writers = Vec::new();
         let field_writers: Vec<IntFastFieldWriter> = schema
             .fields()
             .iter()
             .enumerate()
             .flat_map(|(field_id, field_entry)| {
                 let field = Field(field_id as u32);
+                if field_entry.is_term_ord_fast() {
+                    multi_values_writers.push(MultiValueFastFieldWriter::new(field, true));
+                    return None;
+                }
+                if field_entry.is_bytes_fast() {
+                    bytes_value_writers.push(BytesFastFieldWriter::new(field));
+                    return None;
+                }
                 match *field_entry.field_type() {
+                    FieldType::I64(_) | FieldType::U64(_) | FieldType::F64(_)
+                        if field_entry.is_int_multivalued_fast() =>
+                    {
+                        multi_values_writers.push(MultiValueFastFieldWriter::new(field, false));
+                        None
+                    }
+                    FieldType::Date(ref date_options) if field_entry.is_int_multivalued_fast() => {
+                        let mut multi_value_writer = MultiValueFastFieldWriter::new(field, false);
+                        multi_value_writer.set_date_precision(date_options.get_precision());
+                        multi_values_writers.push(multi_value_writer);
+                        None
+                    }
+                    FieldType::Date(ref date_options) => {
+                        if date_options.is_fast() {
+                            let mut fast_field_writer = IntFastFieldWriter::new(field);
+                            fast_field_writer.set_val_if_missing(common::i64_to_u64(0i64));
+                            fast_field_writer.set_date_precision(date_options.get_precision());
+                            Some(fast_field_writer)
+                        } else {
+                            None
+                        }
+                    }
                     FieldType::I64(ref int_options) => {
                         if int_options.is_fast() {
                             let mut fast_field_writer = IntFastFieldWriter::new(field);
@@ -32,6 +79,15 @@ impl FastFieldsWriter {
                             None
                         }
                     }
+                    FieldType::F64(ref int_options) => {
+                        if int_options.is_fast() {
+                            let mut fast_field_writer = IntFastFieldWriter::new(field);
+                            fast_field_writer.set_val_if_missing(common::f64_to_u64(0f64));
+                            Some(fast_field_writer)
+                        } else {
+                            None
+                        }
+                    }
                     FieldType::U64(ref int_options) => {
                         if int_options.is_fast() {
                             Some(IntFastFieldWriter::new(field))
@@ -43,7 +99,11 @@ impl FastFieldsWriter {
                 }
             })
             .collect();
-        FastFieldsWriter { field_writers }
+        FastFieldsWriter {
+            field_writers,
+            multi_values_writers,
+            bytes_value_writers,
+        }
     }
 
     /// Returns a `FastFieldsWriter`
@@ -52,6 +112,8 @@ impl FastFieldsWriter {
     pub fn new(fields: Vec<Field>) -> FastFieldsWriter {
         FastFieldsWriter {
             field_writers: fields.into_iter().map(IntFastFieldWriter::new).collect(),
+            multi_values_writers: Vec::new(),
+            bytes_value_writers: Vec::new(),
         }
     }
 
@@ -63,19 +125,57 @@ impl FastFieldsWriter {
             .find(|field_writer| field_writer.field == field)
     }
 
+    /// Returns the fast field multi-value writer for the given field.
+    ///
+    /// Returns None if the field does not exist, or is not
+    /// configured as a multivalued fastfield in the schema.
+    pub(crate) fn get_multivalue_writer(
+        &mut self,
+        field: Field,
+    ) -> Option<&mut MultiValueFastFieldWriter> {
+        // TODO optimize
+        self.multi_values_writers
+            .iter_mut()
+            .find(|multivalue_writer| multivalue_writer.field() == field)
+    }
+
     /// Indexes all of the fastfields of a new document.
+    ///
+    /// Term ordinal fast fields (facets and fast text fields)
+    /// only open a new document:
+    /// their values are added by the `SegmentWriter`.
     pub fn add_document(&mut self, doc: &Document) {
         for field_writer in &mut self.field_writers {
             field_writer.add_document(doc);
         }
+        for multi_values_writer in &mut self.multi_values_writers {
+            multi_values_writer.add_document(doc);
+        }
+        for bytes_value_writer in &mut self.bytes_value_writers {
+            bytes_value_writer.add_document(doc);
+        }
     }
 
     /// Serializes all of the `FastFieldWriter`s by pushing them in
     /// order to the fast field serializer.
-    pub fn serialize(&self, serializer: &mut FastFieldSerializer) -> io::Result<()> {
+    ///
+    /// The `mapping` is used to remap the `UnorderedTermId` recorded
+    /// by the multi-valued fast fields to their `TermOrdinal`.
+    pub fn serialize(
+        &self,
+        serializer: &mut FastFieldSerializer,
+        mapping: &TermOrdinalMapping,
+    ) -> io::Result<()> {
         for field_writer in &self.field_writers {
             field_writer.serialize(serializer)?;
         }
+        for multi_values_writer in &self.multi_values_writers {
+            let field = multi_values_writer.field();
+            multi_values_writer.serialize(serializer, mapping.get(&field))?;
+        }
+        for bytes_value_writer in &self.bytes_value_writers {
+            bytes_value_writer.serialize(serializer)?;
+        }
         Ok(())
     }
 
@@ -102,9 +202,9 @@ impl FastFieldsWriter {
 /// bitpacked and the number of bits required for bitpacking
 /// can only been known once we have seen all of the values.
 ///
-/// Both u64, and i64 use the same writer.
-/// i64 are just remapped to the `0..2^64 - 1`
-/// using `common::i64_to_u64`.
+/// u64, i64 and f64 all use the same writer.
+/// i64 and f64 are just remapped to the `0..2^64 - 1`
+/// using `common::i64_to_u64` and `common::f64_to_u64`.
 pub struct IntFastFieldWriter {
     field: Field,
     vals: Vec<u8>,
@@ -112,6 +212,7 @@ pub struct IntFastFieldWriter {
     val_if_missing: u64,
     val_min: u64,
     val_max: u64,
+    date_precision: DatePrecision,
 }
 
 impl IntFastFieldWriter {
@@ -124,9 +225,15 @@ impl IntFastFieldWriter {
             val_if_missing: 0u64,
             val_min: u64::max_value(),
             val_max: 0,
+            date_precision: DatePrecision::default(),
         }
     }
 
+    /// Sets the precision used to record the values of a date field.
+    fn set_date_precision(&mut self, date_precision: DatePrecision) {
+        self.date_precision = date_precision;
+    }
+
     /// Sets the default value.
     ///
     /// This default value is recorded for documents if
@@ -171,8 +278,9 @@ impl IntFastFieldWriter {
     /// Extract the value associated to the fast field for
     /// this document.
     ///
-    /// i64 are remapped to u64 using the logic
-    /// in `common::i64_to_u64`.
+    /// i64 and f64 are remapped to u64 using the logic
+    /// in `common::i64_to_u64` and `common::f64_to_u64`.
+    /// Dates are first converted to their `i64` timestamp.
     ///
     /// If the value is missing, then the default value is used
     /// instead.
@@ -183,6 +291,10 @@ impl IntFastFieldWriter {
             Some(v) => match *v {
                 Value::U64(ref val) => *val,
                 Value::I64(ref val) => common::i64_to_u64(*val),
+                Value::F64(ref val) => common::f64_to_u64(*val),
+                Value::Date(ref date) => {
+                    common::i64_to_u64(self.date_precision.date_to_timestamp(date))
+                }
                 _ => panic!("Expected a u64field, got {:?} ", v),
             },
             None => self.val_if_missing,
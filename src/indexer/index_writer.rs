@@ -29,6 +29,7 @@ use schema::Document;
 use schema::IndexRecordOption;
 use schema::Schema;
 use schema::Term;
+use schema::Value;
 use std::mem;
 use std::mem::swap;
 use std::thread;
@@ -263,7 +264,10 @@ fn index_documents(
     heap.clear();
     let segment_id = segment.id();
     let mut segment_writer = SegmentWriter::for_segment(heap, table_size, segment.clone(), schema)?;
-    for doc in document_iterator {
+    for mut doc in document_iterator {
+        // the values of deprecated fields are ignored.
+        doc.document
+            .filter_fields(|field| !schema.get_field_entry(field).is_deprecated());
         segment_writer.add_document(&doc, schema)?;
         // There is two possible conditions to close the segment.
         // One is the memory arena dedicated to the segment is
@@ -618,6 +622,79 @@ impl IndexWriter {
         opstamp
     }
 
+    /// Deletes the document bearing the given unique key.
+    ///
+    /// This is equivalent to calling `delete_term` with the term
+    /// of the key.
+    ///
+    /// # Errors
+    /// Returns an error if the schema does not declare a unique key,
+    /// or if the type of the key does not match the type of the
+    /// unique key field.
+    pub fn delete_by_key<V: Into<Value>>(&mut self, key: V) -> Result<u64> {
+        let key_term = self.unique_key_term(&key.into())?;
+        Ok(self.delete_term(key_term))
+    }
+
+    /// Adds a document, replacing the document bearing
+    /// the same unique key if any.
+    ///
+    /// The deletion of the previous document and the addition of
+    /// the new one are stamped with two consecutive opstamps, so
+    /// that no other operation can be interleaved between them.
+    /// The opstamp of the addition is returned.
+    ///
+    /// Like adds, the replacement will be visible only
+    /// after calling `commit()`.
+    ///
+    /// # Errors
+    /// Returns an error if the schema does not declare a unique key,
+    /// or if the document does not have exactly one value for it.
+    pub fn upsert_document(&mut self, document: Document) -> Result<u64> {
+        let key_term = {
+            let schema = self.index.schema();
+            let key_field = schema.unique_key_field().ok_or_else(|| {
+                ErrorKind::InvalidArgument(String::from("The schema does not have a unique key."))
+            })?;
+            let key_values = document.get_all(key_field);
+            if key_values.len() != 1 {
+                bail!(ErrorKind::InvalidArgument(format!(
+                    "Expected exactly one value for the unique key {:?}, got {}.",
+                    schema.get_field_name(key_field),
+                    key_values.len()
+                )));
+            }
+            self.unique_key_term(key_values[0])?
+        };
+        let delete_opstamp = self.stamper.stamps(2u64);
+        let delete_operation = DeleteOperation {
+            opstamp: delete_opstamp,
+            term: key_term,
+        };
+        self.delete_queue.push(delete_operation);
+        // a delete operation only affects the documents with a lower
+        // opstamp, hence the new document is not deleted.
+        let opstamp = delete_opstamp + 1;
+        let add_operation = AddOperation { opstamp, document };
+        self.document_sender.send(add_operation);
+        Ok(opstamp)
+    }
+
+    fn unique_key_term(&self, key: &Value) -> Result<Term> {
+        let schema = self.index.schema();
+        if schema.unique_key_field().is_none() {
+            bail!(ErrorKind::InvalidArgument(String::from(
+                "The schema does not have a unique key."
+            )));
+        }
+        schema.unique_key_term(key).ok_or_else(|| {
+            ErrorKind::InvalidArgument(format!(
+                "The value {:?} does not match the type of the unique key.",
+                key
+            )).into()
+        })
+    }
+
     /// Returns the opstamp of the last successful commit.
     ///
     /// This is, for instance, the opstamp the index will
@@ -742,6 +819,62 @@ mod tests {
         index.searcher();
     }
 
+    #[test]
+    fn test_upsert_and_delete_by_key() {
+        let mut schema_builder = schema::SchemaBuilder::default();
+        let id_field = schema_builder.add_text_field("id", schema::STRING);
+        let text_field = schema_builder.add_text_field("text", schema::TEXT | schema::STORED);
+        schema_builder.set_unique_key(id_field);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema.clone());
+        let key_term = |key: &str| schema.unique_key_term(&key.into()).unwrap();
+        let doc_text = |key: &str| {
+            index
+                .searcher()
+                .doc_by_key(&key_term(key))
+                .unwrap()
+                .map(|doc| doc.get_first(text_field).unwrap().text().to_string())
+        };
+
+        let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+        index_writer
+            .upsert_document(doc!(id_field => "a", text_field => "first"))
+            .unwrap();
+        index_writer
+            .upsert_document(doc!(id_field => "b", text_field => "first"))
+            .unwrap();
+        index_writer.commit().unwrap();
+        index.load_searchers().unwrap();
+        assert_eq!(doc_text("a"), Some(String::from("first")));
+        assert_eq!(doc_text("c"), None);
+
+        // replacing a document within the same commit as its addition.
+        let opstamp = index_writer
+            .upsert_document(doc!(id_field => "c", text_field => "first"))
+            .unwrap();
+        assert_eq!(
+            index_writer
+                .upsert_document(doc!(id_field => "c", text_field => "second"))
+                .unwrap(),
+            opstamp + 2
+        );
+        index_writer
+            .upsert_document(doc!(id_field => "a", text_field => "second"))
+            .unwrap();
+        index_writer.delete_by_key("b").unwrap();
+        index_writer.commit().unwrap();
+        index.load_searchers().unwrap();
+        assert_eq!(index.searcher().num_docs(), 2);
+        assert_eq!(doc_text("a"), Some(String::from("second")));
+        assert_eq!(doc_text("b"), None);
+        assert_eq!(doc_text("c"), Some(String::from("second")));
+
+        assert!(index_writer.delete_by_key(3u64).is_err());
+        assert!(index_writer
+            .upsert_document(doc!(text_field => "missing key"))
+            .is_err());
+    }
+
     #[test]
     fn test_with_merges() {
         let _ = env_logger::init();
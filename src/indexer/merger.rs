@@ -2,11 +2,14 @@ use DocId;
 use core::Segment;
 use core::SegmentReader;
 use core::SerializableSegment;
-use error::{ErrorKind, Result};
+use error::Result;
+use fastfield::BytesFastFieldWriter;
 use fastfield::DeleteBitSet;
 use fastfield::FastFieldReader;
 use fastfield::FastFieldSerializer;
+use fastfield::MultiValueFastFieldWriter;
 use fastfield::U64FastFieldReader;
+use fastfield::val_if_missing;
 use indexer::SegmentSerializer;
 use itertools::Itertools;
 use postings::DocSet;
@@ -16,11 +19,18 @@ use schema::{Field, Schema};
 use schema::FieldValue;
 use schema::Term;
 use std::cmp::{max, min};
+use std::collections::HashMap;
 use store::StoreWriter;
 use termdict::TermDictionary;
 use termdict::TermMerger;
+use termdict::TermOrdinal;
 use termdict::TermStreamer;
 
+/// For each term ordinal fast field, and for each of the segments
+/// being merged, associates the `TermOrdinal` of the terms in the
+/// segment to their `TermOrdinal` in the merged segment.
+type TermOrdinalMappings = HashMap<Field, Vec<HashMap<TermOrdinal, TermOrdinal>>>;
+
 pub struct IndexMerger {
     schema: Schema,
     readers: Vec<SegmentReader>,
@@ -63,7 +73,8 @@ fn extract_fast_field_reader(
     field: Field,
 ) -> Option<U64FastFieldReader>
 {
-    segment_reader.get_fast_field_reader(field).ok()
+    // i64 and f64 fast fields are read through their `u64` representation.
+    segment_reader.open_fast_field_reader(field)
 }
 
 struct DeltaComputer {
@@ -109,12 +120,12 @@ impl IndexMerger {
     }
 
     fn write_fieldnorms(&self, fast_field_serializer: &mut FastFieldSerializer) -> Result<()> {
-        let fieldnorm_fastfields: Vec<Field> = self.schema
+        let fieldnorm_fastfields: Vec<(Field, u64)> = self.schema
             .fields()
             .iter()
             .enumerate()
             .filter(|&(_, field_entry)| field_entry.is_indexed())
-            .map(|(field_id, _)| Field(field_id as u32))
+            .map(|(field_id, _)| (Field(field_id as u32), 0u64))
             .collect();
         self.generic_write_fast_field(
             fieldnorm_fastfields,
@@ -123,30 +134,145 @@ impl IndexMerger {
         )
     }
 
-    fn write_fast_fields(&self, fast_field_serializer: &mut FastFieldSerializer) -> Result<()> {
-        let fast_fields: Vec<Field> = self.schema
+    fn write_fast_fields(
+        &self,
+        fast_field_serializer: &mut FastFieldSerializer,
+        term_ord_mappings: &TermOrdinalMappings,
+    ) -> Result<()> {
+        let fast_fields: Vec<(Field, u64)> = self.schema
             .fields()
             .iter()
             .enumerate()
             .filter(|&(_, field_entry)| field_entry.is_int_fast())
-            .map(|(field_id, _)| Field(field_id as u32))
+            .map(|(field_id, field_entry)| {
+                (Field(field_id as u32), val_if_missing(field_entry.field_type()))
+            })
             .collect();
         self.generic_write_fast_field(
             fast_fields,
             &extract_fast_field_reader,
             fast_field_serializer,
-        )
+        )?;
+        self.write_multi_fast_fields(fast_field_serializer, term_ord_mappings)?;
+        self.write_bytes_fast_fields(fast_field_serializer)
+    }
+
+    // bytes fast fields are merged by copying the bytes
+    // of the documents that are not deleted.
+    //
+    // Segments written before the field was added to the
+    // schema do not have the fast field. Their documents are
+    // given an empty value.
+    fn write_bytes_fast_fields(
+        &self,
+        fast_field_serializer: &mut FastFieldSerializer,
+    ) -> Result<()> {
+        let bytes_fast_fields: Vec<Field> = self.schema
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|&(_, field_entry)| field_entry.is_bytes_fast())
+            .map(|(field_id, _)| Field(field_id as u32))
+            .collect();
+        for field in bytes_fast_fields {
+            let mut bytes_writer = BytesFastFieldWriter::new(field);
+            for reader in &self.readers {
+                let bytes_reader_opt = reader.open_bytes_fast_field_reader(field);
+                for doc_id in 0..reader.max_doc() {
+                    if !reader.is_deleted(doc_id) {
+                        let val = bytes_reader_opt
+                            .as_ref()
+                            .map(|bytes_reader| bytes_reader.get_val(doc_id))
+                            .unwrap_or(&[]);
+                        bytes_writer.add_document_val(val);
+                    }
+                }
+            }
+            bytes_writer.serialize(fast_field_serializer)?;
+        }
+        Ok(())
+    }
+
+    // used both to merge multi-valued int fast fields and term ordinal
+    // fast fields (facets and fast text fields).
+    //
+    // The term ordinals of the documents are remapped
+    // to the term ordinals of the merged term dictionary.
+    //
+    // Segments written before the field was added to the
+    // schema do not have the fast field. Their documents
+    // are given no value.
+    fn write_multi_fast_fields(
+        &self,
+        fast_field_serializer: &mut FastFieldSerializer,
+        term_ord_mappings: &TermOrdinalMappings,
+    ) -> Result<()> {
+        let multi_fast_fields: Vec<(Field, bool)> = self.schema
+            .fields()
+            .iter()
+            .enumerate()
+            .flat_map(|(field_id, field_entry)| {
+                let field = Field(field_id as u32);
+                if field_entry.is_term_ord_fast() {
+                    Some((field, true))
+                } else if field_entry.is_int_multivalued_fast() {
+                    Some((field, false))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let mut vals = Vec::new();
+        for (field, is_term_ord) in multi_fast_fields {
+            // the mapping may be missing if the field
+            // does not have any term.
+            let segment_mappings_opt = term_ord_mappings.get(&field);
+            let mut multi_value_writer = MultiValueFastFieldWriter::new(field, is_term_ord);
+            for (segment_ord, reader) in self.readers.iter().enumerate() {
+                let multi_value_reader_opt =
+                    reader.open_multi_fast_field_reader::<U64FastFieldReader>(field);
+                for doc_id in 0..reader.max_doc() {
+                    if reader.is_deleted(doc_id) {
+                        continue;
+                    }
+                    multi_value_writer.next_doc();
+                    if let Some(ref multi_value_reader) = multi_value_reader_opt {
+                        multi_value_reader.get_vals(doc_id, &mut vals);
+                    } else {
+                        vals.clear();
+                    }
+                    for val in &vals {
+                        if is_term_ord {
+                            // the mapping is increasing, so the
+                            // term ordinals remain sorted.
+                            let new_term_ord = segment_mappings_opt
+                                .and_then(|segment_mappings| segment_mappings[segment_ord].get(val))
+                                .expect("Missing term ordinal in the merged term dictionary.");
+                            multi_value_writer.add_val(*new_term_ord);
+                        } else {
+                            multi_value_writer.add_val(*val);
+                        }
+                    }
+                }
+            }
+            multi_value_writer.serialize(fast_field_serializer, None)?;
+        }
+        Ok(())
     }
 
     // used both to merge field norms and regular u64 fast fields.
+    //
+    // Each field comes with the value given to the documents
+    // of the segments that do not have the fast field, because
+    // they were written before the field was added to the schema.
     fn generic_write_fast_field(
         &self,
-        fields: Vec<Field>,
+        fields: Vec<(Field, u64)>,
         field_reader_extractor: &Fn(&SegmentReader, Field) -> Option<U64FastFieldReader>,
         fast_field_serializer: &mut FastFieldSerializer,
     ) -> Result<()>
     {
-        for field in fields {
+        for (field, default_val) in fields {
             let mut u64_readers = vec![];
             let mut min_val = u64::max_value();
             let mut max_val = u64::min_value();
@@ -164,16 +290,17 @@ impl IndexMerger {
                             max_val = max(max_val, seg_max_val);
                             u64_readers.push((
                                 reader.max_doc(),
-                                u64_reader,
+                                Some(u64_reader),
                                 reader.delete_bitset(),
                             ));
                         }
                     }
                     None => {
-                        let error_msg =
-                            format!("Failed to find a u64_reader for field {:?}", field);
-                        error!("{}", error_msg);
-                        bail!(ErrorKind::SchemaError(error_msg));
+                        if reader.num_docs() > 0 {
+                            min_val = min(min_val, default_val);
+                            max_val = max(max_val, default_val);
+                            u64_readers.push((reader.max_doc(), None, reader.delete_bitset()));
+                        }
                     }
                 }
             }
@@ -188,10 +315,13 @@ impl IndexMerger {
 
             let mut fast_single_field_serializer =
                 fast_field_serializer.new_u64_fast_field(field, min_val, max_val)?;
-            for (max_doc, u64_reader, delete_bitset) in u64_readers {
+            for (max_doc, u64_reader_opt, delete_bitset) in u64_readers {
                 for doc_id in 0..max_doc {
                     if !delete_bitset.is_deleted(doc_id) {
-                        let val = u64_reader.get(doc_id);
+                        let val = u64_reader_opt
+                            .as_ref()
+                            .map(|u64_reader| u64_reader.get(doc_id))
+                            .unwrap_or(default_val);
                         fast_single_field_serializer.add_val(val)?;
                     }
                 }
@@ -202,8 +332,12 @@ impl IndexMerger {
         Ok(())
     }
 
-    fn write_postings(&self, serializer: &mut InvertedIndexSerializer) -> Result<()> {
+    fn write_postings(
+        &self,
+        serializer: &mut InvertedIndexSerializer,
+    ) -> Result<TermOrdinalMappings> {
         let mut delta_computer = DeltaComputer::new();
+        let mut term_ord_mappings = TermOrdinalMappings::new();
 
         let mut indexed_fields = vec![];
         for (field_ord, field_entry) in self.schema.fields().iter().enumerate() {
@@ -258,6 +392,16 @@ impl IndexMerger {
 
             let field_entry = self.schema.get_field_entry(indexed_field);
 
+            // for facets and fast text fields, we keep track of the
+            // new term ordinals, as they are used as fast field values.
+            let mut term_ord_mapping_opt: Option<Vec<HashMap<TermOrdinal, TermOrdinal>>> =
+                if field_entry.is_term_ord_fast() {
+                    Some(vec![HashMap::new(); self.readers.len()])
+                } else {
+                    None
+                };
+            let mut new_term_ord: TermOrdinal = 0;
+
             // ... set segment postings option the new field.
             let segment_postings_option =
                 field_entry.field_type().get_index_record_option().expect(
@@ -296,6 +440,14 @@ impl IndexMerger {
                 if !segment_postings.is_empty() {
                     // If not, the `term` will be entirely removed.
 
+                    if let Some(ref mut term_ord_mapping) = term_ord_mapping_opt {
+                        for heap_item in merged_terms.current_kvs() {
+                            term_ord_mapping[heap_item.segment_ord]
+                                .insert(heap_item.streamer.term_ord(), new_term_ord);
+                        }
+                        new_term_ord += 1;
+                    }
+
                     // We know that there is at least one document containing
                     // the term, so we add it.
                     field_serializer.new_term(term.as_ref())?;
@@ -333,8 +485,12 @@ impl IndexMerger {
             }
 
             field_serializer.close()?;
+
+            if let Some(term_ord_mapping) = term_ord_mapping_opt {
+                term_ord_mappings.insert(indexed_field, term_ord_mapping);
+            }
         }
-        Ok(())
+        Ok(term_ord_mappings)
     }
 
     fn write_storable_fields(&self, store_writer: &mut StoreWriter) -> Result<()> {
@@ -354,9 +510,9 @@ impl IndexMerger {
 
 impl SerializableSegment for IndexMerger {
     fn write(&self, mut serializer: SegmentSerializer) -> Result<u32> {
-        self.write_postings(serializer.get_postings_serializer())?;
+        let term_ord_mappings = self.write_postings(serializer.get_postings_serializer())?;
         self.write_fieldnorms(serializer.get_fieldnorms_serializer())?;
-        self.write_fast_fields(serializer.get_fast_field_serializer())?;
+        self.write_fast_fields(serializer.get_fast_field_serializer(), &term_ord_mappings)?;
         self.write_storable_fields(serializer.get_store_writer())?;
         serializer.close()?;
         Ok(self.max_doc)
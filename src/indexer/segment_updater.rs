@@ -25,7 +25,7 @@ use indexer::stamper::Stamper;
 use schema::Schema;
 use serde_json;
 use std::borrow::BorrowMut;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Write;
 use std::mem;
 use std::ops::DerefMut;
@@ -35,6 +35,7 @@ use std::sync::atomic::{AtomicBool, AtomicUsize};
 use std::sync::atomic::Ordering;
 use std::thread;
 use std::thread::JoinHandle;
+use tokenizer::TokenizerDefinition;
 
 /// Save the index meta file.
 /// This operation is atomic :
@@ -46,7 +47,7 @@ use std::thread::JoinHandle;
 ///
 /// This method is not part of tantivy's public API
 pub fn save_new_metas(schema: Schema, opstamp: u64, directory: &mut Directory) -> Result<()> {
-    save_metas(vec![], schema, opstamp, None, directory)
+    save_metas(vec![], schema, opstamp, None, BTreeMap::new(), directory)
 }
 
 /// Save the index meta file.
@@ -63,6 +64,7 @@ pub fn save_metas(
     schema: Schema,
     opstamp: u64,
     payload: Option<String>,
+    tokenizers: BTreeMap<String, TokenizerDefinition>,
     directory: &mut Directory,
 ) -> Result<()>
 {
@@ -71,6 +73,7 @@ pub fn save_metas(
         schema,
         opstamp,
         payload: payload.clone(),
+        tokenizers,
     };
     let mut buffer = serde_json::to_vec_pretty(&metas)?;
     write!(&mut buffer, "\n")?;
@@ -254,6 +257,7 @@ impl SegmentUpdater {
                 index.schema(),
                 opstamp,
                 commit_message,
+                index.tokenizers().definitions(),
                 directory.box_clone().borrow_mut(),
             ).expect("Could not save metas.");
         }
@@ -8,12 +8,17 @@ use fastfield::FastFieldsWriter;
 use indexer::index_writer::MARGIN_IN_BYTES;
 use indexer::segment_serializer::SegmentSerializer;
 use postings::MultiFieldPostingsWriter;
+use postings::UnorderedTermId;
 use schema::Field;
+use schema::Facet;
 use schema::FieldType;
 use schema::FieldValue;
+use schema::JsonObject;
 use schema::Schema;
 use schema::Term;
 use schema::Value;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
 use std::io;
 use tokenizer::BoxedTokenizer;
 
@@ -31,6 +36,7 @@ pub struct SegmentWriter<'a> {
     fieldnorms_writer: FastFieldsWriter,
     doc_opstamps: Vec<u64>,
     tokenizers: Vec<Option<Box<BoxedTokenizer>>>,
+    schema: Schema,
 }
 
 fn create_fieldnorms_writer(schema: &Schema) -> FastFieldsWriter {
@@ -44,6 +50,96 @@ fn create_fieldnorms_writer(schema: &Schema) -> FastFieldsWriter {
     FastFieldsWriter::new(u64_fields)
 }
 
+/// Indexes all of the values of a JSON object, recursively.
+///
+/// `path` is the path of the object within the root object,
+/// and `position` the position of the next text token.
+/// Text values are separated by a gap of one position, so that
+/// phrase queries do not match across values.
+fn index_json_object(
+    doc_id: DocId,
+    field: Field,
+    json_object: &JsonObject,
+    path: &mut String,
+    tokenizer: &BoxedTokenizer,
+    position: &mut u32,
+    multifield_postings: &mut MultiFieldPostingsWriter,
+) {
+    for (key, json_value) in json_object {
+        let path_len = path.len();
+        if !path.is_empty() {
+            path.push('.');
+        }
+        path.push_str(key);
+        index_json_value(
+            doc_id,
+            field,
+            json_value,
+            path,
+            tokenizer,
+            position,
+            multifield_postings,
+        );
+        path.truncate(path_len);
+    }
+}
+
+fn index_json_value(
+    doc_id: DocId,
+    field: Field,
+    json_value: &JsonValue,
+    path: &mut String,
+    tokenizer: &BoxedTokenizer,
+    position: &mut u32,
+    multifield_postings: &mut MultiFieldPostingsWriter,
+) {
+    match *json_value {
+        JsonValue::String(ref text) => {
+            let start_position = *position;
+            let mut token_stream = tokenizer.token_stream(text);
+            let num_tokens = token_stream.process(&mut |token| {
+                let term = Term::from_field_json_text(field, path, &token.text);
+                let token_position = start_position + token.position as u32;
+                multifield_postings.subscribe_at(doc_id, token_position, &term);
+            });
+            *position = start_position + num_tokens + 1;
+        }
+        JsonValue::Number(ref number) => {
+            if let Some(val) = number.as_f64() {
+                let term = Term::from_field_json_f64(field, path, val);
+                multifield_postings.subscribe(doc_id, &term);
+            }
+        }
+        JsonValue::Bool(val) => {
+            let term = Term::from_field_json_text(field, path, if val { "true" } else { "false" });
+            multifield_postings.subscribe(doc_id, &term);
+        }
+        JsonValue::Array(ref json_items) => for json_item in json_items {
+            index_json_value(
+                doc_id,
+                field,
+                json_item,
+                path,
+                tokenizer,
+                position,
+                multifield_postings,
+            );
+        },
+        JsonValue::Object(ref json_object) => {
+            index_json_object(
+                doc_id,
+                field,
+                json_object,
+                path,
+                tokenizer,
+                position,
+                multifield_postings,
+            );
+        }
+        JsonValue::Null => {}
+    }
+}
+
 impl<'a> SegmentWriter<'a> {
     /// Creates a new `SegmentWriter`
     ///
@@ -74,6 +170,12 @@ impl<'a> SegmentWriter<'a> {
                         segment.index().tokenizers().get(tokenizer_name)
                     },
                 ),
+                FieldType::JsonObject(ref json_object_options) => json_object_options
+                    .get_text_indexing_options()
+                    .and_then(|text_index_option| {
+                        let tokenizer_name = &text_index_option.tokenizer();
+                        segment.index().tokenizers().get(tokenizer_name)
+                    }),
                 _ => None,
             })
             .collect();
@@ -86,6 +188,7 @@ impl<'a> SegmentWriter<'a> {
             fast_field_writers: FastFieldsWriter::from_schema(schema),
             doc_opstamps: Vec::with_capacity(1_000),
             tokenizers,
+            schema: schema.clone(),
         })
     }
 
@@ -98,6 +201,7 @@ impl<'a> SegmentWriter<'a> {
             &self.multifield_postings,
             &self.fast_field_writers,
             &self.fieldnorms_writer,
+            &self.schema,
             self.segment_serializer,
         )?;
         Ok(self.doc_opstamps)
@@ -133,6 +237,7 @@ impl<'a> SegmentWriter<'a> {
         let doc_id = self.max_doc;
         let doc = &add_operation.document;
         self.doc_opstamps.push(add_operation.opstamp);
+        self.fast_field_writers.add_document(doc);
         for (field, field_values) in doc.get_sorted_field_values() {
             let field_options = schema.get_field_entry(field);
             if !field_options.is_indexed() {
@@ -140,6 +245,7 @@ impl<'a> SegmentWriter<'a> {
             }
             match *field_options.field_type() {
                 FieldType::Str(_) => {
+                    let is_term_ord_fast = field_options.is_term_ord_fast();
                     let num_tokens =
                         if let Some(ref mut tokenizer) = self.tokenizers[field.0 as usize] {
                             let texts: Vec<&str> = field_values
@@ -150,8 +256,27 @@ impl<'a> SegmentWriter<'a> {
                                 })
                                 .collect();
                             let mut token_stream = tokenizer.token_stream_texts(&texts[..]);
-                            self.multifield_postings
-                                .index_text(doc_id, field, &mut token_stream)
+                            let mut term_ids: Vec<UnorderedTermId> = Vec::new();
+                            let term_ids_opt = if is_term_ord_fast {
+                                Some(&mut term_ids)
+                            } else {
+                                None
+                            };
+                            let num_tokens = self.multifield_postings.index_text(
+                                doc_id,
+                                field,
+                                &mut token_stream,
+                                term_ids_opt,
+                            );
+                            if is_term_ord_fast {
+                                let multivalue_writer = self.fast_field_writers
+                                    .get_multivalue_writer(field)
+                                    .expect("Multivalued writer for fast text field missing.");
+                                for term_id in term_ids {
+                                    multivalue_writer.add_val(u64::from(term_id));
+                                }
+                            }
+                            num_tokens
                         } else {
                             0
                         };
@@ -161,6 +286,28 @@ impl<'a> SegmentWriter<'a> {
                             field_norms_writer.add_val(u64::from(num_tokens))
                         });
                 }
+                FieldType::HierarchicalFacet => {
+                    let facets: Vec<&Facet> = field_values
+                        .iter()
+                        .flat_map(|field_value| match *field_value.value() {
+                            Value::Facet(ref facet) => Some(facet),
+                            _ => None,
+                        })
+                        .collect();
+                    for facet in facets {
+                        // a document belonging to a facet also belongs to
+                        // all of its ancestors.
+                        for facet_prefix in facet.prefixes() {
+                            let term = Term::from_facet(field, &facet_prefix);
+                            let unordered_term_id =
+                                self.multifield_postings.subscribe(doc_id, &term);
+                            self.fast_field_writers
+                                .get_multivalue_writer(field)
+                                .expect("Multivalued writer for facet field missing.")
+                                .add_val(u64::from(unordered_term_id));
+                        }
+                    }
+                }
                 FieldType::U64(ref int_option) => {
                     if int_option.is_indexed() {
                         for field_value in field_values {
@@ -183,10 +330,80 @@ impl<'a> SegmentWriter<'a> {
                         }
                     }
                 }
+                FieldType::Date(ref date_option) => {
+                    if date_option.is_indexed() {
+                        for field_value in field_values {
+                            let term = Term::from_field_date(
+                                field_value.field(),
+                                field_value.value().date_value(),
+                                date_option.get_precision(),
+                            );
+                            self.multifield_postings.subscribe(doc_id, &term);
+                        }
+                    }
+                }
+                FieldType::F64(ref int_option) => {
+                    if int_option.is_indexed() {
+                        for field_value in field_values {
+                            let term = Term::from_field_f64(
+                                field_value.field(),
+                                field_value.value().f64_value(),
+                            );
+                            self.multifield_postings.subscribe(doc_id, &term);
+                        }
+                    }
+                }
+                FieldType::Bytes(ref bytes_option) => {
+                    if bytes_option.is_indexed() {
+                        for field_value in field_values {
+                            let term = Term::from_field_bytes(
+                                field_value.field(),
+                                field_value.value().bytes_value(),
+                            );
+                            self.multifield_postings.subscribe(doc_id, &term);
+                        }
+                    }
+                }
+                FieldType::JsonObject(_) => {
+                    // the position also accounts for the gaps between the
+                    // text values, which is close enough for the fieldnorm.
+                    let mut position = 0u32;
+                    if let Some(ref tokenizer) = self.tokenizers[field.0 as usize] {
+                        let mut path = String::new();
+                        for field_value in field_values {
+                            if let Value::JsonObject(ref json_object) = *field_value.value() {
+                                index_json_object(
+                                    doc_id,
+                                    field,
+                                    json_object,
+                                    &mut path,
+                                    &**tokenizer,
+                                    &mut position,
+                                    &mut self.multifield_postings,
+                                );
+                            }
+                        }
+                    }
+                    self.fieldnorms_writer
+                        .get_field_writer(field)
+                        .map(|field_norms_writer| {
+                            field_norms_writer.add_val(u64::from(position))
+                        });
+                }
+                FieldType::Ip(ref ip_option) => {
+                    if ip_option.is_indexed() {
+                        for field_value in field_values {
+                            let term = Term::from_field_ip(
+                                field_value.field(),
+                                &field_value.value().ip_value(),
+                            );
+                            self.multifield_postings.subscribe(doc_id, &term);
+                        }
+                    }
+                }
             }
         }
         self.fieldnorms_writer.fill_val_up_to(doc_id);
-        self.fast_field_writers.add_document(doc);
         let stored_fieldvalues: Vec<&FieldValue> = doc.field_values()
             .iter()
             .filter(|field_value| schema.get_field_entry(field_value.field()).is_stored())
@@ -223,12 +440,14 @@ fn write(
     multifield_postings: &MultiFieldPostingsWriter,
     fast_field_writers: &FastFieldsWriter,
     fieldnorms_writer: &FastFieldsWriter,
+    schema: &Schema,
     mut serializer: SegmentSerializer,
 ) -> Result<()>
 {
-    multifield_postings.serialize(serializer.get_postings_serializer())?;
-    fast_field_writers.serialize(serializer.get_fast_field_serializer())?;
-    fieldnorms_writer.serialize(serializer.get_fieldnorms_serializer())?;
+    let term_ord_map =
+        multifield_postings.serialize(serializer.get_postings_serializer(), schema)?;
+    fast_field_writers.serialize(serializer.get_fast_field_serializer(), &term_ord_map)?;
+    fieldnorms_writer.serialize(serializer.get_fieldnorms_serializer(), &HashMap::new())?;
     serializer.close()?;
 
     Ok(())
@@ -241,6 +460,7 @@ impl<'a> SerializableSegment for SegmentWriter<'a> {
             &self.multifield_postings,
             &self.fast_field_writers,
             &self.fieldnorms_writer,
+            &self.schema,
             serializer,
         )?;
         Ok(max_doc)
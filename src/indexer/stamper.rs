@@ -12,4 +12,10 @@ impl Stamper {
     pub fn stamp(&self) -> u64 {
         self.0.fetch_add(1u64, Ordering::SeqCst)
     }
+
+    /// Reserves `n` consecutive opstamps at once,
+    /// and returns the first one.
+    pub fn stamps(&self, n: u64) -> u64 {
+        self.0.fetch_add(n, Ordering::SeqCst)
+    }
 }
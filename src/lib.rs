@@ -35,9 +35,11 @@ extern crate log;
 extern crate error_chain;
 
 extern crate atomicwrites;
+extern crate base64;
 extern crate bit_set;
 extern crate byteorder;
 extern crate chan;
+extern crate chrono;
 extern crate combine;
 extern crate crossbeam;
 extern crate fst;
@@ -55,6 +57,8 @@ extern crate serde_json;
 extern crate stable_deref_trait;
 extern crate tempdir;
 extern crate time;
+extern crate unicode_normalization;
+extern crate unicode_segmentation;
 extern crate uuid;
 #[macro_use]
 extern crate version;
@@ -116,7 +120,7 @@ pub use core::SegmentComponent;
 pub use postings::DocSet;
 pub use postings::Postings;
 
-pub use common::{i64_to_u64, u64_to_i64};
+pub use common::{f64_to_u64, i64_to_u64, u64_to_f64, u64_to_i64};
 
 /// Expose the current version of tantivy, as well
 /// whether it was compiled with the simd compression.
@@ -152,6 +156,12 @@ pub type Score = f32;
 /// It only makes sense for a given searcher.
 pub type SegmentLocalId = u32;
 
+/// A date, in the UTC timezone.
+///
+/// Dates are the values of the date fields
+/// (see `SchemaBuilder::add_date_field`).
+pub type DateTime = chrono::DateTime<chrono::Utc>;
+
 impl DocAddress {
     /// Return the segment ordinal.
     /// The segment ordinal is an id identifying the segment
@@ -185,10 +195,12 @@ mod tests {
     use Index;
     use IndexWriter;
     use Postings;
+    use collector::CountCollector;
     use collector::tests::TestCollector;
     use core::SegmentReader;
     use fastfield::{FastFieldReader, I64FastFieldReader, U64FastFieldReader};
     use query::BooleanQuery;
+    use query::QueryParser;
     use rand::{Rng, SeedableRng, XorShiftRng};
     use schema::*;
     use schema::IndexRecordOption;
@@ -535,6 +547,49 @@ mod tests {
         assert!(!postings.advance());
     }
 
+    #[test]
+    fn test_indexed_json_object() {
+        let mut schema_builder = SchemaBuilder::default();
+        let attributes = schema_builder.add_json_field("attributes", TEXT);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema.clone());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            for doc_json in &[
+                r#"{"attributes": {"color": "red", "size": 15, "dims": {"width": 2.5}}}"#,
+                r#"{"attributes": {"color": "dark red", "size": 25, "in_stock": true}}"#,
+                r#"{"attributes": {"colors": ["blue", "red"], "size": -1.5}}"#,
+            ] {
+                index_writer.add_document(schema.parse_document(doc_json).unwrap());
+            }
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![attributes]);
+        let count = |query_str: &str| {
+            let query = query_parser.parse_query(query_str).unwrap();
+            let mut count_collector = CountCollector::default();
+            query.search(&*searcher, &mut count_collector).unwrap();
+            count_collector.count()
+        };
+        assert_eq!(count("attributes.color:red"), 2);
+        assert_eq!(count("attributes.color:\"dark red\""), 1);
+        assert_eq!(count("attributes.colors:red"), 1);
+        assert_eq!(count("attributes.size:15"), 1);
+        assert_eq!(count("attributes.size:[10 TO 20]"), 1);
+        assert_eq!(count("attributes.size:[* TO 20]"), 2);
+        assert_eq!(count("attributes.size:[10 TO *]"), 2);
+        assert_eq!(count("attributes.dims.width:[* TO 3]"), 1);
+        assert_eq!(count("attributes.in_stock:true"), 1);
+        // the path is part of the term.
+        assert_eq!(count("attributes.dims.color:red"), 0);
+        let num_term = Term::from_field_json_f64(attributes, "size", 15.0);
+        assert_eq!(searcher.doc_freq(&num_term), 1);
+        let text_term = Term::from_field_json_text(attributes, "size", "15");
+        assert_eq!(searcher.doc_freq(&text_term), 0);
+    }
+
     #[test]
     fn test_delete_postings2() {
         let mut schema_builder = SchemaBuilder::default();
@@ -19,6 +19,7 @@ mod docset;
 
 pub use self::docset::{DocSet, SkipResult};
 pub(crate) use self::postings_writer::MultiFieldPostingsWriter;
+pub(crate) use self::postings_writer::TermOrdinalMapping;
 use self::recorder::{NothingRecorder, Recorder, TFAndPositionRecorder, TermFrequencyRecorder};
 pub use self::serializer::{FieldSerializer, InvertedIndexSerializer};
 
@@ -32,6 +33,12 @@ pub use self::intersection::IntersectionDocSet;
 pub use self::segment_postings::{BlockSegmentPostings, SegmentPostings};
 pub use common::HasLen;
 
+/// Identifier of a term within a segment being written.
+///
+/// Contrary to the term ordinal, it does not follow the
+/// order of the terms, and is only known before serialization.
+pub(crate) type UnorderedTermId = u32;
+
 #[cfg(test)]
 mod tests {
 
@@ -4,43 +4,70 @@ use datastruct::stacker::{HashMap, Heap};
 use postings::{FieldSerializer, InvertedIndexSerializer};
 use postings::{NothingRecorder, TFAndPositionRecorder, TermFrequencyRecorder};
 use postings::Recorder;
+use postings::UnorderedTermId;
 use schema::{Field, Schema};
 use schema::FieldEntry;
 use schema::FieldType;
 use schema::IndexRecordOption;
 use schema::Term;
+use schema::TextFieldIndexing;
+use std::collections::HashMap as StdHashMap;
 use std::io;
 use std::marker::PhantomData;
 use std::ops::DerefMut;
+use termdict::TermOrdinal;
 use tokenizer::Token;
 use tokenizer::TokenStream;
 
+fn posting_from_text_indexing<'a>(
+    indexing_options_opt: Option<&TextFieldIndexing>,
+    heap: &'a Heap,
+) -> Box<PostingsWriter + 'a>
+{
+    indexing_options_opt
+        .map(|indexing_options| match indexing_options.index_option() {
+            IndexRecordOption::Basic => {
+                SpecializedPostingsWriter::<NothingRecorder>::new_boxed(heap)
+            }
+            IndexRecordOption::WithFreqs => {
+                SpecializedPostingsWriter::<TermFrequencyRecorder>::new_boxed(heap)
+            }
+            IndexRecordOption::WithFreqsAndPositions => {
+                SpecializedPostingsWriter::<TFAndPositionRecorder>::new_boxed(heap)
+            }
+        })
+        .unwrap_or_else(|| SpecializedPostingsWriter::<NothingRecorder>::new_boxed(heap))
+}
+
 fn posting_from_field_entry<'a>(
     field_entry: &FieldEntry,
     heap: &'a Heap,
 ) -> Box<PostingsWriter + 'a>
 {
     match *field_entry.field_type() {
-        FieldType::Str(ref text_options) => text_options
-            .get_indexing_options()
-            .map(|indexing_options| match indexing_options.index_option() {
-                IndexRecordOption::Basic => {
-                    SpecializedPostingsWriter::<NothingRecorder>::new_boxed(heap)
-                }
-                IndexRecordOption::WithFreqs => {
-                    SpecializedPostingsWriter::<TermFrequencyRecorder>::new_boxed(heap)
-                }
-                IndexRecordOption::WithFreqsAndPositions => {
-                    SpecializedPostingsWriter::<TFAndPositionRecorder>::new_boxed(heap)
-                }
-            })
-            .unwrap_or_else(|| SpecializedPostingsWriter::<NothingRecorder>::new_boxed(heap)),
-        FieldType::U64(_) | FieldType::I64(_) => {
+        FieldType::Str(ref text_options) => {
+            posting_from_text_indexing(text_options.get_indexing_options(), heap)
+        }
+        FieldType::JsonObject(ref json_object_options) => {
+            posting_from_text_indexing(json_object_options.get_text_indexing_options(), heap)
+        }
+        FieldType::U64(_)
+        | FieldType::I64(_)
+        | FieldType::F64(_)
+        | FieldType::Date(_)
+        | FieldType::HierarchicalFacet
+        | FieldType::Bytes(_)
+        | FieldType::Ip(_) => {
             SpecializedPostingsWriter::<NothingRecorder>::new_boxed(heap)
         }
     }
 }
 
+/// For each of the fields requiring it (hierarchical facets and fast text fields),
+/// associates the `UnorderedTermId` of the terms to their
+/// actual `TermOrdinal` in the serialized term dictionary.
+pub(crate) type TermOrdinalMapping = StdHashMap<Field, StdHashMap<UnorderedTermId, TermOrdinal>>;
+
 pub struct MultiFieldPostingsWriter<'a> {
     heap: &'a Heap,
     term_index: HashMap<'a>,
@@ -65,21 +92,57 @@ impl<'a> MultiFieldPostingsWriter<'a> {
         }
     }
 
-    pub fn index_text(&mut self, doc: DocId, field: Field, token_stream: &mut TokenStream) -> u32 {
+    /// Indexes all of the tokens of the token stream.
+    ///
+    /// If `term_ids` is given, the `UnorderedTermId` of each token
+    /// is pushed into it.
+    ///
+    /// Returns the number of tokens.
+    pub fn index_text(
+        &mut self,
+        doc: DocId,
+        field: Field,
+        token_stream: &mut TokenStream,
+        term_ids: Option<&mut Vec<UnorderedTermId>>,
+    ) -> u32 {
         let postings_writer = self.per_field_postings_writers[field.0 as usize].deref_mut();
-        postings_writer.index_text(&mut self.term_index, doc, field, token_stream, self.heap)
+        postings_writer.index_text(
+            &mut self.term_index,
+            doc,
+            field,
+            token_stream,
+            term_ids,
+            self.heap,
+        )
     }
 
-    pub fn subscribe(&mut self, doc: DocId, term: &Term) {
+    /// Records that the document contains the term.
+    ///
+    /// Returns the `UnorderedTermId` of the term.
+    pub fn subscribe(&mut self, doc: DocId, term: &Term) -> UnorderedTermId {
+        self.subscribe_at(doc, 0u32, term)
+    }
+
+    /// Records that the document contains the term at the given position.
+    ///
+    /// Returns the `UnorderedTermId` of the term.
+    pub fn subscribe_at(&mut self, doc: DocId, position: u32, term: &Term) -> UnorderedTermId {
         let postings_writer = self.per_field_postings_writers[term.field().0 as usize].deref_mut();
-        postings_writer.suscribe(&mut self.term_index, doc, 0u32, term, self.heap)
+        postings_writer.suscribe(&mut self.term_index, doc, position, term, self.heap)
     }
 
     /// Serialize the inverted index.
     /// It pushes all term, one field at a time, towards the
     /// postings serializer.
+    ///
+    /// Returns the mapping from `UnorderedTermId` to `TermOrdinal`
+    /// of the fields that require it.
     #[allow(needless_range_loop)]
-    pub fn serialize(&self, serializer: &mut InvertedIndexSerializer) -> Result<()> {
+    pub fn serialize(
+        &self,
+        serializer: &mut InvertedIndexSerializer,
+        schema: &Schema,
+    ) -> Result<TermOrdinalMapping> {
         let mut term_offsets: Vec<(&[u8], u32)> = self.term_index.iter().collect();
         term_offsets.sort_by_key(|&(k, _v)| k);
 
@@ -98,9 +161,21 @@ impl<'a> MultiFieldPostingsWriter<'a> {
             }
         }
         offsets.push((Field(0), term_offsets.len()));
+        let mut term_ordinal_mapping = TermOrdinalMapping::new();
         for i in 0..(offsets.len() - 1) {
             let (field, start) = offsets[i];
             let (_, stop) = offsets[i + 1];
+            if schema.get_field_entry(field).is_term_ord_fast() {
+                let field_mapping: StdHashMap<UnorderedTermId, TermOrdinal> = term_offsets
+                    [start..stop]
+                    .iter()
+                    .enumerate()
+                    .map(|(term_ord, &(_, unordered_term_id))| {
+                        (unordered_term_id, term_ord as TermOrdinal)
+                    })
+                    .collect();
+                term_ordinal_mapping.insert(field, field_mapping);
+            }
             let postings_writer = &self.per_field_postings_writers[field.0 as usize];
             let mut field_serializer = serializer.new_field(field)?;
             postings_writer.serialize(
@@ -110,7 +185,7 @@ impl<'a> MultiFieldPostingsWriter<'a> {
             )?;
             field_serializer.close()?;
         }
-        Ok(())
+        Ok(term_ordinal_mapping)
     }
 
     /// Return true iff the term dictionary is saturated.
@@ -131,6 +206,8 @@ pub trait PostingsWriter {
     /// * term - the term
     /// * heap - heap used to store the postings informations as well as the terms
     /// in the hashmap.
+    ///
+    /// Returns the `UnorderedTermId` of the term.
     fn suscribe(
         &mut self,
         term_index: &mut HashMap,
@@ -138,7 +215,7 @@ pub trait PostingsWriter {
         pos: u32,
         term: &Term,
         heap: &Heap,
-    );
+    ) -> UnorderedTermId;
 
     /// Serializes the postings on disk.
     /// The actual serialization format is handled by the `PostingsSerializer`.
@@ -150,12 +227,16 @@ pub trait PostingsWriter {
     ) -> io::Result<()>;
 
     /// Tokenize a text and suscribe all of its token.
+    ///
+    /// If `term_ids` is given, the `UnorderedTermId` of each
+    /// token is pushed into it.
     fn index_text(
         &mut self,
         term_index: &mut HashMap,
         doc_id: DocId,
         field: Field,
         token_stream: &mut TokenStream,
+        mut term_ids: Option<&mut Vec<UnorderedTermId>>,
         heap: &Heap,
     ) -> u32
     {
@@ -163,7 +244,10 @@ pub trait PostingsWriter {
         term.set_field(field);
         let mut sink = |token: &Token| {
             term.set_text(token.text.as_str());
-            self.suscribe(term_index, doc_id, token.position as u32, &term, heap);
+            let term_id = self.suscribe(term_index, doc_id, token.position as u32, &term, heap);
+            if let Some(ref mut term_ids) = term_ids {
+                term_ids.push(term_id);
+            }
         };
 
         token_stream.process(&mut sink)
@@ -200,10 +284,11 @@ impl<'a, Rec: Recorder + 'static> PostingsWriter for SpecializedPostingsWriter<'
         position: u32,
         term: &Term,
         heap: &Heap,
-    )
+    ) -> UnorderedTermId
     {
         debug_assert!(term.as_slice().len() >= 4);
-        let recorder: &mut Rec = term_index.get_or_create(term);
+        let (term_id, recorder): (UnorderedTermId, &mut Rec) =
+            term_index.get_or_create_with_addr(term);
         let current_doc = recorder.current_doc();
         if current_doc != doc {
             if current_doc != u32::max_value() {
@@ -212,6 +297,7 @@ impl<'a, Rec: Recorder + 'static> PostingsWriter for SpecializedPostingsWriter<'
             recorder.new_doc(doc, heap);
         }
         recorder.record_position(position, heap);
+        term_id
     }
 
     fn serialize(
@@ -125,19 +125,23 @@ impl<'a> FieldSerializer<'a> {
         positions_write: &'a mut CountingWriter<WritePtr>,
     ) -> io::Result<FieldSerializer<'a>>
     {
-        let (term_freq_enabled, position_enabled): (bool, bool) = match field_type {
-            FieldType::Str(ref text_options) => {
-                if let Some(text_indexing_options) = text_options.get_indexing_options() {
-                    let index_option = text_indexing_options.index_option();
-                    (
-                        index_option.is_termfreq_enabled(),
-                        index_option.is_position_enabled(),
-                    )
-                } else {
-                    (false, false)
+        let (term_freq_enabled, position_enabled): (bool, bool) = {
+            let text_indexing_options = match field_type {
+                FieldType::Str(ref text_options) => text_options.get_indexing_options(),
+                FieldType::JsonObject(ref json_object_options) => {
+                    json_object_options.get_text_indexing_options()
                 }
+                _ => None,
+            };
+            if let Some(text_indexing_options) = text_indexing_options {
+                let index_option = text_indexing_options.index_option();
+                (
+                    index_option.is_termfreq_enabled(),
+                    index_option.is_position_enabled(),
+                )
+            } else {
+                (false, false)
             }
-            _ => (false, false),
         };
         let term_dictionary_builder =
             TermDictionaryBuilderImpl::new(term_dictionary_write, field_type)?;
@@ -23,6 +23,11 @@ pub struct TermInfo {
     pub positions_inner_offset: u8,
 }
 
+impl TermInfo {
+    /// Number of bytes used to serialize a `TermInfo`.
+    pub(crate) const SIZE_IN_BYTES: usize = 4 + 8 + 8 + 1;
+}
+
 impl BinarySerializable for TermInfo {
     fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
         self.doc_freq.serialize(writer)?;
@@ -0,0 +1,115 @@
+use DocId;
+use Score;
+use postings::DocSet;
+use query::Scorer;
+
+/// Union of a set of `DocSet`s, used when scores are not required.
+///
+/// Rather than merging the underlying docsets with a heap,
+/// all of their documents are first inserted in a bitset of
+/// `max_doc` bits, which is then iterated in order.
+///
+/// The score of all of the documents is `1`.
+pub struct BitSetUnionScorer {
+    words: Vec<u64>,
+    // index of the next bit to inspect.
+    cursor: usize,
+    doc: DocId,
+    len: usize,
+}
+
+impl BitSetUnionScorer {
+    /// Consumes the docsets and builds their union.
+    pub fn new<TDocSet: DocSet>(docsets: Vec<TDocSet>, max_doc: DocId) -> BitSetUnionScorer {
+        let num_words = (max_doc as usize + 63) / 64;
+        let mut words = vec![0u64; num_words];
+        for mut docset in docsets {
+            while docset.advance() {
+                let doc = docset.doc() as usize;
+                words[doc / 64] |= 1u64 << (doc % 64);
+            }
+        }
+        let len = words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum();
+        BitSetUnionScorer {
+            words,
+            cursor: 0,
+            doc: 0,
+            len,
+        }
+    }
+}
+
+impl DocSet for BitSetUnionScorer {
+    fn advance(&mut self) -> bool {
+        let mut word_id = self.cursor / 64;
+        if word_id >= self.words.len() {
+            return false;
+        }
+        // bits before the cursor have already been emitted.
+        let mut word = self.words[word_id] & (!0u64 << (self.cursor % 64));
+        loop {
+            if word != 0 {
+                let doc = word_id * 64 + word.trailing_zeros() as usize;
+                self.doc = doc as DocId;
+                self.cursor = doc + 1;
+                return true;
+            }
+            word_id += 1;
+            if word_id >= self.words.len() {
+                self.cursor = word_id * 64;
+                return false;
+            }
+            word = self.words[word_id];
+        }
+    }
+
+    fn doc(&self) -> DocId {
+        self.doc
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len
+    }
+}
+
+impl Scorer for BitSetUnionScorer {
+    fn score(&self) -> Score {
+        1f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::BitSetUnionScorer;
+    use postings::{DocSet, VecPostings};
+    use query::term_query::TermScorer;
+
+    fn scorer_from_docs(docs: Vec<u32>) -> TermScorer<VecPostings> {
+        TermScorer {
+            idf: 1f32,
+            fieldnorm_reader_opt: None,
+            postings: VecPostings::from(docs),
+        }
+    }
+
+    #[test]
+    fn test_bitset_union_scorer() {
+        let scorers = vec![
+            scorer_from_docs(vec![1, 63, 64, 200]),
+            scorer_from_docs(vec![0, 1, 65, 199]),
+            scorer_from_docs(vec![]),
+        ];
+        let mut union_scorer = BitSetUnionScorer::new(scorers, 201);
+        assert_eq!(union_scorer.size_hint(), 7);
+        let mut docs = Vec::new();
+        while union_scorer.advance() {
+            docs.push(union_scorer.doc());
+        }
+        assert_eq!(docs, vec![0, 1, 63, 64, 65, 199, 200]);
+        assert!(!union_scorer.advance());
+    }
+}
@@ -1,4 +1,5 @@
 use super::BooleanScorer;
+use super::bitset_union_scorer::BitSetUnionScorer;
 use Result;
 use core::SegmentReader;
 use query::OccurFilter;
@@ -28,4 +29,18 @@ impl Weight for BooleanWeight {
         let boolean_scorer = BooleanScorer::new(sub_scorers, self.occur_filter);
         Ok(box boolean_scorer)
     }
+
+    fn scorer_without_scores<'a>(&'a self, reader: &'a SegmentReader) -> Result<Box<Scorer + 'a>> {
+        let sub_scorers: Vec<Box<Scorer + 'a>> = self.weights
+            .iter()
+            .map(|weight| weight.scorer_without_scores(reader))
+            .collect::<Result<_>>()?;
+        if sub_scorers.len() > 1 && self.occur_filter.is_disjunction() {
+            // pure disjunction: no need to go through the heap
+            // of the `BooleanScorer`.
+            Ok(box BitSetUnionScorer::new(sub_scorers, reader.max_doc()))
+        } else {
+            Ok(box BooleanScorer::new(sub_scorers, self.occur_filter))
+        }
+    }
 }
@@ -1,8 +1,10 @@
+mod bitset_union_scorer;
 mod boolean_query;
 mod boolean_scorer;
 mod boolean_weight;
 mod score_combiner;
 
+pub(crate) use self::bitset_union_scorer::BitSetUnionScorer;
 pub use self::boolean_query::BooleanQuery;
 pub use self::boolean_scorer::BooleanScorer;
 pub use self::score_combiner::ScoreCombiner;
@@ -12,6 +14,7 @@ mod tests {
 
     use super::*;
     use Index;
+    use collector::CountCollector;
     use collector::tests::TestCollector;
     use fastfield::U64FastFieldReader;
     use postings::{DocSet, VecPostings};
@@ -75,7 +78,13 @@ mod tests {
             let searcher = index.searcher();
             let mut test_collector = TestCollector::default();
             searcher.search(boolean_query, &mut test_collector).unwrap();
-            test_collector.docs()
+            let docs = test_collector.docs();
+            // the count collector does not require scoring,
+            // and goes through the non-scoring path.
+            let mut count_collector = CountCollector::default();
+            searcher.search(boolean_query, &mut count_collector).unwrap();
+            assert_eq!(count_collector.count(), docs.len());
+            docs
         };
         {
             let boolean_query = BooleanQuery::from(vec![(Occur::Must, make_term_query("a"))]);
@@ -92,6 +101,14 @@ mod tests {
             ]);
             assert_eq!(matching_docs(&boolean_query), vec![0, 1, 2, 3]);
         }
+        {
+            let boolean_query = BooleanQuery::from(vec![
+                (Occur::Should, make_term_query("a")),
+                (Occur::Should, make_term_query("b")),
+                (Occur::Should, make_term_query("d")),
+            ]);
+            assert_eq!(matching_docs(&boolean_query), vec![0, 1, 2, 3, 4]);
+        }
         {
             let boolean_query = BooleanQuery::from(vec![
                 (Occur::Must, make_term_query("a")),
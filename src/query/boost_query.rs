@@ -0,0 +1,141 @@
+use DocId;
+use Result;
+use Score;
+use Searcher;
+use core::SegmentReader;
+use postings::{DocSet, SkipResult};
+use query::Query;
+use query::Scorer;
+use query::Weight;
+use std::any::Any;
+
+/// `BoostQuery` wraps a query and multiplies the score
+/// of all of the documents it matches by a constant factor.
+///
+/// The set of matched documents is the same as the one
+/// of the underlying query.
+#[derive(Debug)]
+pub struct BoostQuery {
+    query: Box<Query>,
+    boost: Score,
+}
+
+impl BoostQuery {
+    /// Creates a new `BoostQuery`.
+    pub fn new(query: Box<Query>, boost: Score) -> BoostQuery {
+        BoostQuery { query, boost }
+    }
+
+    /// Returns the boost factor.
+    pub fn boost(&self) -> Score {
+        self.boost
+    }
+}
+
+impl Query for BoostQuery {
+    fn as_any(&self) -> &Any {
+        self
+    }
+
+    fn weight(&self, searcher: &Searcher) -> Result<Box<Weight>> {
+        let weight = self.query.weight(searcher)?;
+        Ok(box BoostWeight {
+            weight,
+            boost: self.boost,
+        })
+    }
+}
+
+struct BoostWeight {
+    weight: Box<Weight>,
+    boost: Score,
+}
+
+impl Weight for BoostWeight {
+    fn scorer<'a>(&'a self, reader: &'a SegmentReader) -> Result<Box<Scorer + 'a>> {
+        let underlying = self.weight.scorer(reader)?;
+        Ok(box BoostScorer {
+            underlying,
+            boost: self.boost,
+        })
+    }
+
+    fn scorer_without_scores<'a>(&'a self, reader: &'a SegmentReader) -> Result<Box<Scorer + 'a>> {
+        self.weight.scorer_without_scores(reader)
+    }
+}
+
+struct BoostScorer<TScorer: Scorer> {
+    underlying: TScorer,
+    boost: Score,
+}
+
+impl<TScorer: Scorer> DocSet for BoostScorer<TScorer> {
+    fn advance(&mut self) -> bool {
+        self.underlying.advance()
+    }
+
+    fn skip_next(&mut self, target: DocId) -> SkipResult {
+        self.underlying.skip_next(target)
+    }
+
+    fn doc(&self) -> DocId {
+        self.underlying.doc()
+    }
+
+    fn size_hint(&self) -> usize {
+        self.underlying.size_hint()
+    }
+}
+
+impl<TScorer: Scorer> Scorer for BoostScorer<TScorer> {
+    fn score(&self) -> Score {
+        self.underlying.score() * self.boost
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::BoostQuery;
+    use Index;
+    use collector::TopCollector;
+    use query::{Query, TermQuery};
+    use schema::{IndexRecordOption, SchemaBuilder, Term, TEXT};
+
+    #[test]
+    fn test_boost_query() {
+        let mut schema_builder = SchemaBuilder::default();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(text_field => "a b"));
+            index_writer.add_document(doc!(text_field => "a"));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let term_query = || -> Box<Query> {
+            box TermQuery::new(
+                Term::from_field_text(text_field, "a"),
+                IndexRecordOption::WithFreqs,
+            )
+        };
+        let score_docs = |query: &Query| {
+            let mut top_collector = TopCollector::with_limit(2);
+            searcher.search(query, &mut top_collector).unwrap();
+            top_collector.score_docs()
+        };
+        let unboosted = score_docs(&*term_query());
+        let boosted = score_docs(&BoostQuery::new(term_query(), 3f32));
+        assert_eq!(unboosted.len(), 2);
+        assert_eq!(boosted.len(), 2);
+        for (&(unboosted_score, unboosted_addr), &(boosted_score, boosted_addr)) in
+            unboosted.iter().zip(boosted.iter())
+        {
+            assert_eq!(unboosted_addr, boosted_addr);
+            assert!((boosted_score - unboosted_score * 3f32).abs() < 0.0001f32);
+        }
+    }
+}
@@ -11,14 +11,20 @@ mod occur_filter;
 mod term_query;
 mod query_parser;
 mod phrase_query;
+mod boost_query;
+mod more_like_this;
+mod range_query;
 
 pub use self::boolean_query::BooleanQuery;
+pub use self::boost_query::BoostQuery;
+pub use self::more_like_this::{MoreLikeThisQuery, MoreLikeThisQueryBuilder};
 pub use self::occur::Occur;
 pub use self::occur_filter::OccurFilter;
 pub use self::phrase_query::PhraseQuery;
 pub use self::query::Query;
 pub use self::query_parser::QueryParser;
 pub use self::query_parser::QueryParserError;
+pub use self::range_query::RangeQuery;
 pub use self::scorer::EmptyScorer;
 pub use self::scorer::Scorer;
 pub use self::term_query::TermQuery;
@@ -0,0 +1,78 @@
+mod more_like_this;
+mod more_like_this_query;
+
+pub use self::more_like_this_query::{MoreLikeThisQuery, MoreLikeThisQueryBuilder};
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use DocAddress;
+    use Index;
+    use collector::TopCollector;
+    use schema::{SchemaBuilder, STORED, TEXT};
+
+    #[test]
+    pub fn test_more_like_this_query() {
+        let mut schema_builder = SchemaBuilder::default();
+        let text_field = schema_builder.add_text_field("text", TEXT | STORED);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            // 0
+            index_writer.add_document(doc!(text_field => "apple banana apple cherry"));
+            // 1
+            index_writer.add_document(doc!(text_field => "apple banana apple"));
+            // 2
+            index_writer.add_document(doc!(text_field => "zebra lion zebra tiger"));
+            // 3
+            index_writer.add_document(doc!(text_field => "zebra lion"));
+            // 4
+            index_writer.add_document(doc!(text_field => "cherry"));
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let search = |query: &MoreLikeThisQuery| -> Vec<u32> {
+            let mut top_collector = TopCollector::with_limit(10);
+            searcher.search(query, &mut top_collector).unwrap();
+            let mut docs: Vec<u32> = top_collector
+                .docs()
+                .into_iter()
+                .map(|doc_address| doc_address.doc())
+                .collect();
+            docs.sort();
+            docs
+        };
+        {
+            let query = MoreLikeThisQuery::builder(&index)
+                .with_min_doc_frequency(1)
+                .with_min_term_frequency(2)
+                .with_document(DocAddress(0, 0));
+            assert_eq!(search(&query), vec![0, 1]);
+        }
+        {
+            let query = MoreLikeThisQuery::builder(&index)
+                .with_min_doc_frequency(1)
+                .with_min_term_frequency(1)
+                .with_document(DocAddress(0, 3));
+            assert_eq!(search(&query), vec![2, 3]);
+        }
+        {
+            let query = MoreLikeThisQuery::builder(&index)
+                .with_min_doc_frequency(1)
+                .with_min_term_frequency(1)
+                .with_text("Zebras love cherry");
+            assert_eq!(search(&query), vec![0, 4]);
+        }
+        {
+            let query = MoreLikeThisQuery::builder(&index)
+                .with_min_doc_frequency(1)
+                .with_min_term_frequency(1)
+                .with_max_query_terms(1)
+                .with_text("lion cherry cherry");
+            assert_eq!(search(&query), vec![0, 4]);
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use Result;
+use Score;
+use Searcher;
+use query::{BoostQuery, Occur, Query, TermQuery};
+use query::BooleanQuery;
+use schema::{Field, FieldType, IndexRecordOption, Schema, Term, Value};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use tokenizer::TokenizerManager;
+
+/// `BooleanQuery` (or more accurately the `OccurFilter` it relies on)
+/// cannot handle more than 64 subqueries.
+const MAX_QUERY_TERMS_LIMIT: usize = 64;
+
+/// A term associated to its tf-idf score in the target document.
+///
+/// The ordering is reversed, so that a `BinaryHeap<ScoreTerm>`
+/// behaves as a min-heap.
+struct ScoreTerm {
+    term: Term,
+    score: Score,
+}
+
+impl PartialOrd for ScoreTerm {
+    fn partial_cmp(&self, other: &ScoreTerm) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScoreTerm {
+    fn cmp(&self, other: &ScoreTerm) -> Ordering {
+        other
+            .score
+            .partial_cmp(&self.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.term.cmp(&other.term))
+    }
+}
+
+impl PartialEq for ScoreTerm {
+    fn eq(&self, other: &ScoreTerm) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ScoreTerm {}
+
+/// Parameters and logic used to extract the most
+/// representative terms of a document.
+///
+/// See [`MoreLikeThisQuery`](./struct.MoreLikeThisQuery.html).
+#[derive(Debug, Clone)]
+pub(crate) struct MoreLikeThis {
+    pub min_doc_frequency: u32,
+    pub max_doc_frequency: u32,
+    pub min_term_frequency: usize,
+    pub max_query_terms: usize,
+    pub min_word_length: usize,
+    pub max_word_length: usize,
+    pub boost_factor: Score,
+}
+
+impl Default for MoreLikeThis {
+    fn default() -> MoreLikeThis {
+        MoreLikeThis {
+            min_doc_frequency: 5,
+            max_doc_frequency: u32::max_value(),
+            min_term_frequency: 2,
+            max_query_terms: 25,
+            min_word_length: 0,
+            max_word_length: 0,
+            boost_factor: 1f32,
+        }
+    }
+}
+
+impl MoreLikeThis {
+    /// Builds the boosted disjunction for the stored fields
+    /// of the document at the given address.
+    pub fn query_with_document(
+        &self,
+        searcher: &Searcher,
+        schema: &Schema,
+        tokenizers: &TokenizerManager,
+        fields: &[Field],
+        doc_address: &::DocAddress,
+    ) -> Result<BooleanQuery> {
+        let doc = searcher.doc(doc_address)?;
+        let field_texts: Vec<(Field, Vec<&str>)> = fields
+            .iter()
+            .map(|&field| {
+                let texts: Vec<&str> = doc.get_all(field)
+                    .into_iter()
+                    .flat_map(|value| match *value {
+                        Value::Str(ref text) => Some(text.as_str()),
+                        _ => None,
+                    })
+                    .collect();
+                (field, texts)
+            })
+            .collect();
+        self.query_with_field_texts(searcher, schema, tokenizers, &field_texts)
+    }
+
+    /// Builds the boosted disjunction given some text
+    /// to analyze for each of the fields.
+    pub fn query_with_field_texts(
+        &self,
+        searcher: &Searcher,
+        schema: &Schema,
+        tokenizers: &TokenizerManager,
+        field_texts: &[(Field, Vec<&str>)],
+    ) -> Result<BooleanQuery> {
+        let mut term_frequencies: HashMap<Term, usize> = HashMap::new();
+        for &(field, ref texts) in field_texts {
+            if texts.is_empty() {
+                continue;
+            }
+            self.add_term_frequencies(schema, tokenizers, field, texts, &mut term_frequencies)?;
+        }
+        let score_terms = self.create_score_terms(searcher, term_frequencies);
+        Ok(self.create_query(score_terms))
+    }
+
+    /// Tokenizes the texts with the field's tokenizer and
+    /// counts the occurrences of each of the resulting terms.
+    fn add_term_frequencies(
+        &self,
+        schema: &Schema,
+        tokenizers: &TokenizerManager,
+        field: Field,
+        texts: &[&str],
+        term_frequencies: &mut HashMap<Term, usize>,
+    ) -> Result<()> {
+        let field_entry = schema.get_field_entry(field);
+        let tokenizer_name = match *field_entry.field_type() {
+            FieldType::Str(ref text_options) => match text_options.get_indexing_options() {
+                Some(indexing_options) => indexing_options.tokenizer().to_string(),
+                None => return Ok(()),
+            },
+            _ => {
+                let msg = format!(
+                    "More like this requires text fields. {:?} is not a text field.",
+                    field_entry.name()
+                );
+                bail!(::ErrorKind::InvalidArgument(msg));
+            }
+        };
+        let tokenizer = tokenizers.get(&tokenizer_name).ok_or_else(|| {
+            ::ErrorKind::SchemaError(format!(
+                "Tokenizer {:?} of field {:?} is not registered.",
+                tokenizer_name,
+                field_entry.name()
+            ))
+        })?;
+        let mut token_stream = tokenizer.token_stream_texts(texts);
+        token_stream.process(&mut |token| {
+            if !self.is_noise_word(&token.text) {
+                let term = Term::from_field_text(field, &token.text);
+                *term_frequencies.entry(term).or_insert(0) += 1;
+            }
+        });
+        Ok(())
+    }
+
+    fn is_noise_word(&self, word: &str) -> bool {
+        let word_length = word.chars().count();
+        if word_length == 0 {
+            return true;
+        }
+        if self.min_word_length > 0 && word_length < self.min_word_length {
+            return true;
+        }
+        self.max_word_length > 0 && word_length > self.max_word_length
+    }
+
+    /// Scores the terms by tf-idf and returns the `max_query_terms`
+    /// best ones, sorted by decreasing score.
+    fn create_score_terms(
+        &self,
+        searcher: &Searcher,
+        term_frequencies: HashMap<Term, usize>,
+    ) -> Vec<ScoreTerm> {
+        let num_docs = searcher.num_docs();
+        let max_query_terms = ::std::cmp::min(self.max_query_terms, MAX_QUERY_TERMS_LIMIT);
+        let mut heap: BinaryHeap<ScoreTerm> = BinaryHeap::with_capacity(max_query_terms + 1);
+        for (term, term_freq) in term_frequencies {
+            if term_freq < self.min_term_frequency {
+                continue;
+            }
+            let doc_freq = searcher.doc_freq(&term);
+            if doc_freq == 0 || doc_freq < self.min_doc_frequency
+                || doc_freq > self.max_doc_frequency
+            {
+                continue;
+            }
+            let idf = 1.0 + (num_docs as f32 / (doc_freq as f32 + 1.0)).ln();
+            let score = (term_freq as f32) * idf;
+            heap.push(ScoreTerm { term, score });
+            if heap.len() > max_query_terms {
+                heap.pop();
+            }
+        }
+        // `into_sorted_vec` sorts according to our reversed ordering,
+        // so the best terms come first.
+        heap.into_sorted_vec()
+    }
+
+    /// Creates the disjunction of the selected terms.
+    ///
+    /// Each term is boosted proportionally to its score,
+    /// the best term receiving a boost of `boost_factor`.
+    fn create_query(&self, score_terms: Vec<ScoreTerm>) -> BooleanQuery {
+        let best_score = score_terms
+            .first()
+            .map(|score_term| score_term.score)
+            .unwrap_or(1f32);
+        let sub_queries: Vec<(Occur, Box<Query>)> = score_terms
+            .into_iter()
+            .map(|ScoreTerm { term, score }| {
+                let term_query: Box<Query> =
+                    box TermQuery::new(term, IndexRecordOption::WithFreqs);
+                let boost = self.boost_factor * score / best_score;
+                let boosted_query: Box<Query> = box BoostQuery::new(term_query, boost);
+                (Occur::Should, boosted_query)
+            })
+            .collect();
+        BooleanQuery::from(sub_queries)
+    }
+}
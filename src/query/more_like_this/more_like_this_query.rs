@@ -0,0 +1,220 @@
+use super::more_like_this::MoreLikeThis;
+use DocAddress;
+use Index;
+use Result;
+use Score;
+use Searcher;
+use query::BooleanQuery;
+use query::Query;
+use query::Weight;
+use schema::{Field, FieldType, Schema};
+use std::any::Any;
+use std::fmt;
+use tokenizer::TokenizerManager;
+
+/// What the `MoreLikeThisQuery` should be similar to.
+#[derive(Debug, Clone)]
+enum MoreLikeThisTarget {
+    /// A document already stored in the index.
+    DocumentAddress(DocAddress),
+    /// Some raw text, analyzed with the tokenizer of each of the fields.
+    Text(String),
+}
+
+/// `MoreLikeThisQuery` matches the documents that are similar
+/// to a given document or to a given piece of text.
+///
+/// The target is tokenized using the tokenizer of each of the
+/// fields considered. The terms with the highest tf-idf are
+/// then selected and assembled in a disjunction, each term being
+/// boosted according to its tf-idf.
+///
+/// `MoreLikeThisQuery` instances are created with the
+/// [`MoreLikeThisQueryBuilder`](./struct.MoreLikeThisQueryBuilder.html).
+///
+/// ```rust,ignore
+/// let query = MoreLikeThisQuery::builder(&index)
+///     .with_min_doc_frequency(1)
+///     .with_min_term_frequency(1)
+///     .with_document(doc_address);
+/// searcher.search(&query, &mut top_collector)?;
+/// ```
+pub struct MoreLikeThisQuery {
+    mlt: MoreLikeThis,
+    schema: Schema,
+    tokenizer_manager: TokenizerManager,
+    fields: Vec<Field>,
+    target: MoreLikeThisTarget,
+}
+
+impl MoreLikeThisQuery {
+    /// Creates a new builder for a `MoreLikeThisQuery` running on the given index.
+    pub fn builder(index: &Index) -> MoreLikeThisQueryBuilder {
+        MoreLikeThisQueryBuilder::new(index.schema(), index.tokenizers().clone())
+    }
+
+    /// Builds the boolean query the `MoreLikeThisQuery` actually
+    /// runs against the given searcher.
+    pub fn to_boolean_query(&self, searcher: &Searcher) -> Result<BooleanQuery> {
+        match self.target {
+            MoreLikeThisTarget::DocumentAddress(ref doc_address) => self.mlt.query_with_document(
+                searcher,
+                &self.schema,
+                &self.tokenizer_manager,
+                &self.fields,
+                doc_address,
+            ),
+            MoreLikeThisTarget::Text(ref text) => {
+                let field_texts: Vec<(Field, Vec<&str>)> = self.fields
+                    .iter()
+                    .map(|&field| (field, vec![text.as_str()]))
+                    .collect();
+                self.mlt.query_with_field_texts(
+                    searcher,
+                    &self.schema,
+                    &self.tokenizer_manager,
+                    &field_texts,
+                )
+            }
+        }
+    }
+}
+
+impl fmt::Debug for MoreLikeThisQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "MoreLikeThisQuery(fields={:?}, target={:?}, params={:?})",
+            self.fields,
+            self.target,
+            self.mlt
+        )
+    }
+}
+
+impl Query for MoreLikeThisQuery {
+    fn as_any(&self) -> &Any {
+        self
+    }
+
+    fn weight(&self, searcher: &Searcher) -> Result<Box<Weight>> {
+        self.to_boolean_query(searcher)?.weight(searcher)
+    }
+}
+
+/// Builder for the [`MoreLikeThisQuery`](./struct.MoreLikeThisQuery.html).
+///
+/// Unless specified otherwise with `.with_fields(...)`,
+/// all of the indexed text fields of the schema are considered.
+pub struct MoreLikeThisQueryBuilder {
+    mlt: MoreLikeThis,
+    schema: Schema,
+    tokenizer_manager: TokenizerManager,
+    fields: Vec<Field>,
+}
+
+impl MoreLikeThisQueryBuilder {
+    fn new(schema: Schema, tokenizer_manager: TokenizerManager) -> MoreLikeThisQueryBuilder {
+        let fields: Vec<Field> = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|&(_, field_entry)| match *field_entry.field_type() {
+                FieldType::Str(ref text_options) => text_options.get_indexing_options().is_some(),
+                _ => false,
+            })
+            .map(|(field_id, _)| Field(field_id as u32))
+            .collect();
+        MoreLikeThisQueryBuilder {
+            mlt: MoreLikeThis::default(),
+            schema,
+            tokenizer_manager,
+            fields,
+        }
+    }
+
+    /// Sets the fields to consider. They must be indexed text fields.
+    pub fn with_fields(mut self, fields: Vec<Field>) -> MoreLikeThisQueryBuilder {
+        self.fields = fields;
+        self
+    }
+
+    /// Terms appearing in less than `min_doc_frequency` documents are ignored.
+    ///
+    /// Defaults to 5.
+    pub fn with_min_doc_frequency(mut self, value: u32) -> MoreLikeThisQueryBuilder {
+        self.mlt.min_doc_frequency = value;
+        self
+    }
+
+    /// Terms appearing in more than `max_doc_frequency` documents are ignored.
+    ///
+    /// Defaults to no limit.
+    pub fn with_max_doc_frequency(mut self, value: u32) -> MoreLikeThisQueryBuilder {
+        self.mlt.max_doc_frequency = value;
+        self
+    }
+
+    /// Terms appearing less than `min_term_frequency` times in the
+    /// target are ignored.
+    ///
+    /// Defaults to 2.
+    pub fn with_min_term_frequency(mut self, value: usize) -> MoreLikeThisQueryBuilder {
+        self.mlt.min_term_frequency = value;
+        self
+    }
+
+    /// Maximum number of terms in the resulting query.
+    ///
+    /// Defaults to 25. Values above 64 are capped to 64.
+    pub fn with_max_query_terms(mut self, value: usize) -> MoreLikeThisQueryBuilder {
+        self.mlt.max_query_terms = value;
+        self
+    }
+
+    /// Words shorter than `min_word_length` characters are ignored.
+    ///
+    /// Defaults to 0, which means no limit.
+    pub fn with_min_word_length(mut self, value: usize) -> MoreLikeThisQueryBuilder {
+        self.mlt.min_word_length = value;
+        self
+    }
+
+    /// Words longer than `max_word_length` characters are ignored.
+    ///
+    /// Defaults to 0, which means no limit.
+    pub fn with_max_word_length(mut self, value: usize) -> MoreLikeThisQueryBuilder {
+        self.mlt.max_word_length = value;
+        self
+    }
+
+    /// Boost given to the best term of the query.
+    /// Other terms are boosted proportionally to their tf-idf.
+    ///
+    /// Defaults to 1.
+    pub fn with_boost_factor(mut self, value: Score) -> MoreLikeThisQueryBuilder {
+        self.mlt.boost_factor = value;
+        self
+    }
+
+    /// Creates a query matching documents similar to the
+    /// stored document at the given address.
+    pub fn with_document(self, doc_address: DocAddress) -> MoreLikeThisQuery {
+        self.build(MoreLikeThisTarget::DocumentAddress(doc_address))
+    }
+
+    /// Creates a query matching documents similar to the given text.
+    pub fn with_text<S: Into<String>>(self, text: S) -> MoreLikeThisQuery {
+        self.build(MoreLikeThisTarget::Text(text.into()))
+    }
+
+    fn build(self, target: MoreLikeThisTarget) -> MoreLikeThisQuery {
+        MoreLikeThisQuery {
+            mlt: self.mlt,
+            schema: self.schema,
+            tokenizer_manager: self.tokenizer_manager,
+            fields: self.fields,
+            target,
+        }
+    }
+}
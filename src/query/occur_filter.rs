@@ -17,6 +17,12 @@ impl OccurFilter {
         (self.and_mask & ord_set) == self.result
     }
 
+    /// Returns true iff all of the clauses are `Occur::Should`,
+    /// in which case any non-empty bitset is accepted.
+    pub fn is_disjunction(&self) -> bool {
+        self.and_mask == 0
+    }
+
     /// Builds an `OccurFilter` from a list of `Occur`.
     pub fn new(occurs: &[Occur]) -> OccurFilter {
         let mut and_mask = 0u64;
@@ -12,7 +12,9 @@ mod tests {
     use super::*;
     use collector::tests::TestCollector;
     use core::Index;
-    use schema::{SchemaBuilder, Term, TEXT};
+    use query::QueryParser;
+    use schema::{IndexRecordOption, SchemaBuilder, Term, TextFieldIndexing, TextOptions, TEXT};
+    use tokenizer::{Language, LowerCaser, SimpleTokenizer, StopWordFilter, Tokenizer};
 
     #[test]
     pub fn test_phrase_query() {
@@ -74,4 +76,111 @@ mod tests {
         assert_eq!(test_query(vec!["g", "a"]), empty_vec);
     }
 
+    #[test]
+    pub fn test_phrase_query_with_offset() {
+        let mut schema_builder = SchemaBuilder::default();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(text_field=>"a b c d"));
+            index_writer.add_document(doc!(text_field=>"a c b d"));
+            assert!(index_writer.commit().is_ok());
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let test_query = |terms: Vec<(usize, &str)>| {
+            let mut test_collector = TestCollector::default();
+            let terms: Vec<(usize, Term)> = terms
+                .into_iter()
+                .map(|(offset, text)| (offset, Term::from_field_text(text_field, text)))
+                .collect();
+            let phrase_query = PhraseQuery::new_with_offset(terms);
+            searcher
+                .search(&phrase_query, &mut test_collector)
+                .expect("search should succeed");
+            test_collector.docs()
+        };
+        assert_eq!(test_query(vec![(0, "a"), (2, "c")]), vec![0]);
+        assert_eq!(test_query(vec![(2, "c"), (0, "a")]), vec![0]);
+        assert_eq!(test_query(vec![(1, "a"), (4, "d")]), vec![0, 1]);
+        assert_eq!(test_query(vec![(0, "b"), (1, "c")]), vec![0]);
+    }
+
+    #[test]
+    pub fn test_phrase_query_with_stop_words() {
+        let mut schema_builder = SchemaBuilder::default();
+        let text_field_indexing = TextFieldIndexing::default()
+            .set_tokenizer("en_stop")
+            .set_index_option(IndexRecordOption::WithFreqsAndPositions);
+        let text_options = TextOptions::default().set_indexing_options(text_field_indexing);
+        let text_field = schema_builder.add_text_field("text", text_options);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        index.tokenizers().register(
+            "en_stop",
+            SimpleTokenizer
+                .filter(LowerCaser)
+                .filter(StopWordFilter::for_language(Language::English)),
+        );
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(text_field=>"The Lord of the Rings"));
+            index_writer.add_document(doc!(text_field=>"Lord Rings"));
+            assert!(index_writer.commit().is_ok());
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![text_field]);
+        let test_query = |query_str: &str| {
+            let mut test_collector = TestCollector::default();
+            let query = query_parser.parse_query(query_str).unwrap();
+            searcher
+                .search(&*query, &mut test_collector)
+                .expect("search should succeed");
+            test_collector.docs()
+        };
+        assert_eq!(test_query("\"lord of the rings\""), vec![0]);
+        assert_eq!(test_query("\"lord rings\""), vec![1]);
+    }
+
+    #[test]
+    pub fn test_phrase_query_cjk() {
+        let mut schema_builder = SchemaBuilder::default();
+        let text_field_indexing = TextFieldIndexing::default()
+            .set_tokenizer("cjk")
+            .set_index_option(IndexRecordOption::WithFreqsAndPositions);
+        let text_options = TextOptions::default().set_indexing_options(text_field_indexing);
+        let text_field = schema_builder.add_text_field("text", text_options);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(text_field=>"我爱北京天安门"));
+            index_writer.add_document(doc!(text_field=>"北京大学在天安门附近"));
+            index_writer.add_document(doc!(text_field=>"书店"));
+            assert!(index_writer.commit().is_ok());
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![text_field]);
+        let test_query = |query_str: &str| {
+            let mut test_collector = TestCollector::default();
+            let query = query_parser.parse_query(query_str).unwrap();
+            searcher
+                .search(&*query, &mut test_collector)
+                .expect("search should succeed");
+            test_collector.docs()
+        };
+        assert_eq!(test_query("\"北京天安门\""), vec![0]);
+        assert_eq!(test_query("\"天安门\""), vec![0, 1]);
+        assert_eq!(test_query("北京"), vec![0, 1]);
+        // single-character words.
+        assert_eq!(test_query("书"), vec![2]);
+        assert_eq!(test_query("门"), vec![0, 1]);
+        assert_eq!(test_query("\"京天\""), vec![0]);
+        assert_eq!(test_query("\"京大\""), vec![1]);
+    }
+
 }
@@ -19,10 +19,32 @@ use std::any::Any;
 ///
 /// Using a `PhraseQuery` on a field requires positions
 /// to be indexed for this field.
+///
+/// Each term comes with its offset within the phrase. This makes it possible
+/// to match phrases that contained tokens removed by the tokenizer,
+/// e.g. stop words.
 /// 
 #[derive(Debug)]
 pub struct PhraseQuery {
-    phrase_terms: Vec<Term>,
+    phrase_terms: Vec<(usize, Term)>,
+}
+
+impl PhraseQuery {
+    /// Creates a new `PhraseQuery` given a list of terms and their offsets
+    /// within the phrase.
+    ///
+    /// For instance, the phrase `"lord of the rings"` where `of` and `the`
+    /// are stop words translates into `[(0, "lord"), (3, "rings")]`.
+    pub fn new_with_offset(mut phrase_terms: Vec<(usize, Term)>) -> PhraseQuery {
+        assert!(phrase_terms.len() > 1);
+        phrase_terms.sort_by_key(|&(offset, _)| offset);
+        let first_offset = phrase_terms[0].0;
+        let phrase_terms = phrase_terms
+            .into_iter()
+            .map(|(offset, term)| (offset - first_offset, term))
+            .collect();
+        PhraseQuery { phrase_terms }
+    }
 }
 
 impl Query for PhraseQuery {
@@ -42,7 +64,6 @@ impl Query for PhraseQuery {
 
 impl From<Vec<Term>> for PhraseQuery {
     fn from(phrase_terms: Vec<Term>) -> PhraseQuery {
-        assert!(phrase_terms.len() > 1);
-        PhraseQuery { phrase_terms }
+        PhraseQuery::new_with_offset(phrase_terms.into_iter().enumerate().collect())
     }
 }
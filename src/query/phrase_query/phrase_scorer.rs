@@ -7,6 +7,9 @@ use query::Scorer;
 
 pub struct PhraseScorer {
     pub intersection_docset: IntersectionDocSet<SegmentPostings>,
+    /// Offset within the phrase of the term of each docset
+    /// of the intersection, in the same order.
+    pub offsets: Vec<u32>,
 }
 
 impl PhraseScorer {
@@ -17,38 +20,34 @@ impl PhraseScorer {
             .map(|posting| posting.positions())
             .collect();
 
-        let num_postings = positions_arr.len() as u32;
+        let num_postings = positions_arr.len();
 
-        let mut ord = 1u32;
-        let mut pos_candidate = positions_arr[0][0];
-        positions_arr[0] = &(positions_arr[0])[1..];
-        let mut count_matching = 1;
-
-        #[cfg_attr(feature = "cargo-clippy", allow(never_loop))]
-        'outer: loop {
-            let target = pos_candidate + ord;
-            let positions = positions_arr[ord as usize];
-            for (i, pos_i) in positions.iter().cloned().enumerate() {
-                if pos_i < target {
-                    continue;
-                }
-                if pos_i == target {
-                    count_matching += 1;
-                    if count_matching == num_postings {
-                        return true;
-                    }
-                } else if pos_i > target {
-                    count_matching = 1;
-                    pos_candidate = positions[i] - ord;
-                    positions_arr[ord as usize] = &(positions_arr[ord as usize])[(i + 1)..];
-                }
-                ord += 1;
-                if ord == num_postings {
-                    ord = 0;
-                }
-                continue 'outer;
+        // position of the beginning of the phrase candidate.
+        let mut phrase_start = 0u32;
+        let mut count_matching = 0;
+        let mut ord = 0;
+        loop {
+            let offset = self.offsets[ord];
+            let target = phrase_start + offset;
+            let positions = positions_arr[ord];
+            let i = match positions.iter().position(|&pos| pos >= target) {
+                Some(i) => i,
+                None => return false,
+            };
+            if positions[i] == target {
+                count_matching += 1;
+            } else {
+                count_matching = 1;
+                phrase_start = positions[i] - offset;
+            }
+            if count_matching == num_postings {
+                return true;
+            }
+            positions_arr[ord] = &positions[(i + 1)..];
+            ord += 1;
+            if ord == num_postings {
+                ord = 0;
             }
-            return false;
         }
     }
 }
@@ -1,7 +1,9 @@
 use super::PhraseScorer;
+use DocSet;
 use Result;
 use core::SegmentReader;
 use postings::IntersectionDocSet;
+use postings::SegmentPostings;
 use query::EmptyScorer;
 use query::Scorer;
 use query::Weight;
@@ -9,11 +11,11 @@ use schema::IndexRecordOption;
 use schema::Term;
 
 pub struct PhraseWeight {
-    phrase_terms: Vec<Term>,
+    phrase_terms: Vec<(usize, Term)>,
 }
 
-impl From<Vec<Term>> for PhraseWeight {
-    fn from(phrase_terms: Vec<Term>) -> PhraseWeight {
+impl From<Vec<(usize, Term)>> for PhraseWeight {
+    fn from(phrase_terms: Vec<(usize, Term)>) -> PhraseWeight {
         PhraseWeight { phrase_terms }
     }
 }
@@ -21,18 +23,25 @@ impl From<Vec<Term>> for PhraseWeight {
 impl Weight for PhraseWeight {
     fn scorer<'a>(&'a self, reader: &'a SegmentReader) -> Result<Box<Scorer + 'a>> {
         let mut term_postings_list = Vec::new();
-        for term in &self.phrase_terms {
+        for &(offset, ref term) in &self.phrase_terms {
             let inverted_index = reader.inverted_index(term.field());
             let term_postings_option =
                 inverted_index.read_postings(term, IndexRecordOption::WithFreqsAndPositions);
             if let Some(term_postings) = term_postings_option {
-                term_postings_list.push(term_postings);
+                term_postings_list.push((offset as u32, term_postings));
             } else {
                 return Ok(box EmptyScorer);
             }
         }
+        // `IntersectionDocSet` sorts its docsets by size hint.
+        // The sort being stable, sorting them beforehand keeps
+        // the offsets aligned with the docsets.
+        term_postings_list.sort_by_key(|&(_, ref postings)| postings.size_hint());
+        let (offsets, postings): (Vec<u32>, Vec<SegmentPostings>) =
+            term_postings_list.into_iter().unzip();
         Ok(box PhraseScorer {
-            intersection_docset: IntersectionDocSet::from(term_postings_list),
+            intersection_docset: IntersectionDocSet::from(postings),
+            offsets,
         })
     }
 }
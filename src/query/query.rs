@@ -56,7 +56,8 @@ pub trait Query: fmt::Debug {
     ///
     /// Then, the query loops over the segments and for each segment :
     /// - setup the collector and informs it that the segment being processed has changed.
-    /// - creates a `Scorer` object associated for this segment
+    /// - creates a `Scorer` object associated for this segment. If the collector
+    /// does not require scoring, the scorer is created via `Weight::scorer_without_scores`.
     /// - iterate throw the matched documents and push them to the collector.
     /// 
     fn search(&self, searcher: &Searcher, collector: &mut Collector) -> Result<TimerTree> {
@@ -70,7 +71,11 @@ pub trait Query: fmt::Debug {
                     let _ = segment_search_timer.open("set_segment");
                     collector.set_segment(segment_ord as SegmentLocalId, segment_reader)?;
                 }
-                let mut scorer = weight.scorer(segment_reader)?;
+                let mut scorer = if collector.requires_scoring() {
+                    weight.scorer(segment_reader)?
+                } else {
+                    weight.scorer_without_scores(segment_reader)?
+                };
                 {
                     let _collection_timer = segment_search_timer.open("collection");
                     scorer.collect(collector);
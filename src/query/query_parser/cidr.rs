@@ -0,0 +1,79 @@
+use schema::parse_ip;
+use std::net::Ipv6Addr;
+
+/// Parses a CIDR block (e.g. `10.0.0.0/8` or `2001:db8::/32`),
+/// and returns the first and the last addresses of the block.
+///
+/// IPv4 blocks are mapped into IPv6, so that their prefix
+/// length is shifted by 96 bits.
+///
+/// Returns `None` if the block is invalid.
+pub fn parse_cidr(cidr: &str) -> Option<(Ipv6Addr, Ipv6Addr)> {
+    let slash_pos = get!(cidr.find('/'));
+    let ip_str = &cidr[..slash_pos];
+    let ip = get!(parse_ip(ip_str));
+    let prefix_len: usize = get!(cidr[slash_pos + 1..].parse().ok());
+    let is_ipv4 = !ip_str.contains(':');
+    let prefix_len = if is_ipv4 {
+        if prefix_len > 32 {
+            return None;
+        }
+        96 + prefix_len
+    } else {
+        if prefix_len > 128 {
+            return None;
+        }
+        prefix_len
+    };
+    let mut first = ip.octets();
+    let mut last = ip.octets();
+    for bit in prefix_len..128 {
+        let mask = 1u8 << (7 - bit % 8);
+        first[bit / 8] &= !mask;
+        last[bit / 8] |= mask;
+    }
+    Some((Ipv6Addr::from(first), Ipv6Addr::from(last)))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::parse_cidr;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test_parse_cidr() {
+        assert_eq!(
+            parse_cidr("10.0.0.0/8"),
+            Some((
+                Ipv4Addr::new(10, 0, 0, 0).to_ipv6_mapped(),
+                Ipv4Addr::new(10, 255, 255, 255).to_ipv6_mapped()
+            ))
+        );
+        assert_eq!(
+            parse_cidr("192.168.1.17/32"),
+            Some((
+                Ipv4Addr::new(192, 168, 1, 17).to_ipv6_mapped(),
+                Ipv4Addr::new(192, 168, 1, 17).to_ipv6_mapped()
+            ))
+        );
+        assert_eq!(
+            parse_cidr("192.168.1.17/20"),
+            Some((
+                Ipv4Addr::new(192, 168, 0, 0).to_ipv6_mapped(),
+                Ipv4Addr::new(192, 168, 15, 255).to_ipv6_mapped()
+            ))
+        );
+        assert_eq!(
+            parse_cidr("2001:db8::/32"),
+            Some((
+                Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0),
+                Ipv6Addr::new(0x2001, 0xdb8, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff)
+            ))
+        );
+        assert_eq!(parse_cidr("10.0.0.0"), None);
+        assert_eq!(parse_cidr("10.0.0.0/33"), None);
+        assert_eq!(parse_cidr("10.0.0/8"), None);
+        assert_eq!(parse_cidr("::/129"), None);
+    }
+}
@@ -0,0 +1,97 @@
+use DateTime;
+use chrono::Duration;
+use schema::parse_date;
+
+const MAX_DURATION_SECS: i64 = i64::max_value() / 1_000;
+
+/// Evaluates a date math expression.
+///
+/// The expression starts with an anchor date, which is either
+/// `now` or an explicit date followed by `||`
+/// (e.g. `2018-01-30||`). The anchor is then followed by any number
+/// of additions or subtractions, such as `+1h` or `-7d`.
+///
+/// The supported units are `s` (seconds), `m` (minutes), `h` (hours),
+/// `d` (days) and `w` (weeks).
+///
+/// An explicit date without any operation does not need the `||`.
+///
+/// Returns `None` if the expression is invalid.
+pub fn parse_date_math(expr: &str, now: DateTime) -> Option<DateTime> {
+    let (mut date, mut ops) = if expr.starts_with("now") {
+        (now, &expr[3..])
+    } else if let Some(pos) = expr.find("||") {
+        (get!(parse_date(&expr[..pos])), &expr[pos + 2..])
+    } else {
+        return parse_date(expr);
+    };
+    while !ops.is_empty() {
+        let is_addition = match ops.as_bytes()[0] {
+            b'+' => true,
+            b'-' => false,
+            _ => return None,
+        };
+        let num_end = 1 + get!(ops[1..].find(|c: char| !c.is_digit(10)));
+        let amount: i64 = get!(ops[1..num_end].parse().ok());
+        let unit_secs: i64 = match ops.as_bytes()[num_end] {
+            b's' => 1,
+            b'm' => 60,
+            b'h' => 3_600,
+            b'd' => 86_400,
+            b'w' => 604_800,
+            _ => return None,
+        };
+        let secs = get!(amount.checked_mul(unit_secs));
+        // `Duration` panics beyond `i64::MAX` milliseconds.
+        if secs > MAX_DURATION_SECS {
+            return None;
+        }
+        let duration = Duration::seconds(secs);
+        date = if is_addition {
+            get!(date.checked_add_signed(duration))
+        } else {
+            get!(date.checked_sub_signed(duration))
+        };
+        ops = &ops[num_end + 1..];
+    }
+    Some(date)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::parse_date_math;
+    use schema::parse_date;
+
+    #[test]
+    fn test_parse_date_math() {
+        let now = parse_date("2018-01-30T10:00:00Z").unwrap();
+        assert_eq!(parse_date_math("now", now), Some(now));
+        assert_eq!(
+            parse_date_math("now-7d", now),
+            parse_date("2018-01-23T10:00:00Z")
+        );
+        assert_eq!(
+            parse_date_math("now+1h-30m", now),
+            parse_date("2018-01-30T10:30:00Z")
+        );
+        assert_eq!(
+            parse_date_math("2018-01-01", now),
+            parse_date("2018-01-01T00:00:00Z")
+        );
+        assert_eq!(
+            parse_date_math("2018-01-01||+2w", now),
+            parse_date("2018-01-15T00:00:00Z")
+        );
+        assert_eq!(
+            parse_date_math("2018-01-01T10:00:00+02:00", now),
+            parse_date("2018-01-01T08:00:00Z")
+        );
+        assert_eq!(parse_date_math("now-7", now), None);
+        assert_eq!(parse_date_math("now-7y", now), None);
+        assert_eq!(parse_date_math("now*2d", now), None);
+        assert_eq!(parse_date_math("yesterday", now), None);
+        assert_eq!(parse_date_math("now-999999999999999w", now), None);
+        assert_eq!(parse_date_math("now+99999999999999999999s", now), None);
+    }
+}
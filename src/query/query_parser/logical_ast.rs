@@ -1,11 +1,22 @@
+use Score;
 use query::Occur;
-use schema::Term;
+use schema::{Field, Term};
+use std::collections::Bound;
 use std::fmt;
 
 #[derive(Clone)]
 pub enum LogicalLiteral {
     Term(Term),
-    Phrase(Vec<Term>),
+    Phrase(Vec<(usize, Term)>),
+    /// Terms at the same position, with their weight.
+    Synonyms(Vec<(Term, Score)>),
+    /// Phrase with several alternative terms at some positions.
+    MultiPhrase(Vec<(usize, Vec<Term>)>),
+    Range {
+        field: Field,
+        lower: Bound<Term>,
+        upper: Bound<Term>,
+    },
 }
 
 #[derive(Clone)]
@@ -53,7 +64,38 @@ impl fmt::Debug for LogicalLiteral {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match *self {
             LogicalLiteral::Term(ref term) => write!(formatter, "{:?}", term),
-            LogicalLiteral::Phrase(ref terms) => write!(formatter, "\"{:?}\"", terms),
+            LogicalLiteral::Phrase(ref terms) => {
+                let terms: Vec<&Term> = terms.iter().map(|&(_, ref term)| term).collect();
+                write!(formatter, "\"{:?}\"", terms)
+            }
+            LogicalLiteral::Synonyms(ref weighted_terms) => {
+                let terms: Vec<&Term> = weighted_terms.iter().map(|&(ref term, _)| term).collect();
+                write!(formatter, "Synonyms({:?})", terms)
+            }
+            LogicalLiteral::MultiPhrase(ref positions) => {
+                let alternatives: Vec<&Vec<Term>> = positions
+                    .iter()
+                    .map(|&(_, ref alternatives)| alternatives)
+                    .collect();
+                write!(formatter, "\"{:?}\"", alternatives)
+            }
+            LogicalLiteral::Range {
+                ref lower,
+                ref upper,
+                ..
+            } => {
+                match *lower {
+                    Bound::Included(ref term) => write!(formatter, "[{:?}", term)?,
+                    Bound::Excluded(ref term) => write!(formatter, "{{{:?}", term)?,
+                    Bound::Unbounded => write!(formatter, "{{*")?,
+                }
+                write!(formatter, " TO ")?;
+                match *upper {
+                    Bound::Included(ref term) => write!(formatter, "{:?}]", term),
+                    Bound::Excluded(ref term) => write!(formatter, "{:?}}}", term),
+                    Bound::Unbounded => write!(formatter, "*}}"),
+                }
+            }
         }
     }
 }
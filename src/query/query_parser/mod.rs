@@ -1,5 +1,7 @@
 mod query_parser;
 mod query_grammar;
+mod date_math;
+mod cidr;
 mod user_input_ast;
 
 pub mod logical_ast;
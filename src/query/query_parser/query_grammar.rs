@@ -2,6 +2,15 @@ use super::user_input_ast::*;
 use combine::*;
 use combine::char::*;
 
+fn field<I>(input: I) -> ParseResult<String, I>
+where I: Stream<Item = char> {
+    (
+        letter(),
+        many(satisfy(|c: char| c.is_alphanumeric() || c == '_' || c == '.')),
+    ).map(|(s1, s2): (char, String)| format!("{}{}", s1, s2))
+        .parse_stream(input)
+}
+
 fn literal<I>(input: I) -> ParseResult<UserInputAST, I>
 where I: Stream<Item = char> {
     let term_val = || {
@@ -10,28 +19,80 @@ where I: Stream<Item = char> {
         phrase.or(word)
     };
 
-    let negative_numbers = (char('-'), many1(satisfy(|c: char| c.is_numeric())))
-        .map(|(s1, s2): (char, String)| format!("{}{}", s1, s2));
+    // values of a given field may be decimal numbers, dates or CIDR blocks.
+    let term_val_with_dots = || {
+        let word = (
+            satisfy(|c: char| c.is_alphanumeric()),
+            many(satisfy(|c: char| {
+                c.is_alphanumeric() || c == '.' || c == '-' || c == '/'
+            })),
+        ).map(|(s1, s2): (char, String)| format!("{}{}", s1, s2));
+        let phrase = (char('"'), many1(satisfy(|c| c != '"')), char('"')).map(|(_, s, _)| s);
+        phrase.or(word)
+    };
 
-    let field = (
-        letter(),
-        many(satisfy(|c: char| c.is_alphanumeric() || c == '_')),
+    let negative_numbers = (
+        char('-'),
+        many1(satisfy(|c: char| c.is_numeric() || c == '.')),
     ).map(|(s1, s2): (char, String)| format!("{}{}", s1, s2));
 
-    let term_val_with_field = negative_numbers.or(term_val());
+    let term_val_with_field = negative_numbers.or(term_val_with_dots());
 
-    let term_query =
-        (field, char(':'), term_val_with_field).map(|(field_name, _, phrase)| UserInputLiteral {
+    let term_query = (parser(field), char(':'), term_val_with_field).map(
+        |(field_name, _, phrase)| UserInputLiteral {
             field_name: Some(field_name),
             phrase,
-        });
+        },
+    );
     let term_default_field = term_val().map(|phrase| UserInputLiteral {
         field_name: None,
         phrase,
     });
-    try(term_query)
-        .or(term_default_field)
-        .map(UserInputAST::from)
+    try(parser(range))
+        .or(try(term_query).or(term_default_field).map(UserInputAST::from))
+        .parse_stream(input)
+}
+
+/// Parses a range, e.g. `price:[10 TO 20}`.
+///
+/// `[` and `]` denote inclusive bounds, `{` and `}` exclusive bounds,
+/// and `*` an unbounded side.
+///
+/// Bounds may be dates or date math expressions, e.g. `now-7d`.
+fn range<I>(input: I) -> ParseResult<UserInputAST, I>
+where I: Stream<Item = char> {
+    let bound_val = || {
+        char('*').map(|_| None).or(many1(satisfy(|c: char| {
+            c.is_alphanumeric() || c == '.' || c == '-' || c == '+' || c == ':' || c == '|'
+        })).map(Some))
+    };
+    let lower_bound = (char('[').or(char('{')), spaces(), bound_val()).map(
+        |(boundary, _, val): (char, _, Option<String>)| match val {
+            Some(val) if boundary == '[' => UserInputBound::Inclusive(val),
+            Some(val) => UserInputBound::Exclusive(val),
+            None => UserInputBound::Unbounded,
+        },
+    );
+    let upper_bound = (bound_val(), spaces(), char(']').or(char('}'))).map(
+        |(val, _, boundary): (Option<String>, _, char)| match val {
+            Some(val) if boundary == ']' => UserInputBound::Inclusive(val),
+            Some(val) => UserInputBound::Exclusive(val),
+            None => UserInputBound::Unbounded,
+        },
+    );
+    (
+        parser(field),
+        char(':'),
+        lower_bound,
+        spaces(),
+        string("TO"),
+        spaces(),
+        upper_bound,
+    ).map(|(field, _, lower, _, _, _, upper)| UserInputAST::Range {
+        field,
+        lower,
+        upper,
+    })
         .parse_stream(input)
 }
 
@@ -82,4 +143,20 @@ mod test {
         test_parse_query_to_ast_helper("abc:\"a b\"", "abc:\"a b\"");
         test_is_parse_err("abc +    ");
     }
+
+    #[test]
+    fn test_parse_range_to_ast() {
+        test_parse_query_to_ast_helper("price:[1 TO 10]", "price:[\"1\" TO \"10\"]");
+        test_parse_query_to_ast_helper("price:{-1.5 TO 10}", "price:{\"-1.5\" TO \"10\"}");
+        test_parse_query_to_ast_helper("price:[* TO 10}", "price:{* TO \"10\"}");
+        test_parse_query_to_ast_helper("+price:[1 TO *] a", "+(price:[\"1\" TO *}) \"a\"");
+        test_parse_query_to_ast_helper("price:3.5", "price:\"3.5\"");
+        test_parse_query_to_ast_helper("price:-3.5", "price:\"-3.5\"");
+        test_parse_query_to_ast_helper("created:2018-01-30", "created:\"2018-01-30\"");
+        test_parse_query_to_ast_helper("src_ip:10.0.0.0/8", "src_ip:\"10.0.0.0/8\"");
+        test_parse_query_to_ast_helper(
+            "created:[2018-01-30T10:00:00Z TO now-7d}",
+            "created:[\"2018-01-30T10:00:00Z\" TO \"now-7d\"}",
+        );
+    }
 }
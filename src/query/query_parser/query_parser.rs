@@ -1,16 +1,28 @@
+use super::cidr::parse_cidr;
+use super::date_math::parse_date_math;
 use super::logical_ast::*;
 use super::query_grammar::parse_to_ast;
 use super::user_input_ast::*;
+use DateTime;
+use Score;
+use base64;
+use chrono::Utc;
 use core::Index;
 use query::BooleanQuery;
+use query::BoostQuery;
 use query::Occur;
 use query::PhraseQuery;
 use query::Query;
+use query::RangeQuery;
 use query::TermQuery;
-use schema::{Field, Schema};
+use schema::{Facet, FacetParseError, Field, Schema};
 use schema::{FieldType, Term};
 use schema::IndexRecordOption;
-use std::num::ParseIntError;
+use schema::parse_ip;
+use std::collections::Bound;
+use std::f64;
+use std::net::Ipv6Addr;
+use std::num::{ParseFloatError, ParseIntError};
 use std::str::FromStr;
 use tokenizer::TokenizerManager;
 
@@ -25,6 +37,9 @@ pub enum QueryParserError {
     /// The query contains a term for a `u64`-field, but the value
     /// is not a u64.
     ExpectedInt(ParseIntError),
+    /// The query contains a term for a `f64`-field, but the value
+    /// is not a f64.
+    ExpectedFloat(ParseFloatError),
     /// It is forbidden queries that are only "excluding". (e.g. -title:pop)
     AllButQueryForbidden,
     /// If no default field is declared, running a query without any
@@ -36,14 +51,58 @@ pub enum QueryParserError {
     /// The tokenizer for the given field is unknown
     /// The two argument strings are the name of the field, the name of the tokenizer
     UnknownTokenizer(String, String),
+    /// The query contains a term for a hierarchical facet field, but the value
+    /// is not a valid facet path.
+    ExpectedFacet(FacetParseError),
+    /// The query contains a term for a bytes field, but the value
+    /// is not valid base64.
+    ExpectedBase64(base64::DecodeError),
+    /// The query contains a term for a date field, but the value
+    /// is neither a valid date nor a valid date math expression.
+    ExpectedDate(String),
+    /// The query contains a term for an IP field, but the value
+    /// is neither a valid IP address nor a valid CIDR block.
+    ExpectedIp(String),
+    /// The query contains a range on a field that is not
+    /// a `u64`, `i64`, `f64`, date or IP field.
+    RangeOnNonNumericalField(String),
+    /// The query targets a JSON object field without specifying
+    /// a path within the object, e.g. `attributes:red` instead of
+    /// `attributes.color:red`.
+    JsonPathExpected(String),
+    /// The alternative terms of a phrase, e.g. synonyms, expand into
+    /// more than `MAX_PHRASE_EXPANSIONS` phrases.
+    TooManyPhraseExpansions,
 }
 
+/// Maximum number of phrases a phrase with alternative terms
+/// may expand into, each of them being searched as a `PhraseQuery`.
+const MAX_PHRASE_EXPANSIONS: usize = 64;
+
 impl From<ParseIntError> for QueryParserError {
     fn from(err: ParseIntError) -> QueryParserError {
         QueryParserError::ExpectedInt(err)
     }
 }
 
+impl From<ParseFloatError> for QueryParserError {
+    fn from(err: ParseFloatError) -> QueryParserError {
+        QueryParserError::ExpectedFloat(err)
+    }
+}
+
+impl From<base64::DecodeError> for QueryParserError {
+    fn from(err: base64::DecodeError) -> QueryParserError {
+        QueryParserError::ExpectedBase64(err)
+    }
+}
+
+impl From<FacetParseError> for QueryParserError {
+    fn from(err: FacetParseError) -> QueryParserError {
+        QueryParserError::ExpectedFacet(err)
+    }
+}
+
 /// Tantivy's Query parser
 ///
 /// The language covered by the current parser is extremely simple.
@@ -71,12 +130,47 @@ impl From<ParseIntError> for QueryParserError {
 ///   e.g. `apple -fruit`
 ///
 /// * must terms: By prepending a term by a `+`, a term can be made required for the search.
-/// 
+///
+/// * facets: hierarchical facet fields are searched by quoting the facet path,
+///   e.g. `category:"/electronics/phones"`. The query matches all of the documents
+///   belonging to the facet or to any of its descendants.
+///
+/// * ranges: `u64`, `i64`, `f64` and date fields can be searched by range,
+///   e.g. `price:[10 TO 20}`. `[` and `]` denote inclusive bounds,
+///   `{` and `}` exclusive bounds, and `*` leaves a side of the range unbounded,
+///   as in `price:{* TO 20]`.
+///
+/// * dates: date fields accept RFC 3339 dates, simple days (`2018-01-30`),
+///   and date math expressions relative to `now` or to an explicit date,
+///   e.g. `created:[2018-01-01 TO now-7d]` or `created:[2018-01-01||+1w TO *]`.
+///   Outside of a range, dates containing a `:` must be quoted,
+///   e.g. `created:"2018-01-30T10:00:00Z"`.
+///
+/// * bytes: bytes fields are searched by quoting the base64 encoded
+///   value, e.g. `hash:"AAECAw=="`.
+///
+/// * IP addresses: IP fields accept IPv4 addresses, CIDR blocks
+///   and ranges, e.g. `src_ip:10.0.0.1`, `src_ip:10.0.0.0/8` or
+///   `src_ip:[10.0.0.1 TO 10.0.0.255]`. Outside of a range,
+///   IPv6 addresses must be quoted, e.g. `src_ip:"2001:db8::/32"`.
+///
+/// * JSON objects: the values of a JSON object field are addressed
+///   by appending their path to the name of the field,
+///   e.g. `attributes.color:red` or `attributes.dims.width:[10 TO 20]`.
+///   Values that parse as numbers are searched as numbers, other values
+///   are tokenized like text.
+///
+/// * synonyms: if the tokenizer of a field emits several tokens at
+///   the same position (see `SynonymFilter`), they are searched as
+///   alternatives, e.g. `tv` matches documents containing either `tv`
+///   or `television`. Their weight can be set with `.set_synonym_boost(..)`.
+///
 pub struct QueryParser {
     schema: Schema,
     default_fields: Vec<Field>,
     conjunction_by_default: bool,
     tokenizer_manager: TokenizerManager,
+    synonym_boost: Score,
 }
 
 impl QueryParser {
@@ -95,6 +189,7 @@ impl QueryParser {
             default_fields,
             tokenizer_manager,
             conjunction_by_default: false,
+            synonym_boost: 1f32,
         }
     }
 
@@ -115,6 +210,14 @@ impl QueryParser {
         self.conjunction_by_default = true;
     }
 
+    /// Set the weight of the synonyms emitted by the tokenizer
+    /// relatively to the original token.
+    ///
+    /// By default, synonyms weigh as much as the original token.
+    pub fn set_synonym_boost(&mut self, synonym_boost: Score) {
+        self.synonym_boost = synonym_boost;
+    }
+
     /// Parse a query
     ///
     /// Note that `parse_query` returns an error if the input
@@ -137,10 +240,26 @@ impl QueryParser {
         self.compute_logical_ast(user_input_ast)
     }
 
-    fn resolve_field_name(&self, field_name: &str) -> Result<Field, QueryParserError> {
-        self.schema
-            .get_field(field_name)
-            .ok_or_else(|| QueryParserError::FieldDoesNotExist(String::from(field_name)))
+    /// Resolves a field name that may address a path within
+    /// a JSON object field, e.g. `attributes.color`.
+    ///
+    /// Fields whose name matches exactly take precedence.
+    fn resolve_field_path(
+        &self,
+        field_path: &str,
+    ) -> Result<(Field, Option<String>), QueryParserError>
+    {
+        if let Some(field) = self.schema.get_field(field_path) {
+            return Ok((field, None));
+        }
+        if let Some(dot_pos) = field_path.find('.') {
+            if let Some(field) = self.schema.get_field(&field_path[..dot_pos]) {
+                if let FieldType::JsonObject(_) = *self.schema.get_field_entry(field).field_type() {
+                    return Ok((field, Some(field_path[dot_pos + 1..].to_string())));
+                }
+            }
+        }
+        Err(QueryParserError::FieldDoesNotExist(String::from(field_path)))
     }
 
     fn compute_logical_ast(
@@ -177,9 +296,43 @@ impl QueryParser {
                 let term = Term::from_field_u64(field, val);
                 Ok(Some(LogicalLiteral::Term(term)))
             }
+            FieldType::F64(_) => {
+                let val: f64 = f64::from_str(phrase)?;
+                let term = Term::from_field_f64(field, val);
+                Ok(Some(LogicalLiteral::Term(term)))
+            }
+            FieldType::Date(ref date_options) => {
+                let date = self.parse_date(phrase)?;
+                let term = Term::from_field_date(field, &date, date_options.get_precision());
+                Ok(Some(LogicalLiteral::Term(term)))
+            }
+            FieldType::HierarchicalFacet => {
+                let facet: Facet = Facet::from_str(phrase)?;
+                let term = Term::from_facet(field, &facet);
+                Ok(Some(LogicalLiteral::Term(term)))
+            }
+            FieldType::Bytes(_) => {
+                let bytes = base64::decode(phrase)?;
+                let term = Term::from_field_bytes(field, &bytes);
+                Ok(Some(LogicalLiteral::Term(term)))
+            }
+            FieldType::Ip(_) => {
+                if phrase.contains('/') {
+                    let (first, last) = parse_cidr(phrase)
+                        .ok_or_else(|| QueryParserError::ExpectedIp(phrase.to_string()))?;
+                    Ok(Some(LogicalLiteral::Range {
+                        field,
+                        lower: Bound::Included(Term::from_field_ip(field, &first)),
+                        upper: Bound::Included(Term::from_field_ip(field, &last)),
+                    }))
+                } else {
+                    let ip = self.parse_ip(phrase)?;
+                    Ok(Some(LogicalLiteral::Term(Term::from_field_ip(field, &ip))))
+                }
+            }
             FieldType::Str(ref str_options) => {
                 if let Some(option) = str_options.get_indexing_options() {
-                    let mut tokenizer = self.tokenizer_manager
+                    let tokenizer = self.tokenizer_manager
                         .get(option.tokenizer())
                         .ok_or_else(|| {
                             QueryParserError::UnknownTokenizer(
@@ -187,21 +340,13 @@ impl QueryParser {
                                 option.tokenizer().to_string(),
                             )
                         })?;
-                    let mut terms: Vec<Term> = Vec::new();
+                    let mut terms: Vec<(usize, Term)> = Vec::new();
                     let mut token_stream = tokenizer.token_stream(phrase);
                     token_stream.process(&mut |token| {
                         let term = Term::from_field_text(field, &token.text);
-                        terms.push(term);
+                        terms.push((token.position, term));
                     });
-                    if terms.is_empty() {
-                        Ok(None)
-                    } else if terms.len() == 1 {
-                        Ok(Some(LogicalLiteral::Term(
-                            terms.into_iter().next().unwrap(),
-                        )))
-                    } else {
-                        Ok(Some(LogicalLiteral::Phrase(terms)))
-                    }
+                    self.compute_literal_for_tokens(terms)
                 } else {
                     // This should have been seen earlier really.
                     Err(QueryParserError::FieldNotIndexed(
@@ -209,9 +354,223 @@ impl QueryParser {
                     ))
                 }
             }
+            FieldType::JsonObject(_) => Err(QueryParserError::JsonPathExpected(
+                field_entry.name().to_string(),
+            )),
         }
     }
 
+    /// Values within a JSON object may be numbers or strings, so a
+    /// decimal literal matches both the number and the text, provided
+    /// the tokenizer keeps the literal intact. Otherwise, e.g. for `-12`
+    /// or `2.5` with the default tokenizer, it only matches the number.
+    fn compute_logical_ast_for_json_leaf(
+        &self,
+        field: Field,
+        path: &str,
+        phrase: &str,
+    ) -> Result<Option<LogicalAST>, QueryParserError>
+    {
+        let field_entry = self.schema.get_field_entry(field);
+        let indexing_options = match *field_entry.field_type() {
+            FieldType::JsonObject(ref json_object_options) => {
+                json_object_options.get_text_indexing_options()
+            }
+            _ => None,
+        }.ok_or_else(|| QueryParserError::FieldNotIndexed(field_entry.name().to_string()))?;
+        let tokenizer = self.tokenizer_manager
+            .get(indexing_options.tokenizer())
+            .ok_or_else(|| {
+                QueryParserError::UnknownTokenizer(
+                    field_entry.name().to_string(),
+                    indexing_options.tokenizer().to_string(),
+                )
+            })?;
+        let mut terms: Vec<(usize, Term)> = Vec::new();
+        let mut is_intact = false;
+        let mut token_stream = tokenizer.token_stream(phrase);
+        token_stream.process(&mut |token| {
+            is_intact = terms.is_empty() && token.text == phrase;
+            let term = Term::from_field_json_text(field, path, &token.text);
+            terms.push((token.position, term));
+        });
+        let number_opt = if is_decimal_literal(phrase) {
+            f64::from_str(phrase).ok()
+        } else {
+            None
+        };
+        if let Some(val) = number_opt {
+            let number_ast = LogicalAST::from(LogicalLiteral::Term(
+                Term::from_field_json_f64(field, path, val),
+            ));
+            if !is_intact {
+                return Ok(Some(number_ast));
+            }
+            let text_ast = LogicalAST::from(LogicalLiteral::Term(terms.pop().unwrap().1));
+            return Ok(Some(LogicalAST::Clause(vec![
+                (Occur::Should, number_ast),
+                (Occur::Should, text_ast),
+            ])));
+        }
+        Ok(self.compute_literal_for_tokens(terms)?.map(LogicalAST::from))
+    }
+
+    /// Builds the literal matching a tokenized text, given
+    /// its terms and their positions.
+    ///
+    /// Terms stacked at a same position, e.g. by a `SynonymFilter`, are
+    /// alternatives: the first one is the original token, the others
+    /// are weighted by the synonym boost.
+    ///
+    /// Returns an error if the alternatives of a phrase expand into
+    /// more than `MAX_PHRASE_EXPANSIONS` phrases.
+    fn compute_literal_for_tokens(
+        &self,
+        terms: Vec<(usize, Term)>,
+    ) -> Result<Option<LogicalLiteral>, QueryParserError>
+    {
+        let mut positions: Vec<(usize, Vec<Term>)> = Vec::new();
+        for (position, term) in terms {
+            let ord_opt = positions.iter().position(|&(pos, _)| pos == position);
+            match ord_opt {
+                Some(ord) => positions[ord].1.push(term),
+                None => positions.push((position, vec![term])),
+            }
+        }
+        positions.sort_by_key(|&(position, _)| position);
+        Ok(if positions.is_empty() {
+            None
+        } else if positions.len() == 1 {
+            let (_, mut alternatives) = positions.pop().unwrap();
+            if alternatives.len() == 1 {
+                Some(LogicalLiteral::Term(alternatives.pop().unwrap()))
+            } else {
+                let synonym_boost = self.synonym_boost;
+                let weighted_terms = alternatives
+                    .into_iter()
+                    .enumerate()
+                    .map(|(ord, term)| (term, if ord == 0 { 1f32 } else { synonym_boost }))
+                    .collect();
+                Some(LogicalLiteral::Synonyms(weighted_terms))
+            }
+        } else if positions.iter().all(|&(_, ref alternatives)| alternatives.len() == 1) {
+            let terms = positions
+                .into_iter()
+                .map(|(position, mut alternatives)| (position, alternatives.pop().unwrap()))
+                .collect();
+            Some(LogicalLiteral::Phrase(terms))
+        } else {
+            let num_expansions = positions.iter().fold(Some(1usize), |num_opt, &(_, ref alts)| {
+                num_opt.and_then(|num| num.checked_mul(alts.len()))
+            });
+            if num_expansions.map(|num| num > MAX_PHRASE_EXPANSIONS).unwrap_or(true) {
+                return Err(QueryParserError::TooManyPhraseExpansions);
+            }
+            Some(LogicalLiteral::MultiPhrase(positions))
+        })
+    }
+
+    fn parse_date(&self, phrase: &str) -> Result<DateTime, QueryParserError> {
+        parse_date_math(phrase, Utc::now())
+            .ok_or_else(|| QueryParserError::ExpectedDate(phrase.to_string()))
+    }
+
+    fn parse_ip(&self, phrase: &str) -> Result<Ipv6Addr, QueryParserError> {
+        parse_ip(phrase).ok_or_else(|| QueryParserError::ExpectedIp(phrase.to_string()))
+    }
+
+    fn compute_bound_term(&self, field: Field, phrase: &str) -> Result<Term, QueryParserError> {
+        let field_entry = self.schema.get_field_entry(field);
+        match *field_entry.field_type() {
+            FieldType::I64(_) => Ok(Term::from_field_i64(field, i64::from_str(phrase)?)),
+            FieldType::U64(_) => Ok(Term::from_field_u64(field, u64::from_str(phrase)?)),
+            FieldType::F64(_) => Ok(Term::from_field_f64(field, f64::from_str(phrase)?)),
+            FieldType::Date(ref date_options) => {
+                let date = self.parse_date(phrase)?;
+                Ok(Term::from_field_date(field, &date, date_options.get_precision()))
+            }
+            FieldType::Ip(_) => Ok(Term::from_field_ip(field, &self.parse_ip(phrase)?)),
+            FieldType::JsonObject(_) => Err(QueryParserError::JsonPathExpected(
+                field_entry.name().to_string(),
+            )),
+            FieldType::Str(_) | FieldType::HierarchicalFacet | FieldType::Bytes(_) => Err(
+                QueryParserError::RangeOnNonNumericalField(field_entry.name().to_string()),
+            ),
+        }
+    }
+
+    fn compute_bound(
+        &self,
+        field: Field,
+        bound: &UserInputBound,
+    ) -> Result<Bound<Term>, QueryParserError>
+    {
+        match *bound {
+            UserInputBound::Inclusive(ref phrase) => {
+                Ok(Bound::Included(self.compute_bound_term(field, phrase)?))
+            }
+            UserInputBound::Exclusive(ref phrase) => {
+                Ok(Bound::Excluded(self.compute_bound_term(field, phrase)?))
+            }
+            UserInputBound::Unbounded => Ok(Bound::Unbounded),
+        }
+    }
+
+    /// Ranges within a JSON object are always numerical.
+    ///
+    /// Unbounded sides are replaced by infinite values, so that the
+    /// range does not extend beyond the terms of the path.
+    fn compute_json_bound(
+        &self,
+        field: Field,
+        path: &str,
+        bound: &UserInputBound,
+        unbounded_val: f64,
+    ) -> Result<Bound<Term>, QueryParserError>
+    {
+        match *bound {
+            UserInputBound::Inclusive(ref phrase) => {
+                let val = f64::from_str(phrase)?;
+                Ok(Bound::Included(Term::from_field_json_f64(field, path, val)))
+            }
+            UserInputBound::Exclusive(ref phrase) => {
+                let val = f64::from_str(phrase)?;
+                Ok(Bound::Excluded(Term::from_field_json_f64(field, path, val)))
+            }
+            UserInputBound::Unbounded => Ok(Bound::Included(Term::from_field_json_f64(
+                field,
+                path,
+                unbounded_val,
+            ))),
+        }
+    }
+
+    fn compute_logical_ast_for_range(
+        &self,
+        field_name: &str,
+        lower: &UserInputBound,
+        upper: &UserInputBound,
+    ) -> Result<LogicalLiteral, QueryParserError>
+    {
+        let (field, path_opt) = self.resolve_field_path(field_name)?;
+        let field_entry = self.schema.get_field_entry(field);
+        if !field_entry.field_type().is_indexed() {
+            return Err(QueryParserError::FieldNotIndexed(field_name.to_string()));
+        }
+        if let Some(path) = path_opt {
+            return Ok(LogicalLiteral::Range {
+                field,
+                lower: self.compute_json_bound(field, &path, lower, f64::NEG_INFINITY)?,
+                upper: self.compute_json_bound(field, &path, upper, f64::INFINITY)?,
+            });
+        }
+        Ok(LogicalLiteral::Range {
+            field,
+            lower: self.compute_bound(field, lower)?,
+            upper: self.compute_bound(field, upper)?,
+        })
+    }
+
     fn default_occur(&self) -> Occur {
         if self.conjunction_by_default {
             Occur::Must
@@ -244,10 +603,27 @@ impl QueryParser {
                 let (occur, logical_sub_queries) = self.compute_logical_ast_with_occur(*subquery)?;
                 Ok((compose_occur(Occur::Must, occur), logical_sub_queries))
             }
+            UserInputAST::Range {
+                ref field,
+                ref lower,
+                ref upper,
+            } => {
+                let range_literal = self.compute_logical_ast_for_range(field, lower, upper)?;
+                Ok((Occur::Should, LogicalAST::from(range_literal)))
+            }
             UserInputAST::Leaf(literal) => {
                 let term_phrases: Vec<(Field, String)> = match literal.field_name {
                     Some(ref field_name) => {
-                        let field = self.resolve_field_name(field_name)?;
+                        let (field, path_opt) = self.resolve_field_path(field_name)?;
+                        if let Some(path) = path_opt {
+                            let ast_opt = self.compute_logical_ast_for_json_leaf(
+                                field,
+                                &path,
+                                &literal.phrase,
+                            )?;
+                            let ast = ast_opt.ok_or(QueryParserError::SyntaxError)?;
+                            return Ok((Occur::Should, ast));
+                        }
                         vec![(field, literal.phrase.clone())]
                     }
                     None => {
@@ -281,6 +657,22 @@ impl QueryParser {
     }
 }
 
+/// Returns true if `phrase` is a plain decimal number, such as `-12` or `2.5`.
+///
+/// `f64::from_str` also accepts `nan`, `inf` or `1e5`, which are
+/// more likely to be words.
+fn is_decimal_literal(phrase: &str) -> bool {
+    let unsigned = if phrase.starts_with('-') {
+        &phrase[1..]
+    } else {
+        phrase
+    };
+    let is_digits = |digits: &str| !digits.is_empty() && digits.chars().all(|c| c.is_digit(10));
+    let mut parts = unsigned.splitn(2, '.');
+    let integer_part = parts.next().unwrap_or("");
+    is_digits(integer_part) && parts.next().map(&is_digits).unwrap_or(true)
+}
+
 /// Compose two occur values.
 fn compose_occur(left: Occur, right: Occur) -> Occur {
     match left {
@@ -305,7 +697,50 @@ fn compose_occur(left: Occur, right: Occur) -> Occur {
 fn convert_literal_to_query(logical_literal: LogicalLiteral) -> Box<Query> {
     match logical_literal {
         LogicalLiteral::Term(term) => box TermQuery::new(term, IndexRecordOption::WithFreqs),
-        LogicalLiteral::Phrase(terms) => box PhraseQuery::from(terms),
+        LogicalLiteral::Phrase(terms) => box PhraseQuery::new_with_offset(terms),
+        LogicalLiteral::Synonyms(weighted_terms) => {
+            let occur_subqueries: Vec<(Occur, Box<Query>)> = weighted_terms
+                .into_iter()
+                .map(|(term, weight)| {
+                    let term_query: Box<Query> =
+                        box TermQuery::new(term, IndexRecordOption::WithFreqs);
+                    if weight == 1f32 {
+                        (Occur::Should, term_query)
+                    } else {
+                        (Occur::Should, box BoostQuery::new(term_query, weight) as Box<Query>)
+                    }
+                })
+                .collect();
+            box BooleanQuery::from(occur_subqueries)
+        }
+        LogicalLiteral::MultiPhrase(positions) => {
+            // expands the alternatives into all of the possible phrases.
+            let mut phrases: Vec<Vec<(usize, Term)>> = vec![vec![]];
+            for (position, alternatives) in positions {
+                phrases = phrases
+                    .into_iter()
+                    .flat_map(|phrase| {
+                        alternatives.iter().cloned().map(move |term| {
+                            let mut phrase = phrase.clone();
+                            phrase.push((position, term));
+                            phrase
+                        })
+                    })
+                    .collect();
+            }
+            let occur_subqueries: Vec<(Occur, Box<Query>)> = phrases
+                .into_iter()
+                .map(|phrase| {
+                    (Occur::Should, box PhraseQuery::new_with_offset(phrase) as Box<Query>)
+                })
+                .collect();
+            box BooleanQuery::from(occur_subqueries)
+        }
+        LogicalLiteral::Range {
+            field,
+            lower,
+            upper,
+        } => box RangeQuery::new_term_bounds(field, lower, upper),
     }
 }
 
@@ -327,10 +762,16 @@ mod test {
     use super::QueryParser;
     use super::QueryParserError;
     use super::super::logical_ast::*;
-    use query::Query;
+    use query::{BooleanQuery, Query};
+    use schema::{IndexRecordOption, TextFieldIndexing, TextOptions};
     use schema::{SchemaBuilder, Term, INT_INDEXED, STORED, STRING, TEXT};
+    use schema::Facet;
     use schema::Field;
-    use tokenizer::TokenizerManager;
+    use schema::{parse_date, DateOptions, DatePrecision};
+    use std::f64;
+    use std::collections::HashMap;
+    use std::net::Ipv4Addr;
+    use tokenizer::{LowerCaser, SimpleTokenizer, SynonymFilter, Tokenizer, TokenizerManager};
 
     fn make_query_parser() -> QueryParser {
         let mut schema_builder = SchemaBuilder::default();
@@ -342,6 +783,15 @@ mod test {
         schema_builder.add_text_field("notindexed_u64", STORED);
         schema_builder.add_text_field("notindexed_i64", STORED);
         schema_builder.add_text_field("nottokenized", STRING);
+        schema_builder.add_facet_field("category");
+        schema_builder.add_f64_field("float", INT_INDEXED);
+        schema_builder.add_date_field(
+            "created",
+            DateOptions::from(INT_INDEXED).set_precision(DatePrecision::Milliseconds),
+        );
+        schema_builder.add_bytes_field("hash", INT_INDEXED);
+        schema_builder.add_ip_field("src_ip", INT_INDEXED);
+        schema_builder.add_json_field("attributes", TEXT);
         let schema = schema_builder.build();
         let default_fields = vec![title, text];
         let tokenizer_manager = TokenizerManager::default();
@@ -414,6 +864,25 @@ mod test {
         );
     }
 
+    #[test]
+    pub fn test_parse_query_facet() {
+        let query_parser = make_query_parser();
+        assert!(
+            query_parser
+                .parse_query("category:\"/electronics/phones\"")
+                .is_ok()
+        );
+        assert!(query_parser.parse_query("category:\"electronics\"").is_err());
+        test_parse_query_to_logical_ast_helper(
+            "category:\"/a/b\"",
+            &format!(
+                "{:?}",
+                Term::from_facet(Field(8u32), &Facet::from_text("/a/b"))
+            ),
+            false,
+        );
+    }
+
     #[test]
     pub fn test_parse_query_ints() {
         let query_parser = make_query_parser();
@@ -452,6 +921,235 @@ mod test {
         );
     }
 
+    #[test]
+    pub fn test_parse_query_f64() {
+        let query_parser = make_query_parser();
+        assert!(query_parser.parse_query("float:2.5").is_ok());
+        assert!(query_parser.parse_query("float:-3").is_ok());
+        assert!(query_parser.parse_query("float:\"1e10\"").is_ok());
+        match query_parser.parse_query("float:\"abc\"") {
+            Err(QueryParserError::ExpectedFloat(_)) => {}
+            _ => panic!("expected a float parsing error"),
+        }
+        test_parse_query_to_logical_ast_helper(
+            "float:-2.5",
+            &format!("{:?}", Term::from_field_f64(Field(9u32), -2.5f64)),
+            false,
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_date() {
+        let query_parser = make_query_parser();
+        assert!(query_parser.parse_query("created:2018-01-30").is_ok());
+        assert!(
+            query_parser
+                .parse_query("created:\"2018-01-30T10:00:00+01:00\"")
+                .is_ok()
+        );
+        assert!(query_parser.parse_query("created:now").is_ok());
+        assert_eq!(
+            query_parser.parse_query("created:yesterday").err().unwrap(),
+            QueryParserError::ExpectedDate(String::from("yesterday"))
+        );
+        let date = parse_date("2018-01-30T09:00:00.123Z").unwrap();
+        test_parse_query_to_logical_ast_helper(
+            "created:\"2018-01-30T10:00:00.123+01:00\"",
+            &format!(
+                "{:?}",
+                Term::from_field_date(Field(10u32), &date, DatePrecision::Milliseconds)
+            ),
+            false,
+        );
+        let lower = parse_date("2018-01-01").unwrap();
+        let upper = parse_date("2018-01-15").unwrap();
+        test_parse_query_to_logical_ast_helper(
+            "created:[2018-01-01 TO 2018-01-01||+2w}",
+            &format!(
+                "[{:?} TO {:?}}}",
+                Term::from_field_date(Field(10u32), &lower, DatePrecision::Milliseconds),
+                Term::from_field_date(Field(10u32), &upper, DatePrecision::Milliseconds)
+            ),
+            false,
+        );
+        assert!(
+            query_parser
+                .parse_query("created:[2018-01-01T00:00:00Z TO now-7d]")
+                .is_ok()
+        );
+        assert!(query_parser.parse_query("created:{now-1h TO *]").is_ok());
+        assert_eq!(
+            query_parser
+                .parse_query("created:[2018-01-01 TO now-7y]")
+                .err()
+                .unwrap(),
+            QueryParserError::ExpectedDate(String::from("now-7y"))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_bytes() {
+        let query_parser = make_query_parser();
+        test_parse_query_to_logical_ast_helper(
+            "hash:\"AAECAw==\"",
+            &format!(
+                "{:?}",
+                Term::from_field_bytes(Field(11u32), &[0u8, 1u8, 2u8, 3u8])
+            ),
+            false,
+        );
+        match query_parser.parse_query("hash:\"!!\"") {
+            Err(QueryParserError::ExpectedBase64(_)) => {}
+            _ => panic!("expected a base64 parsing error"),
+        }
+        assert_eq!(
+            query_parser.parse_query("hash:[AA TO BB]").err().unwrap(),
+            QueryParserError::RangeOnNonNumericalField(String::from("hash"))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_ip() {
+        let query_parser = make_query_parser();
+        let ip_term = |a: u8, b: u8, c: u8, d: u8| {
+            Term::from_field_ip(Field(12u32), &Ipv4Addr::new(a, b, c, d).to_ipv6_mapped())
+        };
+        test_parse_query_to_logical_ast_helper(
+            "src_ip:10.0.0.1",
+            &format!("{:?}", ip_term(10, 0, 0, 1)),
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "src_ip:10.0.0.0/8",
+            &format!(
+                "[{:?} TO {:?}]",
+                ip_term(10, 0, 0, 0),
+                ip_term(10, 255, 255, 255)
+            ),
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "src_ip:{192.168.0.1 TO *]",
+            &format!("{{{:?} TO *}}", ip_term(192, 168, 0, 1)),
+            false,
+        );
+        assert!(query_parser.parse_query("src_ip:\"2001:db8::1\"").is_ok());
+        assert!(query_parser.parse_query("src_ip:\"2001:db8::/32\"").is_ok());
+        assert!(query_parser.parse_query("src_ip:[::1 TO ::ffff]").is_ok());
+        assert_eq!(
+            query_parser.parse_query("src_ip:10.0.0.0/33").err().unwrap(),
+            QueryParserError::ExpectedIp(String::from("10.0.0.0/33"))
+        );
+        assert_eq!(
+            query_parser.parse_query("src_ip:10.0.0").err().unwrap(),
+            QueryParserError::ExpectedIp(String::from("10.0.0"))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_json() {
+        let query_parser = make_query_parser();
+        let attributes = Field(13u32);
+        test_parse_query_to_logical_ast_helper(
+            "attributes.color:Red",
+            &format!("{:?}", Term::from_field_json_text(attributes, "color", "red")),
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "attributes.dims.width:2.5",
+            &format!("{:?}", Term::from_field_json_f64(attributes, "dims.width", 2.5)),
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "attributes.size:-12",
+            &format!("{:?}", Term::from_field_json_f64(attributes, "size", -12.0)),
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "attributes.size:12",
+            &format!(
+                "({:?} {:?})",
+                Term::from_field_json_f64(attributes, "size", 12.0),
+                Term::from_field_json_text(attributes, "size", "12")
+            ),
+            false,
+        );
+        for word in &["nan", "inf", "infinity", "1e5"] {
+            test_parse_query_to_logical_ast_helper(
+                &format!("attributes.color:{}", word),
+                &format!("{:?}", Term::from_field_json_text(attributes, "color", word)),
+                false,
+            );
+        }
+        test_parse_query_to_logical_ast_helper(
+            "attributes.size:[10 TO 20]",
+            &format!(
+                "[{:?} TO {:?}]",
+                Term::from_field_json_f64(attributes, "size", 10.0),
+                Term::from_field_json_f64(attributes, "size", 20.0)
+            ),
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "attributes.size:{10 TO *]",
+            &format!(
+                "{{{:?} TO {:?}]",
+                Term::from_field_json_f64(attributes, "size", 10.0),
+                Term::from_field_json_f64(attributes, "size", f64::INFINITY)
+            ),
+            false,
+        );
+        assert_eq!(
+            query_parser.parse_query("attributes:red").err().unwrap(),
+            QueryParserError::JsonPathExpected(String::from("attributes"))
+        );
+        assert_eq!(
+            query_parser.parse_query("title.color:red").err().unwrap(),
+            QueryParserError::FieldDoesNotExist(String::from("title.color"))
+        );
+        assert!(query_parser.parse_query("attributes.size:[red TO *]").is_err());
+    }
+
+    #[test]
+    pub fn test_parse_query_range() {
+        let query_parser = make_query_parser();
+        test_parse_query_to_logical_ast_helper(
+            "unsigned:[2 TO 10}",
+            &format!(
+                "[{:?} TO {:?}}}",
+                Term::from_field_u64(Field(3u32), 2u64),
+                Term::from_field_u64(Field(3u32), 10u64)
+            ),
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "float:{* TO -1.5]",
+            &format!("{{* TO {:?}]", Term::from_field_f64(Field(9u32), -1.5f64)),
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "signed:[-3 TO *] +title:toto",
+            &format!(
+                "([{:?} TO *}} +Term([0, 0, 0, 0, 116, 111, 116, 111]))",
+                Term::from_field_i64(Field(2u32), -3i64)
+            ),
+            false,
+        );
+        assert!(query_parser.parse_query("float:[1.5 TO 2.5]").is_ok());
+        assert!(query_parser.parse_query("unsigned:[-2 TO 3]").is_err());
+        assert_eq!(
+            query_parser.parse_query("title:[a TO b]").err().unwrap(),
+            QueryParserError::RangeOnNonNumericalField(String::from("title"))
+        );
+        assert_eq!(
+            query_parser
+                .parse_query("notindexed_u64:[1 TO 2]")
+                .err()
+                .unwrap(),
+            QueryParserError::FieldNotIndexed(String::from("notindexed_u64"))
+        );
+    }
+
     #[test]
     pub fn test_parse_query_to_ast_disjunction() {
         test_parse_query_to_logical_ast_helper(
@@ -523,4 +1221,60 @@ mod test {
             true,
         );
     }
+
+    #[test]
+    pub fn test_parse_query_synonyms() {
+        let mut schema_builder = SchemaBuilder::default();
+        let text_field_indexing = TextFieldIndexing::default()
+            .set_tokenizer("en_synonyms")
+            .set_index_option(IndexRecordOption::WithFreqsAndPositions);
+        let text_options = TextOptions::default().set_indexing_options(text_field_indexing);
+        let text = schema_builder.add_text_field("text", text_options);
+        let schema = schema_builder.build();
+        let tokenizer_manager = TokenizerManager::default();
+        let mut synonyms = HashMap::new();
+        synonyms.insert("tv".to_string(), vec!["television".to_string()]);
+        synonyms.insert(
+            "many".to_string(),
+            (0..100).map(|ord| format!("many{}", ord)).collect(),
+        );
+        tokenizer_manager.register(
+            "en_synonyms",
+            SimpleTokenizer
+                .filter(LowerCaser)
+                .filter(SynonymFilter::new(synonyms).unwrap()),
+        );
+        let mut query_parser = QueryParser::new(schema, vec![text], tokenizer_manager);
+        query_parser.set_synonym_boost(0.5f32);
+        let tv_term = "Term([0, 0, 0, 0, 116, 118])";
+        let television_term =
+            "Term([0, 0, 0, 0, 116, 101, 108, 101, 118, 105, 115, 105, 111, 110])";
+        let logical_ast = query_parser.parse_query_to_logical_ast("TV").unwrap();
+        assert_eq!(
+            format!("{:?}", logical_ast),
+            format!("Synonyms([{}, {}])", tv_term, television_term)
+        );
+        let logical_ast = query_parser.parse_query_to_logical_ast("\"big tv\"").unwrap();
+        assert_eq!(
+            format!("{:?}", logical_ast),
+            format!(
+                "\"[[Term([0, 0, 0, 0, 98, 105, 103])], [{}, {}]]\"",
+                tv_term, television_term
+            )
+        );
+        let query = query_parser.parse_query("tv").unwrap();
+        assert!(query.as_any().downcast_ref::<BooleanQuery>().is_some());
+        // 2^6 phrases.
+        assert!(query_parser.parse_query("\"tv tv tv tv tv tv\"").is_ok());
+        assert_eq!(
+            query_parser.parse_query("\"tv tv tv tv tv tv tv\"").err().unwrap(),
+            QueryParserError::TooManyPhraseExpansions
+        );
+        // a single word is not expanded into phrases.
+        assert!(query_parser.parse_query("many").is_ok());
+        assert_eq!(
+            query_parser.parse_query("\"many tv\"").err().unwrap(),
+            QueryParserError::TooManyPhraseExpansions
+        );
+    }
 }
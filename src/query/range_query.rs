@@ -0,0 +1,278 @@
+use Result;
+use Searcher;
+use core::SegmentReader;
+use query::Query;
+use query::Scorer;
+use query::Weight;
+use query::boolean_query::BitSetUnionScorer;
+use schema::{Field, IndexRecordOption, Term};
+use std::any::Any;
+use std::collections::Bound;
+use std::ops::Range;
+use termdict::{TermDictionary, TermStreamer, TermStreamerBuilder};
+
+fn map_bound<TFrom, Transform: Fn(TFrom) -> Vec<u8>>(
+    bound: Bound<TFrom>,
+    transform: &Transform,
+) -> Bound<Vec<u8>>
+{
+    match bound {
+        Bound::Included(from_val) => Bound::Included(transform(from_val)),
+        Bound::Excluded(from_val) => Bound::Excluded(transform(from_val)),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+/// `RangeQuery` matches all of the documents having a value
+/// within a given range, for a `u64`, `i64` or `f64` field.
+///
+/// The field needs to be indexed. As the terms of these fields
+/// are encoded in an order-preserving manner, the query simply
+/// streams the terms of the range in the term dictionary.
+///
+/// All of the matching documents get a score of `1`.
+///
+/// ```rust,ignore
+/// // matches the documents with 10 <= price < 20
+/// let query = RangeQuery::new_f64(price, 10f64..20f64);
+/// searcher.search(&query, &mut count_collector)?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct RangeQuery {
+    field: Field,
+    left_bound: Bound<Vec<u8>>,
+    right_bound: Bound<Vec<u8>>,
+}
+
+impl RangeQuery {
+    /// Creates a new `RangeQuery` over a `u64` field,
+    /// matching the values within the half-open `range`.
+    pub fn new_u64(field: Field, range: Range<u64>) -> RangeQuery {
+        RangeQuery::new_u64_bounds(
+            field,
+            Bound::Included(range.start),
+            Bound::Excluded(range.end),
+        )
+    }
+
+    /// Creates a new `RangeQuery` over a `u64` field,
+    /// given explicit bounds.
+    pub fn new_u64_bounds(field: Field, left: Bound<u64>, right: Bound<u64>) -> RangeQuery {
+        let make_term_val = |val: u64| Term::from_field_u64(field, val).as_slice().to_owned();
+        RangeQuery {
+            field,
+            left_bound: map_bound(left, &make_term_val),
+            right_bound: map_bound(right, &make_term_val),
+        }
+    }
+
+    /// Creates a new `RangeQuery` over a `i64` field,
+    /// matching the values within the half-open `range`.
+    pub fn new_i64(field: Field, range: Range<i64>) -> RangeQuery {
+        RangeQuery::new_i64_bounds(
+            field,
+            Bound::Included(range.start),
+            Bound::Excluded(range.end),
+        )
+    }
+
+    /// Creates a new `RangeQuery` over a `i64` field,
+    /// given explicit bounds.
+    pub fn new_i64_bounds(field: Field, left: Bound<i64>, right: Bound<i64>) -> RangeQuery {
+        let make_term_val = |val: i64| Term::from_field_i64(field, val).as_slice().to_owned();
+        RangeQuery {
+            field,
+            left_bound: map_bound(left, &make_term_val),
+            right_bound: map_bound(right, &make_term_val),
+        }
+    }
+
+    /// Creates a new `RangeQuery` over a `f64` field,
+    /// matching the values within the half-open `range`.
+    pub fn new_f64(field: Field, range: Range<f64>) -> RangeQuery {
+        RangeQuery::new_f64_bounds(
+            field,
+            Bound::Included(range.start),
+            Bound::Excluded(range.end),
+        )
+    }
+
+    /// Creates a new `RangeQuery` over a `f64` field,
+    /// given explicit bounds.
+    pub fn new_f64_bounds(field: Field, left: Bound<f64>, right: Bound<f64>) -> RangeQuery {
+        let make_term_val = |val: f64| Term::from_field_f64(field, val).as_slice().to_owned();
+        RangeQuery {
+            field,
+            left_bound: map_bound(left, &make_term_val),
+            right_bound: map_bound(right, &make_term_val),
+        }
+    }
+
+    /// Creates a new `RangeQuery` given the bounds expressed as terms.
+    ///
+    /// The terms are expected to belong to `field`.
+    pub fn new_term_bounds(field: Field, left: Bound<Term>, right: Bound<Term>) -> RangeQuery {
+        let term_to_bytes = |term: Term| term.as_slice().to_owned();
+        RangeQuery {
+            field,
+            left_bound: map_bound(left, &term_to_bytes),
+            right_bound: map_bound(right, &term_to_bytes),
+        }
+    }
+
+    /// Returns the field of the range query.
+    pub fn field(&self) -> Field {
+        self.field
+    }
+}
+
+impl Query for RangeQuery {
+    fn as_any(&self) -> &Any {
+        self
+    }
+
+    fn weight(&self, _searcher: &Searcher) -> Result<Box<Weight>> {
+        Ok(box RangeWeight {
+            field: self.field,
+            left_bound: self.left_bound.clone(),
+            right_bound: self.right_bound.clone(),
+        })
+    }
+}
+
+struct RangeWeight {
+    field: Field,
+    left_bound: Bound<Vec<u8>>,
+    right_bound: Bound<Vec<u8>>,
+}
+
+impl Weight for RangeWeight {
+    fn scorer<'a>(&'a self, reader: &'a SegmentReader) -> Result<Box<Scorer + 'a>> {
+        let inverted_index = reader.inverted_index(self.field);
+        let term_dict = inverted_index.terms();
+        let mut term_range = term_dict.range();
+        term_range = match self.left_bound {
+            Bound::Included(ref term_val) => term_range.ge(term_val),
+            Bound::Excluded(ref term_val) => term_range.gt(term_val),
+            Bound::Unbounded => term_range,
+        };
+        term_range = match self.right_bound {
+            Bound::Included(ref term_val) => term_range.le(term_val),
+            Bound::Excluded(ref term_val) => term_range.lt(term_val),
+            Bound::Unbounded => term_range,
+        };
+        let mut term_stream = term_range.into_stream();
+        let mut segment_postings = Vec::new();
+        while term_stream.advance() {
+            let term_info = term_stream.value();
+            segment_postings.push(
+                inverted_index.read_postings_from_terminfo(term_info, IndexRecordOption::Basic),
+            );
+        }
+        Ok(box BitSetUnionScorer::new(segment_postings, reader.max_doc()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::RangeQuery;
+    use Index;
+    use collector::CountCollector;
+    use query::Query;
+    use schema::{Document, SchemaBuilder, INT_INDEXED};
+    use std::collections::Bound;
+
+    #[test]
+    fn test_range_query_simple() {
+        let mut schema_builder = SchemaBuilder::default();
+        let year_field = schema_builder.add_u64_field("year", INT_INDEXED);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            for year in 1950u64..2017u64 {
+                let num_docs_within_year = 10 + (year - 1950) * (year - 1950);
+                for _ in 0..num_docs_within_year {
+                    index_writer.add_document(doc!(year_field => year));
+                }
+            }
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let docs_in_the_sixties = RangeQuery::new_u64(year_field, 1960u64..1970u64);
+        let mut count_collector = CountCollector::default();
+        docs_in_the_sixties
+            .search(&*searcher, &mut count_collector)
+            .unwrap();
+        assert_eq!(count_collector.count(), 2285);
+    }
+
+    #[test]
+    fn test_range_query_bounds() {
+        let mut schema_builder = SchemaBuilder::default();
+        let int_field = schema_builder.add_i64_field("intfield", INT_INDEXED);
+        let float_field = schema_builder.add_f64_field("floatfield", INT_INDEXED);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            for i in -5i64..5i64 {
+                let mut doc = Document::default();
+                doc.add_i64(int_field, i * 10);
+                doc.add_f64(float_field, i as f64 * 0.5f64);
+                index_writer.add_document(doc);
+            }
+            // a document with two values within the range is counted once.
+            let mut doc = Document::default();
+            doc.add_f64(float_field, 0.1f64);
+            doc.add_f64(float_field, 0.2f64);
+            index_writer.add_document(doc);
+            index_writer.commit().unwrap();
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let count = |query: &Query| {
+            let mut count_collector = CountCollector::default();
+            query.search(&*searcher, &mut count_collector).unwrap();
+            count_collector.count()
+        };
+        // -50, -40, ..., 40
+        assert_eq!(count(&RangeQuery::new_i64(int_field, -20..20)), 4);
+        assert_eq!(
+            count(&RangeQuery::new_i64_bounds(
+                int_field,
+                Bound::Excluded(-20),
+                Bound::Included(20)
+            )),
+            4
+        );
+        assert_eq!(
+            count(&RangeQuery::new_i64_bounds(
+                int_field,
+                Bound::Unbounded,
+                Bound::Excluded(0)
+            )),
+            5
+        );
+        // -2.5, -2.0, ..., 2.0, and 0.1, 0.2
+        assert_eq!(count(&RangeQuery::new_f64(float_field, -1f64..0.5f64)), 4);
+        assert_eq!(
+            count(&RangeQuery::new_f64_bounds(
+                float_field,
+                Bound::Excluded(0f64),
+                Bound::Unbounded
+            )),
+            5
+        );
+        assert_eq!(
+            count(&RangeQuery::new_f64_bounds(
+                float_field,
+                Bound::Unbounded,
+                Bound::Included(-2.5f64)
+            )),
+            1
+        );
+    }
+}
@@ -15,9 +15,18 @@ pub trait Scorer: DocSet {
 
     /// Consumes the complete `DocSet` and
     /// push the scored documents to the collector.
+    ///
+    /// If the collector does not require scoring, scores are not
+    /// computed and documents are pushed with a score of `1`.
     fn collect(&mut self, collector: &mut Collector) {
-        while self.advance() {
-            collector.collect(self.doc(), self.score());
+        if collector.requires_scoring() {
+            while self.advance() {
+                collector.collect(self.doc(), self.score());
+            }
+        } else {
+            while self.advance() {
+                collector.collect(self.doc(), 1f32);
+            }
         }
     }
 }
@@ -29,9 +38,7 @@ impl<'a> Scorer for Box<Scorer + 'a> {
 
     fn collect(&mut self, collector: &mut Collector) {
         let scorer = self.deref_mut();
-        while scorer.advance() {
-            collector.collect(scorer.doc(), scorer.score());
-        }
+        scorer.collect(collector);
     }
 }
 
@@ -10,4 +10,13 @@ pub trait Weight {
     /// Returns the scorer for the given segment.
     /// See [`Query`](./trait.Query.html).
     fn scorer<'a>(&'a self, reader: &'a SegmentReader) -> Result<Box<Scorer + 'a>>;
+
+    /// Returns a scorer for the given segment, for collectors
+    /// that do not require scoring.
+    ///
+    /// The scores of the returned scorer are meaningless, which makes it possible
+    /// to use cheaper strategies. By default, it is simply `.scorer(reader)`.
+    fn scorer_without_scores<'a>(&'a self, reader: &'a SegmentReader) -> Result<Box<Scorer + 'a>> {
+        self.scorer(reader)
+    }
 }
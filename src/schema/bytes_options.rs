@@ -0,0 +1,91 @@
+use schema::IntOptions;
+
+/// Define how a bytes field should be handled by tantivy.
+///
+/// - indexed: the bytes are indexed as a single raw term,
+/// which makes it possible to search for exact keys (e.g. hashes).
+/// - fast: the bytes are stored in a variable-length column,
+/// that can be read via a `BytesFastFieldReader`.
+/// - stored: the bytes are stored in the doc store.
+///
+/// `IntOptions` can be converted into `BytesOptions`, so that the
+/// int shortcuts can be used :
+///
+/// ```rust,ignore
+/// let thumbnail = schema_builder.add_bytes_field("thumbnail", FAST | INT_STORED);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BytesOptions {
+    indexed: bool,
+    fast: bool,
+    stored: bool,
+}
+
+impl BytesOptions {
+    /// Returns true iff the value is stored.
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+
+    /// Returns true iff the value is indexed.
+    pub fn is_indexed(&self) -> bool {
+        self.indexed
+    }
+
+    /// Returns true if the value is a fast field.
+    pub fn is_fast(&self) -> bool {
+        self.fast
+    }
+
+    /// Set the bytes options as stored.
+    pub fn set_stored(mut self) -> BytesOptions {
+        self.stored = true;
+        self
+    }
+
+    /// Set the bytes options as indexed.
+    pub fn set_indexed(mut self) -> BytesOptions {
+        self.indexed = true;
+        self
+    }
+
+    /// Set the bytes options as a fast field.
+    ///
+    /// Only the first value of each document is
+    /// recorded in the fast field.
+    pub fn set_fast(mut self) -> BytesOptions {
+        self.fast = true;
+        self
+    }
+}
+
+impl From<IntOptions> for BytesOptions {
+    fn from(int_options: IntOptions) -> BytesOptions {
+        BytesOptions {
+            indexed: int_options.is_indexed(),
+            fast: int_options.is_fast(),
+            stored: int_options.is_stored(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::BytesOptions;
+    use schema::{FAST, INT_STORED};
+    use serde_json;
+
+    #[test]
+    fn test_bytes_options() {
+        let bytes_options = BytesOptions::from(FAST | INT_STORED);
+        assert!(bytes_options.is_fast());
+        assert!(bytes_options.is_stored());
+        assert!(!bytes_options.is_indexed());
+        let bytes_options = bytes_options.set_indexed();
+        let json = serde_json::to_string(&bytes_options).unwrap();
+        assert_eq!(json, r#"{"indexed":true,"fast":true,"stored":true}"#);
+        let bytes_options_deser: BytesOptions = serde_json::from_str(&json).unwrap();
+        assert_eq!(bytes_options_deser, bytes_options);
+    }
+}
@@ -0,0 +1,256 @@
+use DateTime;
+use chrono::{NaiveDate, TimeZone, Utc};
+use schema::{Cardinality, IntOptions};
+
+/// Precision with which the dates of a date field are
+/// indexed and stored in fast fields.
+///
+/// Dates are represented as `i64` timestamps, expressed in
+/// the unit of the precision. Finer parts of the dates are truncated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DatePrecision {
+    /// Seconds since the UNIX epoch.
+    #[serde(rename = "seconds")]
+    Seconds,
+    /// Milliseconds since the UNIX epoch.
+    #[serde(rename = "milliseconds")]
+    Milliseconds,
+    /// Microseconds since the UNIX epoch.
+    #[serde(rename = "microseconds")]
+    Microseconds,
+}
+
+impl DatePrecision {
+    fn is_seconds(&self) -> bool {
+        *self == DatePrecision::Seconds
+    }
+
+    fn units_per_second(self) -> i64 {
+        match self {
+            DatePrecision::Seconds => 1,
+            DatePrecision::Milliseconds => 1_000,
+            DatePrecision::Microseconds => 1_000_000,
+        }
+    }
+
+    /// Returns the timestamp of the date, truncated to the precision.
+    pub fn date_to_timestamp(self, date: &DateTime) -> i64 {
+        let units_per_second = self.units_per_second();
+        let nanos_per_unit = 1_000_000_000 / units_per_second;
+        date.timestamp() * units_per_second
+            + i64::from(date.timestamp_subsec_nanos()) / nanos_per_unit
+    }
+
+    /// Returns the date associated to a timestamp
+    /// expressed in the unit of the precision.
+    ///
+    /// Returns `None` if the timestamp is out of the range of `DateTime`.
+    pub fn timestamp_to_date(self, timestamp: i64) -> Option<DateTime> {
+        let units_per_second = self.units_per_second();
+        let mut secs = timestamp / units_per_second;
+        let mut units = timestamp % units_per_second;
+        if units < 0 {
+            secs -= 1;
+            units += units_per_second;
+        }
+        let nanos = units * (1_000_000_000 / units_per_second);
+        Utc.timestamp_opt(secs, nanos as u32).single()
+    }
+}
+
+impl Default for DatePrecision {
+    fn default() -> DatePrecision {
+        DatePrecision::Seconds
+    }
+}
+
+/// Parses a date, either expressed in the RFC 3339 format
+/// (e.g. `2018-01-30T10:00:00+01:00`) or as a simple day
+/// (e.g. `2018-01-30`, taken at midnight UTC).
+pub fn parse_date(date_str: &str) -> Option<DateTime> {
+    if let Ok(date) = ::chrono::DateTime::parse_from_rfc3339(date_str) {
+        return Some(date.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+        .ok()
+        .and_then(|day| day.and_hms_opt(0, 0, 0))
+        .map(|datetime| Utc.from_utc_datetime(&datetime))
+}
+
+/// Define how a date field should be handled by tantivy.
+///
+/// The options are the same as the one of the int fields
+/// (see `IntOptions`), with the addition of the `DatePrecision`.
+///
+/// `IntOptions` can be converted into `DateOptions`, so that the
+/// int shortcuts can be used :
+///
+/// ```rust,ignore
+/// let created = schema_builder.add_date_field("created", INT_INDEXED | FAST);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateOptions {
+    indexed: bool,
+    fast: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Cardinality::is_single_value")]
+    cardinality: Cardinality,
+    stored: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "DatePrecision::is_seconds")]
+    precision: DatePrecision,
+}
+
+impl DateOptions {
+    /// Returns true iff the value is stored.
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+
+    /// Returns true iff the value is indexed.
+    pub fn is_indexed(&self) -> bool {
+        self.indexed
+    }
+
+    /// Returns true if the value is a fast field.
+    pub fn is_fast(&self) -> bool {
+        self.fast
+    }
+
+    /// Returns the cardinality of the fastfield.
+    ///
+    /// If the field has not been declared as a fastfield, then
+    /// the method returns None.
+    pub fn get_fastfield_cardinality(&self) -> Option<Cardinality> {
+        if self.fast {
+            Some(self.cardinality)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the precision of the dates.
+    pub fn get_precision(&self) -> DatePrecision {
+        self.precision
+    }
+
+    /// Set the date options as stored.
+    pub fn set_stored(mut self) -> DateOptions {
+        self.stored = true;
+        self
+    }
+
+    /// Set the date options as indexed.
+    pub fn set_indexed(mut self) -> DateOptions {
+        self.indexed = true;
+        self
+    }
+
+    /// Set the date options as a fast field.
+    ///
+    /// The fast field holds the `i64` timestamps of the dates,
+    /// and can be read via an `I64FastFieldReader`.
+    pub fn set_fast(mut self) -> DateOptions {
+        self.fast = true;
+        self
+    }
+
+    /// Set the date options as a multi-valued fast field.
+    pub fn set_multivalued_fast(mut self) -> DateOptions {
+        self.fast = true;
+        self.cardinality = Cardinality::MultiValues;
+        self
+    }
+
+    /// Sets the precision of the dates.
+    ///
+    /// Defaults to `DatePrecision::Seconds`.
+    pub fn set_precision(mut self, precision: DatePrecision) -> DateOptions {
+        self.precision = precision;
+        self
+    }
+}
+
+impl Default for DateOptions {
+    fn default() -> DateOptions {
+        DateOptions::from(IntOptions::default())
+    }
+}
+
+impl From<IntOptions> for DateOptions {
+    fn from(int_options: IntOptions) -> DateOptions {
+        DateOptions {
+            indexed: int_options.is_indexed(),
+            fast: int_options.is_fast(),
+            cardinality: int_options
+                .get_fastfield_cardinality()
+                .unwrap_or(Cardinality::SingleValue),
+            stored: int_options.is_stored(),
+            precision: DatePrecision::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{parse_date, DateOptions, DatePrecision};
+    use schema::{Cardinality, FAST, INT_INDEXED};
+    use serde_json;
+
+    #[test]
+    fn test_date_precision() {
+        let date = parse_date("2018-01-30T10:00:01.123456789Z").unwrap();
+        assert_eq!(DatePrecision::Seconds.date_to_timestamp(&date), 1_517_306_401);
+        assert_eq!(
+            DatePrecision::Milliseconds.date_to_timestamp(&date),
+            1_517_306_401_123
+        );
+        assert_eq!(
+            DatePrecision::Microseconds.date_to_timestamp(&date),
+            1_517_306_401_123_456
+        );
+        let truncated = DatePrecision::Milliseconds.timestamp_to_date(1_517_306_401_123);
+        assert_eq!(truncated, parse_date("2018-01-30T10:00:01.123Z"));
+        // dates before the epoch
+        let date = parse_date("1969-12-31T23:59:59.5Z").unwrap();
+        assert_eq!(DatePrecision::Seconds.date_to_timestamp(&date), -1);
+        assert_eq!(DatePrecision::Milliseconds.date_to_timestamp(&date), -500);
+        assert_eq!(DatePrecision::Milliseconds.timestamp_to_date(-500), Some(date));
+        // dates out of range
+        assert_eq!(DatePrecision::Seconds.timestamp_to_date(i64::max_value()), None);
+        assert_eq!(DatePrecision::Seconds.timestamp_to_date(i64::min_value()), None);
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(
+            parse_date("2018-01-30"),
+            parse_date("2018-01-30T00:00:00Z")
+        );
+        assert_eq!(
+            parse_date("2018-01-30T10:00:00+01:00"),
+            parse_date("2018-01-30T09:00:00Z")
+        );
+        assert!(parse_date("30/01/2018").is_none());
+    }
+
+    #[test]
+    fn test_date_options() {
+        let date_options = DateOptions::from(INT_INDEXED | FAST);
+        assert!(date_options.is_indexed());
+        assert_eq!(
+            date_options.get_fastfield_cardinality(),
+            Some(Cardinality::SingleValue)
+        );
+        assert_eq!(date_options.get_precision(), DatePrecision::Seconds);
+        let date_options = date_options.set_precision(DatePrecision::Milliseconds);
+        let json = serde_json::to_string(&date_options).unwrap();
+        assert_eq!(
+            json,
+            r#"{"indexed":true,"fast":true,"stored":false,"precision":"milliseconds"}"#
+        );
+        let date_options_deser: DateOptions = serde_json::from_str(&json).unwrap();
+        assert_eq!(date_options_deser, date_options);
+    }
+}
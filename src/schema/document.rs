@@ -1,5 +1,7 @@
 use super::*;
+use DateTime;
 use itertools::Itertools;
+use std::net::IpAddr;
 
 /// Tantivy's Document is the object that can
 /// be indexed and then searched for.
@@ -61,11 +63,50 @@ impl Document {
         self.add(FieldValue::new(field, Value::I64(value)));
     }
 
+    /// Add a f64 field
+    pub fn add_f64(&mut self, field: Field, value: f64) {
+        self.add(FieldValue::new(field, Value::F64(value)));
+    }
+
+    /// Add a date field
+    pub fn add_date(&mut self, field: Field, value: DateTime) {
+        self.add(FieldValue::new(field, Value::Date(value)));
+    }
+
+    /// Add a facet field
+    pub fn add_facet(&mut self, field: Field, facet: Facet) {
+        self.add(FieldValue::new(field, Value::Facet(facet)));
+    }
+
+    /// Add a bytes field
+    pub fn add_bytes(&mut self, field: Field, value: Vec<u8>) {
+        self.add(FieldValue::new(field, Value::Bytes(value)));
+    }
+
+    /// Add an IP field
+    ///
+    /// IPv4 addresses are mapped into IPv6.
+    pub fn add_ip(&mut self, field: Field, value: IpAddr) {
+        self.add(FieldValue::new(field, Value::Ip(ip_to_ipv6(value))));
+    }
+
+    /// Add a JSON object field
+    pub fn add_json_object(&mut self, field: Field, json_object: JsonObject) {
+        self.add(FieldValue::new(field, Value::JsonObject(json_object)));
+    }
+
     /// Add a field value
     pub fn add(&mut self, field_value: FieldValue) {
         self.field_values.push(field_value);
     }
 
+    /// Retains only the field values whose field
+    /// matches the predicate.
+    pub fn filter_fields<P: Fn(Field) -> bool>(&mut self, predicate: P) {
+        self.field_values
+            .retain(|field_value| predicate(field_value.field()));
+    }
+
     /// field_values accessor
     pub fn field_values(&self) -> &[FieldValue] {
         &self.field_values
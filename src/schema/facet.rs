@@ -0,0 +1,335 @@
+use common::BinarySerializable;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::Error as SerdeError;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::io::{self, Read, Write};
+use std::str::FromStr;
+
+/// Separator used between the levels of a facet
+/// in its encoded representation.
+pub const FACET_SEP_BYTE: u8 = 0u8;
+
+const FACET_SEP_CHAR: char = '\u{0}';
+const SLASH_CHAR: char = '/';
+const ESCAPE_CHAR: char = '\\';
+
+/// Error returned when parsing a facet path fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FacetParseError {
+    /// Facet paths must start with a `/`.
+    MissingLeadingSlash(String),
+    /// Facet paths may not contain empty levels, as in `/a//b` or `/a/`.
+    EmptyLevel(String),
+}
+
+impl Display for FacetParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            FacetParseError::MissingLeadingSlash(ref path) => {
+                write!(f, "Facet path {:?} does not start with '/'", path)
+            }
+            FacetParseError::EmptyLevel(ref path) => {
+                write!(f, "Facet path {:?} contains an empty level", path)
+            }
+        }
+    }
+}
+
+/// A `Facet` represents a point in a given hierarchy.
+///
+/// They are typically represented similarly to a filepath.
+/// For instance, an e-commerce website could
+/// have a `Facet` for `/electronics/tv_and_video/led_tv`.
+///
+/// A document can be associated to any number of facets.
+/// The hierarchy implicitely imply that a document
+/// belonging to a facet also belongs to the ancestor of
+/// its facet. In the example above, `/electronics/tv_and_video/`
+/// and `/electronics`.
+///
+/// Slashes within a level can be escaped with a backslash,
+/// as in `/music/ac\/dc`.
+///
+/// Internally, the levels are separated by a `\u{0}` character.
+/// The root facet is encoded as the empty string.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Facet(String);
+
+impl Facet {
+    /// Returns the root facet `/`.
+    pub fn root() -> Facet {
+        Facet(String::new())
+    }
+
+    /// Returns true iff the facet is the root facet `/`.
+    pub fn is_root(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the encoded representation of the facet.
+    ///
+    /// Levels are separated by `\u{0}`.
+    pub fn encoded_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Creates a `Facet` from its encoded representation.
+    pub(crate) fn from_encoded(encoded: String) -> Facet {
+        Facet(encoded)
+    }
+
+    /// Parses a facet from its path representation.
+    /// (e.g. `/electronics/tv_and_video`)
+    ///
+    /// # Panics
+    ///
+    /// If the path is not a valid facet path.
+    /// Use `str::parse` to handle the error instead.
+    pub fn from_text(path: &str) -> Facet {
+        path.parse::<Facet>().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates a `Facet` from its list of levels.
+    ///
+    /// `Facet::from_path(vec!["electronics", "tv_and_video"])`
+    /// is equivalent to `Facet::from_text("/electronics/tv_and_video")`.
+    ///
+    /// # Panics
+    ///
+    /// If a level is empty or contains a `\u{0}` character,
+    /// as the facet would then be ambiguous.
+    pub fn from_path<Path>(path: Path) -> Facet
+    where
+        Path: IntoIterator,
+        Path::Item: ToString,
+    {
+        let levels: Vec<String> = path.into_iter().map(|level| level.to_string()).collect();
+        for level in &levels {
+            assert!(
+                !level.is_empty() && !level.contains(FACET_SEP_CHAR),
+                "Invalid facet level {:?}",
+                level
+            );
+        }
+        Facet(levels.join("\u{0}"))
+    }
+
+    /// Returns the list of levels of the facet.
+    ///
+    /// The root facet has no level.
+    pub fn to_path(&self) -> Vec<&str> {
+        if self.is_root() {
+            Vec::new()
+        } else {
+            self.0.split(FACET_SEP_CHAR).collect()
+        }
+    }
+
+    /// Returns the number of levels of the facet.
+    pub fn depth(&self) -> usize {
+        self.to_path().len()
+    }
+
+    /// Returns the parent of the facet, or `None` for the root facet.
+    pub fn parent(&self) -> Option<Facet> {
+        if self.is_root() {
+            None
+        } else {
+            let parent_len = self.0.rfind(FACET_SEP_CHAR).unwrap_or(0);
+            Some(Facet(self.0[..parent_len].to_string()))
+        }
+    }
+
+    /// Returns all of the non-root ancestors of the facet,
+    /// including the facet itself, from the shallowest to the deepest.
+    ///
+    /// `/a/b/c` returns `[/a, /a/b, /a/b/c]`.
+    pub fn prefixes(&self) -> Vec<Facet> {
+        if self.is_root() {
+            return Vec::new();
+        }
+        let mut prefixes: Vec<Facet> = self.0
+            .match_indices(FACET_SEP_CHAR)
+            .map(|(pos, _)| Facet(self.0[..pos].to_string()))
+            .collect();
+        prefixes.push(self.clone());
+        prefixes
+    }
+
+    /// Returns true iff `other` is a strict descendant of `self`.
+    pub fn is_prefix_of(&self, other: &Facet) -> bool {
+        let self_bytes = self.0.as_bytes();
+        let other_bytes = other.0.as_bytes();
+        if other_bytes.len() <= self_bytes.len() || !other_bytes.starts_with(self_bytes) {
+            return false;
+        }
+        self.is_root() || other_bytes[self_bytes.len()] == FACET_SEP_BYTE
+    }
+
+    /// Returns true iff `other` is a direct child of `self`.
+    pub fn is_parent_of(&self, other: &Facet) -> bool {
+        other.parent().as_ref() == Some(self)
+    }
+}
+
+impl FromStr for Facet {
+    type Err = FacetParseError;
+
+    fn from_str(path: &str) -> Result<Facet, FacetParseError> {
+        if !path.starts_with(SLASH_CHAR) {
+            return Err(FacetParseError::MissingLeadingSlash(path.to_string()));
+        }
+        if path.len() == 1 {
+            return Ok(Facet::root());
+        }
+        let mut levels: Vec<String> = vec![String::new()];
+        let mut escaped = false;
+        for c in path[1..].chars() {
+            if escaped {
+                levels.last_mut().unwrap().push(c);
+                escaped = false;
+            } else if c == ESCAPE_CHAR {
+                escaped = true;
+            } else if c == SLASH_CHAR {
+                levels.push(String::new());
+            } else if c != FACET_SEP_CHAR {
+                levels.last_mut().unwrap().push(c);
+            }
+        }
+        if levels.iter().any(|level| level.is_empty()) {
+            return Err(FacetParseError::EmptyLevel(path.to_string()));
+        }
+        Ok(Facet::from_path(levels))
+    }
+}
+
+impl<'a> From<&'a str> for Facet {
+    fn from(path: &'a str) -> Facet {
+        Facet::from_text(path)
+    }
+}
+
+impl BinarySerializable for Facet {
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        BinarySerializable::serialize(&self.0, writer)
+    }
+
+    fn deserialize<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let encoded = <String as BinarySerializable>::deserialize(reader)?;
+        Ok(Facet(encoded))
+    }
+}
+
+impl Display for Facet {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.is_root() {
+            return write!(f, "{}", SLASH_CHAR);
+        }
+        for level in self.to_path() {
+            write!(f, "{}", SLASH_CHAR)?;
+            for c in level.chars() {
+                if c == SLASH_CHAR || c == ESCAPE_CHAR {
+                    write!(f, "{}", ESCAPE_CHAR)?;
+                }
+                write!(f, "{}", c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Debug for Facet {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Facet({})", self)
+    }
+}
+
+impl Serialize for Facet {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Facet {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: Deserializer<'de> {
+        let path = <String as Deserialize>::deserialize(deserializer)?;
+        path.parse::<Facet>().map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Facet;
+    use super::FacetParseError;
+
+    #[test]
+    fn test_root() {
+        assert_eq!(Facet::from_text("/"), Facet::root());
+        assert_eq!(Facet::root().to_string(), "/");
+        assert!(Facet::root().to_path().is_empty());
+        assert!(Facet::root().parent().is_none());
+    }
+
+    #[test]
+    fn test_from_text_to_string() {
+        let facet = Facet::from_text("/electronics/phones/android");
+        assert_eq!(facet.to_path(), vec!["electronics", "phones", "android"]);
+        assert_eq!(facet.encoded_str(), "electronics\u{0}phones\u{0}android");
+        assert_eq!(facet.to_string(), "/electronics/phones/android");
+        assert_eq!(facet, Facet::from_path(vec!["electronics", "phones", "android"]));
+    }
+
+    #[test]
+    fn test_escaping() {
+        let facet = Facet::from_text("/music/ac\\/dc");
+        assert_eq!(facet.to_path(), vec!["music", "ac/dc"]);
+        assert_eq!(facet.to_string(), "/music/ac\\/dc");
+        assert_eq!(facet.to_string().parse::<Facet>().unwrap(), facet);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "a/b".parse::<Facet>(),
+            Err(FacetParseError::MissingLeadingSlash("a/b".to_string()))
+        );
+        assert_eq!(
+            "/a//b".parse::<Facet>(),
+            Err(FacetParseError::EmptyLevel("/a//b".to_string()))
+        );
+        assert!("/a/".parse::<Facet>().is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_path_empty_level() {
+        Facet::from_path(vec!["a", "", "b"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_path_separator_in_level() {
+        Facet::from_path(vec!["a\u{0}b"]);
+    }
+
+    #[test]
+    fn test_hierarchy() {
+        let a = Facet::from_text("/a");
+        let ab = Facet::from_text("/a/b");
+        let abc = Facet::from_text("/a/b/c");
+        let ac = Facet::from_text("/ab");
+        assert_eq!(abc.prefixes(), vec![a.clone(), ab.clone(), abc.clone()]);
+        assert_eq!(abc.parent(), Some(ab.clone()));
+        assert_eq!(a.parent(), Some(Facet::root()));
+        assert!(Facet::root().is_prefix_of(&a));
+        assert!(a.is_prefix_of(&abc));
+        assert!(!a.is_prefix_of(&a));
+        assert!(!a.is_prefix_of(&ac));
+        assert!(a.is_parent_of(&ab));
+        assert!(!a.is_parent_of(&abc));
+        assert!(Facet::root().is_parent_of(&a));
+    }
+}
@@ -1,4 +1,9 @@
+use schema::BytesOptions;
+use schema::Cardinality;
+use schema::DateOptions;
 use schema::IntOptions;
+use schema::IpOptions;
+use schema::JsonObjectOptions;
 use schema::TextOptions;
 
 use schema::FieldType;
@@ -14,10 +19,14 @@ use std::fmt;
 /// - a field name
 /// - a field type, itself wrapping up options describing
 /// how the field should be indexed.
-#[derive(Clone, Debug)]
+/// - whether the field is deprecated.
+/// - whether the field is the unique key of the schema.
+#[derive(Clone, Debug, PartialEq)]
 pub struct FieldEntry {
     name: String,
     field_type: FieldType,
+    deprecated: bool,
+    unique_key: bool,
 }
 
 impl FieldEntry {
@@ -27,6 +36,8 @@ impl FieldEntry {
         FieldEntry {
             name: field_name,
             field_type: FieldType::Str(text_options),
+            deprecated: false,
+            unique_key: false,
         }
     }
 
@@ -36,6 +47,8 @@ impl FieldEntry {
         FieldEntry {
             name: field_name,
             field_type: FieldType::U64(field_type),
+            deprecated: false,
+            unique_key: false,
         }
     }
 
@@ -45,6 +58,74 @@ impl FieldEntry {
         FieldEntry {
             name: field_name,
             field_type: FieldType::I64(field_type),
+            deprecated: false,
+            unique_key: false,
+        }
+    }
+
+    /// Creates a new f64 field entry in the schema, given
+    /// a name, and some options.
+    pub fn new_f64(field_name: String, field_type: IntOptions) -> FieldEntry {
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::F64(field_type),
+            deprecated: false,
+            unique_key: false,
+        }
+    }
+
+    /// Creates a new date field entry in the schema, given
+    /// a name, and some options.
+    pub fn new_date(field_name: String, field_type: DateOptions) -> FieldEntry {
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::Date(field_type),
+            deprecated: false,
+            unique_key: false,
+        }
+    }
+
+    /// Creates a new bytes field entry in the schema, given
+    /// a name, and some options.
+    pub fn new_bytes(field_name: String, field_type: BytesOptions) -> FieldEntry {
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::Bytes(field_type),
+            deprecated: false,
+            unique_key: false,
+        }
+    }
+
+    /// Creates a new IP field entry in the schema, given
+    /// a name, and some options.
+    pub fn new_ip(field_name: String, field_type: IpOptions) -> FieldEntry {
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::Ip(field_type),
+            deprecated: false,
+            unique_key: false,
+        }
+    }
+
+    /// Creates a new JSON object field entry in the schema, given
+    /// a name, and some options.
+    pub fn new_json_object(field_name: String, field_type: JsonObjectOptions) -> FieldEntry {
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::JsonObject(field_type),
+            deprecated: false,
+            unique_key: false,
+        }
+    }
+
+    /// Creates a new hierarchical facet field entry in the schema, given
+    /// a name.
+    pub fn new_facet(field_name: String) -> FieldEntry {
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::HierarchicalFacet,
+            deprecated: false,
+            unique_key: false,
         }
     }
 
@@ -58,27 +139,105 @@ impl FieldEntry {
         &self.field_type
     }
 
+    /// Returns true iff the field is deprecated.
+    ///
+    /// The values of a deprecated field are ignored when
+    /// indexing new documents.
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecated
+    }
+
+    /// Marks the field as deprecated.
+    pub(crate) fn set_deprecated(&mut self) {
+        self.deprecated = true;
+    }
+
+    /// Returns true iff the field is the unique key of the schema.
+    pub fn is_unique_key(&self) -> bool {
+        self.unique_key
+    }
+
+    /// Marks the field as the unique key of the schema.
+    pub(crate) fn set_unique_key(&mut self, unique_key: bool) {
+        self.unique_key = unique_key;
+    }
+
     /// Returns true iff the field is indexed
     pub fn is_indexed(&self) -> bool {
         match self.field_type {
             FieldType::Str(ref options) => options.get_indexing_options().is_some(),
-            FieldType::U64(ref options) | FieldType::I64(ref options) => options.is_indexed(),
+            FieldType::U64(ref options)
+            | FieldType::I64(ref options)
+            | FieldType::F64(ref options) => options.is_indexed(),
+            FieldType::Date(ref options) => options.is_indexed(),
+            FieldType::HierarchicalFacet => true,
+            FieldType::Bytes(ref options) => options.is_indexed(),
+            FieldType::Ip(ref options) => options.is_indexed(),
+            FieldType::JsonObject(ref options) => options.is_indexed(),
         }
     }
 
-    /// Returns true iff the field is a int (signed or unsigned) fast field
+    /// Returns true iff the field is a single-valued numerical (u64, i64, f64 or date) fast field
     pub fn is_int_fast(&self) -> bool {
+        self.int_fast_cardinality() == Some(Cardinality::SingleValue)
+    }
+
+    /// Returns true iff the field is a multi-valued numerical (u64, i64, f64 or date) fast field
+    pub fn is_int_multivalued_fast(&self) -> bool {
+        self.int_fast_cardinality() == Some(Cardinality::MultiValues)
+    }
+
+    /// Returns true iff the term ordinals of the field are stored
+    /// in a multi-valued fast field.
+    ///
+    /// This is the case of hierarchical facets, and of
+    /// indexed text fields set as fast.
+    pub fn is_term_ord_fast(&self) -> bool {
+        match self.field_type {
+            FieldType::HierarchicalFacet => true,
+            FieldType::Str(ref options) => {
+                options.is_fast() && options.get_indexing_options().is_some()
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns true iff the values of the field are stored
+    /// in a bytes fast field.
+    ///
+    /// This is the case of bytes and IP fields set as fast.
+    pub fn is_bytes_fast(&self) -> bool {
         match self.field_type {
-            FieldType::U64(ref options) | FieldType::I64(ref options) => options.is_fast(),
+            FieldType::Bytes(ref options) => options.is_fast(),
+            FieldType::Ip(ref options) => options.is_fast(),
             _ => false,
         }
     }
 
+    fn int_fast_cardinality(&self) -> Option<Cardinality> {
+        match self.field_type {
+            FieldType::U64(ref options)
+            | FieldType::I64(ref options)
+            | FieldType::F64(ref options) => {
+                options.get_fastfield_cardinality()
+            }
+            FieldType::Date(ref options) => options.get_fastfield_cardinality(),
+            _ => None,
+        }
+    }
+
     /// Returns true iff the field is stored
     pub fn is_stored(&self) -> bool {
         match self.field_type {
-            FieldType::U64(ref options) | FieldType::I64(ref options) => options.is_stored(),
+            FieldType::U64(ref options)
+            | FieldType::I64(ref options)
+            | FieldType::F64(ref options) => options.is_stored(),
+            FieldType::Date(ref options) => options.is_stored(),
             FieldType::Str(ref options) => options.is_stored(),
+            FieldType::HierarchicalFacet => true,
+            FieldType::Bytes(ref options) => options.is_stored(),
+            FieldType::Ip(ref options) => options.is_stored(),
+            FieldType::JsonObject(ref options) => options.is_stored(),
         }
     }
 }
@@ -102,6 +261,35 @@ impl Serialize for FieldEntry {
                 s.serialize_field("type", "i64")?;
                 s.serialize_field("options", options)?;
             }
+            FieldType::F64(ref options) => {
+                s.serialize_field("type", "f64")?;
+                s.serialize_field("options", options)?;
+            }
+            FieldType::Date(ref options) => {
+                s.serialize_field("type", "date")?;
+                s.serialize_field("options", options)?;
+            }
+            FieldType::HierarchicalFacet => {
+                s.serialize_field("type", "hierarchical_facet")?;
+            }
+            FieldType::Bytes(ref options) => {
+                s.serialize_field("type", "bytes")?;
+                s.serialize_field("options", options)?;
+            }
+            FieldType::Ip(ref options) => {
+                s.serialize_field("type", "ip")?;
+                s.serialize_field("options", options)?;
+            }
+            FieldType::JsonObject(ref options) => {
+                s.serialize_field("type", "json_object")?;
+                s.serialize_field("options", options)?;
+            }
+        }
+        if self.deprecated {
+            s.serialize_field("deprecated", &self.deprecated)?;
+        }
+        if self.unique_key {
+            s.serialize_field("unique_key", &self.unique_key)?;
         }
 
         s.end()
@@ -112,14 +300,16 @@ impl<'de> Deserialize<'de> for FieldEntry {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where D: Deserializer<'de> {
         #[derive(Deserialize)]
-        #[serde(field_identifier, rename_all = "lowercase")]
+        #[serde(field_identifier, rename_all = "snake_case")]
         enum Field {
             Name,
             Type,
             Options,
+            Deprecated,
+            UniqueKey,
         };
 
-        const FIELDS: &[&str] = &["name", "type", "options"];
+        const FIELDS: &[&str] = &["name", "type", "options", "deprecated", "unique_key"];
 
         struct FieldEntryVisitor;
 
@@ -135,6 +325,8 @@ impl<'de> Deserialize<'de> for FieldEntry {
                 let mut name = None;
                 let mut ty = None;
                 let mut field_type = None;
+                let mut deprecated = None;
+                let mut unique_key = None;
                 while let Some(key) = map.next_key()? {
                     match key {
                         Field::Name => {
@@ -147,7 +339,12 @@ impl<'de> Deserialize<'de> for FieldEntry {
                             if ty.is_some() {
                                 return Err(de::Error::duplicate_field("type"));
                             }
-                            ty = Some(map.next_value()?);
+                            let ty_str: &str = map.next_value()?;
+                            if ty_str == "hierarchical_facet" {
+                                // facet fields do not have any options.
+                                field_type = Some(FieldType::HierarchicalFacet);
+                            }
+                            ty = Some(ty_str);
                         }
                         Field::Options => match ty {
                             None => {
@@ -158,12 +355,31 @@ impl<'de> Deserialize<'de> for FieldEntry {
                                 "text" => field_type = Some(FieldType::Str(map.next_value()?)),
                                 "u64" => field_type = Some(FieldType::U64(map.next_value()?)),
                                 "i64" => field_type = Some(FieldType::I64(map.next_value()?)),
+                                "f64" => field_type = Some(FieldType::F64(map.next_value()?)),
+                                "date" => field_type = Some(FieldType::Date(map.next_value()?)),
+                                "bytes" => field_type = Some(FieldType::Bytes(map.next_value()?)),
+                                "ip" => field_type = Some(FieldType::Ip(map.next_value()?)),
+                                "json_object" => {
+                                    field_type = Some(FieldType::JsonObject(map.next_value()?))
+                                }
                                 _ => {
                                     let msg = format!("Unrecognised type {}", ty);
                                     return Err(de::Error::custom(msg));
                                 }
                             },
                         },
+                        Field::Deprecated => {
+                            if deprecated.is_some() {
+                                return Err(de::Error::duplicate_field("deprecated"));
+                            }
+                            deprecated = Some(map.next_value()?);
+                        }
+                        Field::UniqueKey => {
+                            if unique_key.is_some() {
+                                return Err(de::Error::duplicate_field("unique_key"));
+                            }
+                            unique_key = Some(map.next_value()?);
+                        }
                     }
                 }
 
@@ -171,7 +387,12 @@ impl<'de> Deserialize<'de> for FieldEntry {
                 ty.ok_or_else(|| de::Error::missing_field("ty"))?;
                 let field_type = field_type.ok_or_else(|| de::Error::missing_field("options"))?;
 
-                Ok(FieldEntry { name, field_type })
+                Ok(FieldEntry {
+                    name,
+                    field_type,
+                    deprecated: deprecated.unwrap_or(false),
+                    unique_key: unique_key.unwrap_or(false),
+                })
             }
         }
 
@@ -214,4 +435,36 @@ mod tests {
             _ => panic!("expected FieldType::Str"),
         }
     }
+
+    #[test]
+    fn test_facet_json_serialization() {
+        let field_value = FieldEntry::new_facet(String::from("category"));
+        let expected = r#"{
+  "name": "category",
+  "type": "hierarchical_facet"
+}"#;
+        let field_value_json = serde_json::to_string_pretty(&field_value).unwrap();
+        assert_eq!(expected, &field_value_json);
+        let field_value: FieldEntry = serde_json::from_str(expected).unwrap();
+        assert_eq!("category", field_value.name);
+        match field_value.field_type {
+            FieldType::HierarchicalFacet => assert!(true),
+            _ => panic!("expected FieldType::HierarchicalFacet"),
+        }
+    }
+
+    #[test]
+    fn test_deprecated_json_serialization() {
+        let mut field_value = FieldEntry::new_facet(String::from("category"));
+        field_value.set_deprecated();
+        let expected = r#"{
+  "name": "category",
+  "type": "hierarchical_facet",
+  "deprecated": true
+}"#;
+        let field_value_json = serde_json::to_string_pretty(&field_value).unwrap();
+        assert_eq!(expected, &field_value_json);
+        let field_value: FieldEntry = serde_json::from_str(expected).unwrap();
+        assert!(field_value.is_deprecated());
+    }
 }
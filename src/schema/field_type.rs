@@ -1,7 +1,10 @@
-use schema::{IntOptions, TextOptions};
+use base64;
+use schema::{BytesOptions, DateOptions, IntOptions, IpOptions, JsonObjectOptions, TextOptions};
 
+use schema::Facet;
 use schema::IndexRecordOption;
 use schema::Value;
+use schema::{parse_date, parse_ip};
 use serde_json::Value as JsonValue;
 
 /// Possible error that may occur while parsing a field value
@@ -16,9 +19,9 @@ pub enum ValueParsingError {
     TypeError(String),
 }
 
-/// A `FieldType` describes the type (text, u64) of a field as well as
+/// A `FieldType` describes the type (text, u64, f64...) of a field as well as
 /// how it should be handled by tantivy.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum FieldType {
     /// String field type configuration
     Str(TextOptions),
@@ -26,6 +29,18 @@ pub enum FieldType {
     U64(IntOptions),
     /// Signed 64-bits integers 64 field type configuration
     I64(IntOptions),
+    /// 64-bits float 64 field type configuration
+    F64(IntOptions),
+    /// Date field type configuration
+    Date(DateOptions),
+    /// Hierachical Facet
+    HierarchicalFacet,
+    /// Bytes field type configuration
+    Bytes(BytesOptions),
+    /// IP address field type configuration
+    Ip(IpOptions),
+    /// JSON object field type configuration
+    JsonObject(JsonObjectOptions),
 }
 
 impl FieldType {
@@ -33,9 +48,16 @@ impl FieldType {
     pub fn is_indexed(&self) -> bool {
         match *self {
             FieldType::Str(ref text_options) => text_options.get_indexing_options().is_some(),
-            FieldType::U64(ref int_options) | FieldType::I64(ref int_options) => {
+            FieldType::U64(ref int_options)
+            | FieldType::I64(ref int_options)
+            | FieldType::F64(ref int_options) => {
                 int_options.is_indexed()
             }
+            FieldType::Date(ref date_options) => date_options.is_indexed(),
+            FieldType::HierarchicalFacet => true,
+            FieldType::Bytes(ref bytes_options) => bytes_options.is_indexed(),
+            FieldType::Ip(ref ip_options) => ip_options.is_indexed(),
+            FieldType::JsonObject(ref json_object_options) => json_object_options.is_indexed(),
         }
     }
 
@@ -48,13 +70,40 @@ impl FieldType {
             FieldType::Str(ref text_options) => text_options
                 .get_indexing_options()
                 .map(|indexing_options| indexing_options.index_option()),
-            FieldType::U64(ref int_options) | FieldType::I64(ref int_options) => {
+            FieldType::U64(ref int_options)
+            | FieldType::I64(ref int_options)
+            | FieldType::F64(ref int_options) => {
                 if int_options.is_indexed() {
                     Some(IndexRecordOption::Basic)
                 } else {
                     None
                 }
             }
+            FieldType::Date(ref date_options) => {
+                if date_options.is_indexed() {
+                    Some(IndexRecordOption::Basic)
+                } else {
+                    None
+                }
+            }
+            FieldType::HierarchicalFacet => Some(IndexRecordOption::Basic),
+            FieldType::Bytes(ref bytes_options) => {
+                if bytes_options.is_indexed() {
+                    Some(IndexRecordOption::Basic)
+                } else {
+                    None
+                }
+            }
+            FieldType::Ip(ref ip_options) => {
+                if ip_options.is_indexed() {
+                    Some(IndexRecordOption::Basic)
+                } else {
+                    None
+                }
+            }
+            FieldType::JsonObject(ref json_object_options) => json_object_options
+                .get_text_indexing_options()
+                .map(|indexing_options| indexing_options.index_option()),
         }
     }
 
@@ -63,13 +112,48 @@ impl FieldType {
     /// Tantivy will not try to cast values.
     /// For instance, If the json value is the integer `3` and the
     /// target field is a `Str`, this method will return an Error.
+    ///
+    /// Dates are expected to be strings in the RFC 3339 format
+    /// (e.g. `"2018-01-30T10:00:00Z"`), bytes are expected
+    /// to be base64 encoded strings, and IP addresses are expected
+    /// to be IPv4 or IPv6 strings (e.g. `"10.0.0.1"`).
+    ///
+    /// JSON object fields accept any JSON object.
     pub fn value_from_json(&self, json: &JsonValue) -> Result<Value, ValueParsingError> {
         match *json {
             JsonValue::String(ref field_text) => match *self {
                 FieldType::Str(_) => Ok(Value::Str(field_text.clone())),
-                FieldType::U64(_) | FieldType::I64(_) => Err(ValueParsingError::TypeError(
-                    format!("Expected an integer, got {:?}", json),
-                )),
+                FieldType::U64(_) | FieldType::I64(_) | FieldType::F64(_) => Err(
+                    ValueParsingError::TypeError(format!("Expected a number, got {:?}", json)),
+                ),
+                FieldType::Date(_) => parse_date(field_text).map(Value::Date).ok_or_else(|| {
+                    ValueParsingError::TypeError(format!(
+                        "Expected a RFC 3339 date, got {:?}",
+                        json
+                    ))
+                }),
+                FieldType::HierarchicalFacet => field_text
+                    .parse::<Facet>()
+                    .map(Value::Facet)
+                    .map_err(|err| ValueParsingError::TypeError(format!("{}", err))),
+                FieldType::Bytes(_) => base64::decode(field_text)
+                    .map(Value::Bytes)
+                    .map_err(|_| {
+                        ValueParsingError::TypeError(format!(
+                            "Expected base64 encoded bytes, got {:?}",
+                            json
+                        ))
+                    }),
+                FieldType::Ip(_) => parse_ip(field_text).map(Value::Ip).ok_or_else(|| {
+                    ValueParsingError::TypeError(format!(
+                        "Expected an IP address, got {:?}",
+                        json
+                    ))
+                }),
+                FieldType::JsonObject(_) => Err(ValueParsingError::TypeError(format!(
+                    "Expected a JSON object, got {:?}",
+                    json
+                ))),
             },
             JsonValue::Number(ref field_val_num) => match *self {
                 FieldType::I64(_) => {
@@ -88,11 +172,31 @@ impl FieldType {
                         Err(ValueParsingError::OverflowError(msg))
                     }
                 }
-                FieldType::Str(_) => {
+                FieldType::F64(_) => {
+                    if let Some(field_val_f64) = field_val_num.as_f64() {
+                        Ok(Value::F64(field_val_f64))
+                    } else {
+                        let msg = format!("Expected a f64, got {:?}", json);
+                        Err(ValueParsingError::OverflowError(msg))
+                    }
+                }
+                FieldType::Str(_)
+                | FieldType::Date(_)
+                | FieldType::HierarchicalFacet
+                | FieldType::Bytes(_)
+                | FieldType::Ip(_)
+                | FieldType::JsonObject(_) => {
                     let msg = format!("Expected a string, got {:?}", json);
                     Err(ValueParsingError::TypeError(msg))
                 }
             },
+            JsonValue::Object(ref json_object) => match *self {
+                FieldType::JsonObject(_) => Ok(Value::JsonObject(json_object.clone())),
+                _ => {
+                    let msg = format!("Unexpected JSON object {:?}. Expected {:?}", json, self);
+                    Err(ValueParsingError::TypeError(msg))
+                }
+            },
             _ => {
                 let msg = format!(
                     "Json value not supported error {:?}. Expected {:?}",
@@ -1,10 +1,38 @@
 use std::ops::BitOr;
 
+/// Express the number of values a fast field
+/// can have per document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Cardinality {
+    /// The document must have exactly one value associated to the document.
+    #[serde(rename = "single")]
+    SingleValue,
+    /// The document can have any number of values associated to the document.
+    /// This is more memory and CPU expensive than the `SingleValue` solution.
+    #[serde(rename = "multi")]
+    MultiValues,
+}
+
+impl Cardinality {
+    pub(crate) fn is_single_value(&self) -> bool {
+        *self == Cardinality::SingleValue
+    }
+}
+
+impl Default for Cardinality {
+    fn default() -> Cardinality {
+        Cardinality::SingleValue
+    }
+}
+
 /// Define how an int field should be handled by tantivy.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IntOptions {
     indexed: bool,
     fast: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Cardinality::is_single_value")]
+    cardinality: Cardinality,
     stored: bool,
 }
 
@@ -20,10 +48,25 @@ impl IntOptions {
     }
 
     /// Returns true if the value is a fast field.
+    ///
+    /// This is true for both single-valued and
+    /// multi-valued fast fields.
     pub fn is_fast(&self) -> bool {
         self.fast
     }
 
+    /// Returns the cardinality of the fastfield.
+    ///
+    /// If the field has not been declared as a fastfield, then
+    /// the method returns None.
+    pub fn get_fastfield_cardinality(&self) -> Option<Cardinality> {
+        if self.fast {
+            Some(self.cardinality)
+        } else {
+            None
+        }
+    }
+
     /// Set the u64 options as stored.
     ///
     /// Only the fields that are set as *stored* are
@@ -46,18 +89,30 @@ impl IntOptions {
     ///
     /// Fast fields are designed for random access.
     /// Access time are similar to a random lookup in an array.
-    /// If more than one value is associated to a fast field, only the last one is
-    /// kept.
+    /// If more than one value is associated to a fast field, only the first one is
+    /// kept. Use `.set_multivalued_fast()` to keep all of the values.
     pub fn set_fast(mut self) -> IntOptions {
         self.fast = true;
         self
     }
+
+    /// Set the u64 options as a multi-valued fast field.
+    ///
+    /// All of the values associated to a document are kept,
+    /// in the order they were added to the document.
+    /// They can be accessed via a `MultiValueFastFieldReader`.
+    pub fn set_multivalued_fast(mut self) -> IntOptions {
+        self.fast = true;
+        self.cardinality = Cardinality::MultiValues;
+        self
+    }
 }
 
 impl Default for IntOptions {
     fn default() -> IntOptions {
         IntOptions {
             fast: false,
+            cardinality: Cardinality::SingleValue,
             indexed: false,
             stored: false,
         }
@@ -71,6 +126,7 @@ pub const FAST: IntOptions = IntOptions {
     indexed: false,
     stored: false,
     fast: true,
+    cardinality: Cardinality::SingleValue,
 };
 
 /// Shortcut for a u64 indexed field.
@@ -80,6 +136,7 @@ pub const INT_INDEXED: IntOptions = IntOptions {
     indexed: true,
     stored: false,
     fast: false,
+    cardinality: Cardinality::SingleValue,
 };
 
 /// Shortcut for a u64 stored field.
@@ -89,6 +146,7 @@ pub const INT_STORED: IntOptions = IntOptions {
     indexed: false,
     stored: true,
     fast: false,
+    cardinality: Cardinality::SingleValue,
 };
 
 impl BitOr for IntOptions {
@@ -99,6 +157,13 @@ impl BitOr for IntOptions {
         res.indexed = self.indexed | other.indexed;
         res.stored = self.stored | other.stored;
         res.fast = self.fast | other.fast;
+        res.cardinality = if self.cardinality == Cardinality::MultiValues
+            || other.cardinality == Cardinality::MultiValues
+        {
+            Cardinality::MultiValues
+        } else {
+            Cardinality::SingleValue
+        };
         res
     }
 }
@@ -0,0 +1,130 @@
+use schema::IntOptions;
+use std::net::{IpAddr, Ipv6Addr};
+
+/// Parses an IPv4 or IPv6 address.
+///
+/// IPv4 addresses are mapped into IPv6 (e.g. `10.0.0.1`
+/// becomes `::ffff:10.0.0.1`), so that all addresses share
+/// the same representation.
+pub fn parse_ip(ip_str: &str) -> Option<Ipv6Addr> {
+    ip_str.parse::<IpAddr>().ok().map(ip_to_ipv6)
+}
+
+/// Maps an address into IPv6.
+pub fn ip_to_ipv6(ip: IpAddr) -> Ipv6Addr {
+    match ip {
+        IpAddr::V4(ipv4) => ipv4.to_ipv6_mapped(),
+        IpAddr::V6(ipv6) => ipv6,
+    }
+}
+
+/// Returns the `IpAddr` associated to an IPv6 address,
+/// reverting the mapping of IPv4 addresses.
+pub fn ipv6_to_ip(ipv6: Ipv6Addr) -> IpAddr {
+    let segments = ipv6.segments();
+    let is_ipv4_mapped =
+        segments[..5].iter().all(|&segment| segment == 0) && segments[5] == 0xffff;
+    if is_ipv4_mapped {
+        let octets = ipv6.octets();
+        IpAddr::from([octets[12], octets[13], octets[14], octets[15]])
+    } else {
+        IpAddr::V6(ipv6)
+    }
+}
+
+/// Define how an IP field should be handled by tantivy.
+///
+/// IP addresses are indexed as 16 bytes terms, in an
+/// order-preserving manner, which makes it possible to run
+/// range queries over them.
+///
+/// The fast field of an IP field holds the 16 bytes of the
+/// address of each document, and can be read via
+/// an `IpFastFieldReader`.
+///
+/// `IntOptions` can be converted into `IpOptions`, so that the
+/// int shortcuts can be used :
+///
+/// ```rust,ignore
+/// let src_ip = schema_builder.add_ip_field("src_ip", INT_INDEXED | FAST);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IpOptions {
+    indexed: bool,
+    fast: bool,
+    stored: bool,
+}
+
+impl IpOptions {
+    /// Returns true iff the value is stored.
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+
+    /// Returns true iff the value is indexed.
+    pub fn is_indexed(&self) -> bool {
+        self.indexed
+    }
+
+    /// Returns true if the value is a fast field.
+    pub fn is_fast(&self) -> bool {
+        self.fast
+    }
+
+    /// Set the IP options as stored.
+    pub fn set_stored(mut self) -> IpOptions {
+        self.stored = true;
+        self
+    }
+
+    /// Set the IP options as indexed.
+    pub fn set_indexed(mut self) -> IpOptions {
+        self.indexed = true;
+        self
+    }
+
+    /// Set the IP options as a fast field.
+    ///
+    /// Only the first value of each document is
+    /// recorded in the fast field.
+    pub fn set_fast(mut self) -> IpOptions {
+        self.fast = true;
+        self
+    }
+}
+
+impl From<IntOptions> for IpOptions {
+    fn from(int_options: IntOptions) -> IpOptions {
+        IpOptions {
+            indexed: int_options.is_indexed(),
+            fast: int_options.is_fast(),
+            stored: int_options.is_stored(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{ipv6_to_ip, parse_ip};
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test_parse_ip() {
+        let ipv4 = Ipv4Addr::new(10, 0, 0, 1);
+        assert_eq!(parse_ip("10.0.0.1"), Some(ipv4.to_ipv6_mapped()));
+        assert_eq!(parse_ip("::ffff:10.0.0.1"), Some(ipv4.to_ipv6_mapped()));
+        assert_eq!(parse_ip("::1"), Some(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)));
+        assert!(parse_ip("10.0.0").is_none());
+        assert!(parse_ip("10.0.0.0/8").is_none());
+    }
+
+    #[test]
+    fn test_ipv6_to_ip() {
+        let ipv4 = Ipv4Addr::new(192, 168, 1, 12);
+        assert_eq!(ipv6_to_ip(ipv4.to_ipv6_mapped()), IpAddr::V4(ipv4));
+        // IPv4-compatible addresses are not reverted.
+        let localhost = Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1);
+        assert_eq!(ipv6_to_ip(localhost), IpAddr::V6(localhost));
+    }
+}
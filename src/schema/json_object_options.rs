@@ -0,0 +1,63 @@
+use schema::{TextFieldIndexing, TextOptions};
+
+/// Define how a JSON object field should be handled by tantivy.
+///
+/// JSON object fields accept arbitrary JSON objects.
+/// When the field is indexed, the objects are flattened into
+/// terms encoding the path of each value (e.g. `dims.width`)
+/// as well as the value itself:
+/// - strings are tokenized with the tokenizer of the indexing options,
+/// - numbers are indexed as `f64`, which makes range queries possible,
+/// - booleans are indexed as the `true` or `false` strings, without
+/// going through the tokenizer.
+///
+/// `TextOptions` can be converted into `JsonObjectOptions`, so that
+/// the text shortcuts can be used :
+///
+/// ```rust,ignore
+/// let attributes = schema_builder.add_json_field("attributes", TEXT | STORED);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonObjectOptions {
+    stored: bool,
+    indexing: Option<TextFieldIndexing>,
+}
+
+impl JsonObjectOptions {
+    /// Returns true iff the JSON objects are stored.
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+
+    /// Returns true iff the JSON objects are indexed.
+    pub fn is_indexed(&self) -> bool {
+        self.indexing.is_some()
+    }
+
+    /// Returns the indexing options of the text values.
+    pub fn get_text_indexing_options(&self) -> Option<&TextFieldIndexing> {
+        self.indexing.as_ref()
+    }
+
+    /// Sets the field as stored
+    pub fn set_stored(mut self) -> JsonObjectOptions {
+        self.stored = true;
+        self
+    }
+
+    /// Sets the field as indexed, with the indexing options
+    /// used for the text values.
+    pub fn set_indexing_options(mut self, indexing: TextFieldIndexing) -> JsonObjectOptions {
+        self.indexing = Some(indexing);
+        self
+    }
+}
+
+impl From<TextOptions> for JsonObjectOptions {
+    fn from(text_options: TextOptions) -> JsonObjectOptions {
+        JsonObjectOptions {
+            stored: text_options.is_stored(),
+            indexing: text_options.get_indexing_options().cloned(),
+        }
+    }
+}
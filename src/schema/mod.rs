@@ -110,15 +110,22 @@ mod field_value;
 
 mod text_options;
 mod int_options;
+mod date_options;
+mod bytes_options;
+mod ip_options;
+mod json_object_options;
 mod field;
 mod value;
 mod named_field_document;
 mod index_record_option;
+mod facet;
 
 pub use self::named_field_document::NamedFieldDocument;
 pub use self::schema::{Schema, SchemaBuilder};
 pub use self::schema::DocParsingError;
-pub use self::value::Value;
+pub use self::value::{JsonObject, Value};
+pub use self::facet::{Facet, FacetParseError};
+pub(crate) use self::facet::FACET_SEP_BYTE;
 
 pub use self::document::Document;
 pub use self::field::Field;
@@ -135,11 +142,22 @@ pub use self::text_options::TEXT;
 pub use self::text_options::TextFieldIndexing;
 pub use self::text_options::TextOptions;
 
+pub use self::int_options::Cardinality;
 pub use self::int_options::FAST;
 pub use self::int_options::INT_INDEXED;
 pub use self::int_options::INT_STORED;
 pub use self::int_options::IntOptions;
 
+pub use self::date_options::{DateOptions, DatePrecision};
+pub(crate) use self::date_options::parse_date;
+
+pub use self::bytes_options::BytesOptions;
+
+pub use self::ip_options::IpOptions;
+pub(crate) use self::ip_options::{ip_to_ipv6, ipv6_to_ip, parse_ip};
+
+pub use self::json_object_options::JsonObjectOptions;
+
 use regex::Regex;
 
 /// Validator for a potential `field_name`.
@@ -73,6 +73,120 @@ impl SchemaBuilder {
         self.add_field(field_entry)
     }
 
+    /// Adds a new f64 field.
+    /// Returns the associated field handle
+    ///
+    /// # Caution
+    ///
+    /// Appending two fields with the same name
+    /// will result in the shadowing of the first
+    /// by the second one.
+    /// The first field will get a field id
+    /// but only the second one will be indexed
+    pub fn add_f64_field(&mut self, field_name_str: &str, field_options: IntOptions) -> Field {
+        let field_name = String::from(field_name_str);
+        let field_entry = FieldEntry::new_f64(field_name, field_options);
+        self.add_field(field_entry)
+    }
+
+    /// Adds a new date field.
+    /// Returns the associated field handle
+    ///
+    /// The options may be given either as `DateOptions`, or
+    /// as `IntOptions` (e.g. `INT_INDEXED | FAST`), in which case
+    /// the dates have a precision of one second.
+    ///
+    /// # Caution
+    ///
+    /// Appending two fields with the same name
+    /// will result in the shadowing of the first
+    /// by the second one.
+    /// The first field will get a field id
+    /// but only the second one will be indexed
+    pub fn add_date_field<T: Into<DateOptions>>(
+        &mut self,
+        field_name_str: &str,
+        field_options: T,
+    ) -> Field
+    {
+        let field_name = String::from(field_name_str);
+        let field_entry = FieldEntry::new_date(field_name, field_options.into());
+        self.add_field(field_entry)
+    }
+
+    /// Adds a new bytes field.
+    /// Returns the associated field handle
+    ///
+    /// The options may be given either as `BytesOptions`, or
+    /// as `IntOptions` (e.g. `INT_INDEXED | FAST`).
+    ///
+    /// # Caution
+    ///
+    /// Appending two fields with the same name
+    /// will result in the shadowing of the first
+    /// by the second one.
+    /// The first field will get a field id
+    /// but only the second one will be indexed
+    pub fn add_bytes_field<T: Into<BytesOptions>>(
+        &mut self,
+        field_name_str: &str,
+        field_options: T,
+    ) -> Field
+    {
+        let field_name = String::from(field_name_str);
+        let field_entry = FieldEntry::new_bytes(field_name, field_options.into());
+        self.add_field(field_entry)
+    }
+
+    /// Adds a new IP field.
+    /// Returns the associated field handle
+    ///
+    /// The options may be given either as `IpOptions`, or
+    /// as `IntOptions` (e.g. `INT_INDEXED | FAST`).
+    ///
+    /// # Caution
+    ///
+    /// Appending two fields with the same name
+    /// will result in the shadowing of the first
+    /// by the second one.
+    /// The first field will get a field id
+    /// but only the second one will be indexed
+    pub fn add_ip_field<T: Into<IpOptions>>(
+        &mut self,
+        field_name_str: &str,
+        field_options: T,
+    ) -> Field
+    {
+        let field_name = String::from(field_name_str);
+        let field_entry = FieldEntry::new_ip(field_name, field_options.into());
+        self.add_field(field_entry)
+    }
+
+    /// Adds a new JSON object field.
+    /// Returns the associated field handle
+    ///
+    /// The options may be given either as `JsonObjectOptions`, or
+    /// as `TextOptions` (e.g. `TEXT | STORED`), in which case the
+    /// tokenizer of the text options is used for the text values.
+    ///
+    /// # Caution
+    ///
+    /// Appending two fields with the same name
+    /// will result in the shadowing of the first
+    /// by the second one.
+    /// The first field will get a field id
+    /// but only the second one will be indexed
+    pub fn add_json_field<T: Into<JsonObjectOptions>>(
+        &mut self,
+        field_name_str: &str,
+        field_options: T,
+    ) -> Field
+    {
+        let field_name = String::from(field_name_str);
+        let field_entry = FieldEntry::new_json_object(field_name, field_options.into());
+        self.add_field(field_entry)
+    }
+
     /// Adds a new text field.
     /// Returns the associated field handle
     ///
@@ -89,6 +203,66 @@ impl SchemaBuilder {
         self.add_field(field_entry)
     }
 
+    /// Adds a new hierarchical facet field.
+    /// Returns the associated field handle
+    ///
+    /// Facet fields are always indexed and stored.
+    /// The term ordinals of the facets of each document are also
+    /// stored in a column, so that facets can be counted efficiently
+    /// by the `HierarchicalFacetCollector`.
+    pub fn add_facet_field(&mut self, field_name_str: &str) -> Field {
+        let field_name = String::from(field_name_str);
+        let field_entry = FieldEntry::new_facet(field_name);
+        self.add_field(field_entry)
+    }
+
+    /// Marks a field as deprecated.
+    ///
+    /// A deprecated field keeps its `Field` handle, and the values
+    /// indexed before its deprecation remain searchable, but its values
+    /// are ignored when indexing new documents.
+    ///
+    /// # Panics
+    /// Panics if the field does not belong to the schema in build.
+    pub fn deprecate_field(&mut self, field: Field) {
+        self.fields[field.0 as usize].set_deprecated();
+    }
+
+    /// Declares a field as the unique key of the schema.
+    ///
+    /// The unique key identifies documents, and makes it possible
+    /// to replace or delete them via `IndexWriter::upsert_document`
+    /// and `IndexWriter::delete_by_key`.
+    /// A schema has at most one unique key: declaring a new one
+    /// replaces the previous one.
+    /// The unique key of an existing index cannot be changed afterwards,
+    /// see `Index::update_schema`.
+    ///
+    /// # Panics
+    /// Panics if the field does not belong to the schema in build,
+    /// or if it is not an indexed `u64`, `i64` or bytes field, or
+    /// an untokenized (`STRING`) text field.
+    pub fn set_unique_key(&mut self, field: Field) {
+        let is_valid_key = match *self.fields[field.0 as usize].field_type() {
+            FieldType::Str(ref text_options) => text_options
+                .get_indexing_options()
+                .map(|indexing_options| indexing_options.tokenizer() == "raw")
+                .unwrap_or(false),
+            FieldType::U64(ref int_options) | FieldType::I64(ref int_options) => {
+                int_options.is_indexed()
+            }
+            FieldType::Bytes(ref bytes_options) => bytes_options.is_indexed(),
+            _ => false,
+        };
+        assert!(
+            is_valid_key,
+            "The unique key must be an indexed u64, i64, bytes or untokenized text field."
+        );
+        for (field_id, field_entry) in self.fields.iter_mut().enumerate() {
+            field_entry.set_unique_key(field_id == field.0 as usize);
+        }
+    }
+
     /// Adds a field entry to the schema in build.
     fn add_field(&mut self, field_entry: FieldEntry) -> Field {
         let field = Field(self.fields.len() as u32);
@@ -175,6 +349,33 @@ impl Schema {
         self.0.fields_map.get(field_name).cloned()
     }
 
+    /// Returns the unique key field of the schema, if any.
+    pub fn unique_key_field(&self) -> Option<Field> {
+        self.0
+            .fields
+            .iter()
+            .position(FieldEntry::is_unique_key)
+            .map(|field_id| Field(field_id as u32))
+    }
+
+    /// Returns the term identifying the documents
+    /// bearing the given unique key.
+    ///
+    /// Returns `None` if the schema does not have a unique key,
+    /// or if the key value does not match the type of the unique key field.
+    pub fn unique_key_term(&self, key: &Value) -> Option<Term> {
+        let field = self.unique_key_field()?;
+        match (self.get_field_entry(field).field_type(), key) {
+            (&FieldType::Str(_), &Value::Str(ref text)) => Some(Term::from_field_text(field, text)),
+            (&FieldType::U64(_), &Value::U64(val)) => Some(Term::from_field_u64(field, val)),
+            (&FieldType::I64(_), &Value::I64(val)) => Some(Term::from_field_i64(field, val)),
+            (&FieldType::Bytes(_), &Value::Bytes(ref bytes)) => {
+                Some(Term::from_field_bytes(field, bytes))
+            }
+            _ => None,
+        }
+    }
+
     /// Create a named document off the doc.
     pub fn to_named_doc(&self, doc: &Document) -> NamedFieldDocument {
         let mut field_map = BTreeMap::new();
@@ -285,6 +486,20 @@ impl<'de> Deserialize<'de> for Schema {
     }
 }
 
+/// Creates a `SchemaBuilder` holding all of the fields of
+/// an existing schema.
+///
+/// This is typically used to append fields to the schema
+/// of an existing index, see `Index::update_schema`.
+impl From<Schema> for SchemaBuilder {
+    fn from(schema: Schema) -> SchemaBuilder {
+        SchemaBuilder {
+            fields: schema.0.fields.clone(),
+            fields_map: schema.0.fields_map.clone(),
+        }
+    }
+}
+
 impl From<SchemaBuilder> for Schema {
     fn from(schema_builder: SchemaBuilder) -> Schema {
         schema_builder.build()
@@ -543,4 +758,172 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    pub fn test_parse_document_f64() {
+        let mut schema_builder = SchemaBuilder::default();
+        let price_field = schema_builder.add_f64_field("price", INT_STORED);
+        let schema = schema_builder.build();
+        {
+            let doc = schema.parse_document(r#"{"price": 3.25}"#).unwrap();
+            assert_eq!(doc.get_first(price_field).unwrap().f64_value(), 3.25f64);
+            let doc_serdeser = schema.parse_document(&schema.to_json(&doc)).unwrap();
+            assert_eq!(doc, doc_serdeser);
+        }
+        {
+            let doc = schema.parse_document(r#"{"price": -7}"#).unwrap();
+            assert_eq!(doc.get_first(price_field).unwrap().f64_value(), -7f64);
+        }
+        {
+            let json_err = schema.parse_document(r#"{"price": "3.25"}"#);
+            match json_err {
+                Err(DocParsingError::ValueError(_, ValueParsingError::TypeError(_))) => {}
+                _ => {
+                    panic!("expected string of 3.25 to fail but didn't");
+                }
+            }
+        }
+        {
+            let schema_json = serde_json::to_string(&schema).unwrap();
+            let schema_deser: Schema = serde_json::from_str(&schema_json).unwrap();
+            match *schema_deser.get_field_entry(price_field).field_type() {
+                FieldType::F64(ref options) => assert!(options.is_stored()),
+                _ => panic!("expected a f64 field"),
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_parse_document_date() {
+        let mut schema_builder = SchemaBuilder::default();
+        let created_field = schema_builder.add_date_field("created", INT_STORED);
+        let schema = schema_builder.build();
+        {
+            let doc = schema
+                .parse_document(r#"{"created": "2018-01-30T10:00:00+01:00"}"#)
+                .unwrap();
+            let expected = parse_date("2018-01-30T09:00:00Z").unwrap();
+            assert_eq!(*doc.get_first(created_field).unwrap().date_value(), expected);
+            assert_eq!(
+                schema.to_json(&doc),
+                r#"{"created":["2018-01-30T09:00:00+00:00"]}"#
+            );
+            let doc_serdeser = schema.parse_document(&schema.to_json(&doc)).unwrap();
+            assert_eq!(doc, doc_serdeser);
+        }
+        {
+            let json_err = schema.parse_document(r#"{"created": 1517302800}"#);
+            match json_err {
+                Err(DocParsingError::ValueError(_, ValueParsingError::TypeError(_))) => {}
+                _ => {
+                    panic!("expected a timestamp to fail but didn't");
+                }
+            }
+        }
+        {
+            let json_err = schema.parse_document(r#"{"created": "30/01/2018"}"#);
+            match json_err {
+                Err(DocParsingError::ValueError(_, ValueParsingError::TypeError(_))) => {}
+                _ => {
+                    panic!("expected an invalid date to fail but didn't");
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_parse_document_bytes() {
+        let mut schema_builder = SchemaBuilder::default();
+        let bytes_field = schema_builder.add_bytes_field("thumbnail", INT_STORED);
+        let schema = schema_builder.build();
+        {
+            let doc = schema.parse_document(r#"{"thumbnail": "AAECAw=="}"#).unwrap();
+            assert_eq!(
+                doc.get_first(bytes_field).unwrap().bytes_value(),
+                &[0u8, 1u8, 2u8, 3u8]
+            );
+            assert_eq!(schema.to_json(&doc), r#"{"thumbnail":["AAECAw=="]}"#);
+            let doc_serdeser = schema.parse_document(&schema.to_json(&doc)).unwrap();
+            assert_eq!(doc, doc_serdeser);
+        }
+        {
+            let json_err = schema.parse_document(r#"{"thumbnail": "not base64!"}"#);
+            match json_err {
+                Err(DocParsingError::ValueError(_, ValueParsingError::TypeError(_))) => {}
+                _ => {
+                    panic!("expected invalid base64 to fail but didn't");
+                }
+            }
+        }
+        {
+            let schema_json = serde_json::to_string(&schema).unwrap();
+            let schema_deser: Schema = serde_json::from_str(&schema_json).unwrap();
+            match *schema_deser.get_field_entry(bytes_field).field_type() {
+                FieldType::Bytes(ref options) => assert!(options.is_stored()),
+                _ => panic!("expected a bytes field"),
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_parse_document_ip() {
+        let mut schema_builder = SchemaBuilder::default();
+        let ip_field = schema_builder.add_ip_field("src_ip", INT_STORED);
+        let schema = schema_builder.build();
+        {
+            let doc = schema
+                .parse_document(r#"{"src_ip": ["10.0.0.1", "2001:db8::1"]}"#)
+                .unwrap();
+            let ips: Vec<String> = doc.get_all(ip_field)
+                .into_iter()
+                .map(|value| value.ip_value().to_string())
+                .collect();
+            assert_eq!(ips, vec!["::ffff:10.0.0.1", "2001:db8::1"]);
+            assert_eq!(
+                schema.to_json(&doc),
+                r#"{"src_ip":["10.0.0.1","2001:db8::1"]}"#
+            );
+            let doc_serdeser = schema.parse_document(&schema.to_json(&doc)).unwrap();
+            assert_eq!(doc, doc_serdeser);
+        }
+        {
+            let json_err = schema.parse_document(r#"{"src_ip": "10.0.0.256"}"#);
+            match json_err {
+                Err(DocParsingError::ValueError(_, ValueParsingError::TypeError(_))) => {}
+                _ => {
+                    panic!("expected an invalid IP to fail but didn't");
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_parse_document_json() {
+        let mut schema_builder = SchemaBuilder::default();
+        let attributes = schema_builder.add_json_field("attributes", TEXT | STORED);
+        let schema = schema_builder.build();
+        {
+            let doc = schema
+                .parse_document(r#"{"attributes": {"color": "red", "dims": {"width": 2.5}}}"#)
+                .unwrap();
+            let json_object = doc.get_first(attributes).unwrap().json_object();
+            assert_eq!(json_object["color"], "red");
+            assert_eq!(json_object["dims"]["width"], 2.5);
+            assert_eq!(
+                schema.to_json(&doc),
+                r#"{"attributes":[{"color":"red","dims":{"width":2.5}}]}"#
+            );
+            let doc_serdeser = schema.parse_document(&schema.to_json(&doc)).unwrap();
+            assert_eq!(doc, doc_serdeser);
+        }
+        {
+            let json_err = schema.parse_document(r#"{"attributes": "red"}"#);
+            match json_err {
+                Err(DocParsingError::ValueError(_, ValueParsingError::TypeError(_))) => {}
+                _ => {
+                    panic!("expected a string to fail for a JSON object field but didn't");
+                }
+            }
+        }
+    }
 }
@@ -1,13 +1,24 @@
 use std::fmt;
 
+use super::DatePrecision;
+use super::Facet;
 use super::Field;
+use DateTime;
 use byteorder::{BigEndian, ByteOrder};
 use common;
+use std::net::Ipv6Addr;
 use std::str;
 
 /// Size (in bytes) of the buffer of a int field.
 const INT_TERM_LEN: usize = 4 + 8;
 
+/// Byte marking the end of the path, within the terms of a JSON object field.
+const JSON_END_OF_PATH: u8 = 0u8;
+/// Type code of the text values of a JSON object field.
+const JSON_TEXT_CODE: u8 = b's';
+/// Type code of the numerical values of a JSON object field.
+const JSON_F64_CODE: u8 = b'f';
+
 /// Term represents the value that the token can take.
 ///
 /// It actually wraps a `Vec<u8>`.
@@ -28,6 +39,23 @@ impl Term {
         Term::from_field_u64(field, val_u64)
     }
 
+    /// Builds a term given a field, and a f64-value
+    ///
+    /// The value is mapped to a `u64` using `common::f64_to_u64`,
+    /// so that the order of the terms matches the order of the values.
+    pub fn from_field_f64(field: Field, val: f64) -> Term {
+        let val_u64: u64 = common::f64_to_u64(val);
+        Term::from_field_u64(field, val_u64)
+    }
+
+    /// Builds a term given a field, and a date.
+    ///
+    /// The date is encoded as its `i64` timestamp, in the unit
+    /// of the given precision, which should be the precision of the field.
+    pub fn from_field_date(field: Field, date: &DateTime, precision: DatePrecision) -> Term {
+        Term::from_field_i64(field, precision.date_to_timestamp(date))
+    }
+
     /// Builds a term given a field, and a string value
     ///
     /// Assuming the term has a field id of 2, and a text value of "abc",
@@ -42,6 +70,68 @@ impl Term {
         term
     }
 
+    /// Builds a term given a field, and some raw bytes.
+    ///
+    /// The term bytes consist in the field id followed
+    /// by the bytes, left untouched.
+    pub fn from_field_bytes(field: Field, bytes: &[u8]) -> Term {
+        let buffer = Vec::with_capacity(4 + bytes.len());
+        let mut term = Term(buffer);
+        term.set_field(field);
+        term.set_bytes(bytes);
+        term
+    }
+
+    /// Builds a term given a field, and an IP address.
+    ///
+    /// The address is encoded as its 16 bytes, in network order,
+    /// so that the order of the terms matches the order of the addresses.
+    /// IPv4 addresses are expected to be mapped into IPv6.
+    pub fn from_field_ip(field: Field, ip: &Ipv6Addr) -> Term {
+        Term::from_field_bytes(field, &ip.octets())
+    }
+
+    /// Builds a term given a JSON object field, the path
+    /// of a value within the objects (e.g. `dims.width`), and a text token.
+    ///
+    /// The term bytes consist in the field id, followed by the path,
+    /// a `0` byte, a type code, and finally the token.
+    pub fn from_field_json_text(field: Field, path: &str, text: &str) -> Term {
+        let mut term = Term::for_json_path(field, path, JSON_TEXT_CODE);
+        term.0.extend(text.as_bytes());
+        term
+    }
+
+    /// Builds a term given a JSON object field, the path
+    /// of a value within the objects (e.g. `dims.width`), and a number.
+    ///
+    /// All of the numbers are encoded as `f64`, in an order-preserving manner,
+    /// so that the terms of a given path can be searched by range.
+    pub fn from_field_json_f64(field: Field, path: &str, val: f64) -> Term {
+        let mut term = Term::for_json_path(field, path, JSON_F64_CODE);
+        let mut val_bytes = [0u8; 8];
+        BigEndian::write_u64(&mut val_bytes, common::f64_to_u64(val));
+        term.0.extend(&val_bytes);
+        term
+    }
+
+    fn for_json_path(field: Field, path: &str, type_code: u8) -> Term {
+        let mut term = Term(Vec::with_capacity(4 + path.len() + 2 + 8));
+        term.set_field(field);
+        term.0.extend(path.as_bytes());
+        term.0.push(JSON_END_OF_PATH);
+        term.0.push(type_code);
+        term
+    }
+
+    /// Builds a term given a field, and a facet.
+    ///
+    /// The term bytes consist in the field id followed by
+    /// the encoded representation of the facet.
+    pub fn from_facet(field: Field, facet: &Facet) -> Term {
+        Term::from_field_text(field, facet.encoded_str())
+    }
+
     /// Builds a term given a field, and a u64-value
     ///
     /// Assuming the term has a field id of 1, and a u64 value of 3234,
@@ -90,10 +180,20 @@ impl Term {
         self.set_u64(common::i64_to_u64(val));
     }
 
+    /// Sets a `f64` value in the term.
+    pub fn set_f64(&mut self, val: f64) {
+        self.set_u64(common::f64_to_u64(val));
+    }
+
     /// Set the texts only, keeping the field untouched.
     pub fn set_text(&mut self, text: &str) {
+        self.set_bytes(text.as_bytes());
+    }
+
+    /// Set the raw bytes only, keeping the field untouched.
+    pub fn set_bytes(&mut self, bytes: &[u8]) {
         self.0.resize(4, 0u8);
-        self.0.extend(text.as_bytes());
+        self.0.extend(bytes);
     }
 
     /// Builds a term from its byte representation.
@@ -136,6 +236,15 @@ where B: AsRef<[u8]> {
         common::u64_to_i64(BigEndian::read_u64(&self.0.as_ref()[4..]))
     }
 
+    /// Returns the `f64` value stored in a term.
+    ///
+    /// # Panics
+    /// ... or returns an invalid value
+    /// if the term is not a `f64` field.
+    pub fn get_f64(&self) -> f64 {
+        common::u64_to_f64(BigEndian::read_u64(&self.0.as_ref()[4..]))
+    }
+
     /// Returns the text associated with the term.
     ///
     /// # Panics
@@ -179,6 +288,7 @@ impl fmt::Debug for Term {
 mod tests {
 
     use schema::*;
+    use std::net::Ipv4Addr;
 
     #[test]
     pub fn test_term() {
@@ -206,5 +316,30 @@ mod tests {
             assert_eq!(term.as_slice()[10], (933u64 / 256u64) as u8);
             assert_eq!(term.as_slice()[11], (983u64 % 256u64) as u8);
         }
+        {
+            let term = Term::from_field_bytes(count_field, &[0u8, 255u8, 3u8]);
+            assert_eq!(term.field(), count_field);
+            assert_eq!(term.value_bytes(), &[0u8, 255u8, 3u8]);
+        }
+        {
+            let term = Term::from_field_json_text(count_field, "dims.unit", "cm");
+            assert_eq!(term.value_bytes(), b"dims.unit\0scm");
+            let lower = Term::from_field_json_f64(count_field, "dims.width", -1.5f64);
+            let upper = Term::from_field_json_f64(count_field, "dims.width", 10f64);
+            assert_eq!(lower.as_slice().len(), 4 + 10 + 2 + 8);
+            assert!(lower < upper);
+            assert!(upper < Term::from_field_json_f64(count_field, "dims.widths", -1.5f64));
+        }
+        {
+            let ip = Ipv4Addr::new(10, 0, 0, 1).to_ipv6_mapped();
+            let term = Term::from_field_ip(count_field, &ip);
+            assert_eq!(term.as_slice().len(), 4 + 16);
+            assert_eq!(
+                &term.value_bytes()[10..],
+                &[255u8, 255u8, 10u8, 0u8, 0u8, 1u8]
+            );
+            let other_ip = Ipv4Addr::new(9, 255, 255, 255).to_ipv6_mapped();
+            assert!(Term::from_field_ip(count_field, &other_ip) < term);
+        }
     }
 }
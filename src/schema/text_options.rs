@@ -7,6 +7,13 @@ use std::ops::BitOr;
 pub struct TextOptions {
     indexing: Option<TextFieldIndexing>,
     stored: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_false")]
+    fast: bool,
+}
+
+fn is_false(val: &bool) -> bool {
+    !*val
 }
 
 impl TextOptions {
@@ -20,12 +27,33 @@ impl TextOptions {
         self.stored
     }
 
+    /// Returns true iff the term ordinals of the field are
+    /// stored in a fast field.
+    pub fn is_fast(&self) -> bool {
+        self.fast
+    }
+
     /// Sets the field as stored
     pub fn set_stored(mut self) -> TextOptions {
         self.stored = true;
         self
     }
 
+    /// Sets the field as fast.
+    ///
+    /// For each document, the ordinals of its terms within the
+    /// segment term dictionary are stored in a multi-valued fast field.
+    /// This makes it possible to run terms aggregations over the field.
+    ///
+    /// Only the terms produced by the tokenizer are recorded, so this
+    /// option is mostly relevant for fields indexed with the
+    /// `raw` tokenizer (e.g. `STRING`), and has no effect
+    /// if the field is not indexed.
+    pub fn set_fast(mut self) -> TextOptions {
+        self.fast = true;
+        self
+    }
+
     /// Sets the field as indexed, with the specific indexing options.
     pub fn set_indexing_options(mut self, indexing: TextFieldIndexing) -> TextOptions {
         self.indexing = Some(indexing);
@@ -38,6 +66,7 @@ impl Default for TextOptions {
         TextOptions {
             indexing: None,
             stored: false,
+            fast: false,
         }
     }
 }
@@ -92,6 +121,7 @@ pub const STRING: TextOptions = TextOptions {
         record: IndexRecordOption::Basic,
     }),
     stored: false,
+    fast: false,
 };
 
 /// The field will be tokenized and indexed
@@ -101,6 +131,7 @@ pub const TEXT: TextOptions = TextOptions {
         record: IndexRecordOption::WithFreqsAndPositions,
     }),
     stored: false,
+    fast: false,
 };
 
 /// A stored fields of a document can be retrieved given its `DocId`.
@@ -110,6 +141,7 @@ pub const TEXT: TextOptions = TextOptions {
 pub const STORED: TextOptions = TextOptions {
     indexing: None,
     stored: true,
+    fast: false,
 };
 
 impl BitOr for TextOptions {
@@ -119,6 +151,7 @@ impl BitOr for TextOptions {
         let mut res = TextOptions::default();
         res.indexing = self.indexing.or(other.indexing);
         res.stored = self.stored | other.stored;
+        res.fast = self.fast | other.fast;
         res
     }
 }
@@ -155,6 +188,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fast_text_options() {
+        assert!(!STRING.is_fast());
+        let field_options = STRING.set_fast() | STORED;
+        assert!(field_options.is_fast());
+        assert!(field_options.is_stored());
+        let json = ::serde_json::to_string(&field_options).unwrap();
+        let field_options_deser: TextOptions = ::serde_json::from_str(&json).unwrap();
+        assert_eq!(field_options_deser, field_options);
+        let json = ::serde_json::to_string(&STRING).unwrap();
+        assert!(!json.contains("fast"));
+    }
+
     #[test]
     fn test_cmp_index_record_option() {
         assert!(IndexRecordOption::WithFreqsAndPositions > IndexRecordOption::WithFreqs);
@@ -1,10 +1,21 @@
+use DateTime;
+use base64;
+use schema::Facet;
+use schema::ipv6_to_ip;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use common;
 use serde::de::Visitor;
+use serde_json::{Map as JsonMap, Number as JsonNumber, Value as JsonValue};
+use std::cmp::Ordering;
 use std::fmt;
+use std::net::Ipv6Addr;
+
+/// JSON object, as accepted by JSON object fields.
+pub type JsonObject = JsonMap<String, JsonValue>;
 
 /// Value represents the value of a any field.
 /// It is an enum over all over all of the possible field type.
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Debug, Clone)]
 pub enum Value {
     /// The str type is used for any text information.
     Str(String),
@@ -12,8 +23,146 @@ pub enum Value {
     U64(u64),
     /// Signed 64-bits Integer `i64`
     I64(i64),
+    /// 64-bits Float `f64`
+    F64(f64),
+    /// Date, in the UTC timezone
+    Date(DateTime),
+    /// Hierarchical Facet
+    Facet(Facet),
+    /// Arbitrary bytes
+    Bytes(Vec<u8>),
+    /// IP address, IPv4 addresses being mapped into IPv6
+    Ip(Ipv6Addr),
+    /// JSON object
+    JsonObject(JsonObject),
+}
+
+impl Value {
+    /// Rank of the value type, used to order values of different types.
+    fn type_rank(&self) -> u8 {
+        match *self {
+            Value::Str(_) => 0,
+            Value::U64(_) => 1,
+            Value::I64(_) => 2,
+            Value::F64(_) => 3,
+            Value::Date(_) => 4,
+            Value::Facet(_) => 5,
+            Value::Bytes(_) => 6,
+            Value::Ip(_) => 7,
+            Value::JsonObject(_) => 8,
+        }
+    }
+}
+
+impl Ord for Value {
+    /// Values of different types are ordered by type.
+    ///
+    /// `f64` values are compared through their order-preserving
+    /// `u64` representation, which makes the order total.
+    ///
+    /// JSON objects are compared structurally, see `cmp_json_values`.
+    fn cmp(&self, other: &Value) -> Ordering {
+        match (self, other) {
+            (&Value::Str(ref left), &Value::Str(ref right)) => left.cmp(right),
+            (&Value::U64(left), &Value::U64(right)) => left.cmp(&right),
+            (&Value::I64(left), &Value::I64(right)) => left.cmp(&right),
+            (&Value::F64(left), &Value::F64(right)) => {
+                common::f64_to_u64(left).cmp(&common::f64_to_u64(right))
+            }
+            (&Value::Date(ref left), &Value::Date(ref right)) => left.cmp(right),
+            (&Value::Facet(ref left), &Value::Facet(ref right)) => left.cmp(right),
+            (&Value::Bytes(ref left), &Value::Bytes(ref right)) => left.cmp(right),
+            (&Value::Ip(ref left), &Value::Ip(ref right)) => left.cmp(right),
+            (&Value::JsonObject(ref left), &Value::JsonObject(ref right)) => {
+                cmp_json_objects(left, right)
+            }
+            _ => self.type_rank().cmp(&other.type_rank()),
+        }
+    }
+}
+
+/// Rank of the JSON value type, used to order JSON values of different types.
+fn json_type_rank(value: &JsonValue) -> u8 {
+    match *value {
+        JsonValue::Null => 0,
+        JsonValue::Bool(_) => 1,
+        JsonValue::Number(_) => 2,
+        JsonValue::String(_) => 3,
+        JsonValue::Array(_) => 4,
+        JsonValue::Object(_) => 5,
+    }
+}
+
+/// Numbers are ordered by value. Integers are compared exactly,
+/// and a float comes after the integers it is equal to.
+fn cmp_json_numbers(left: &JsonNumber, right: &JsonNumber) -> Ordering {
+    if let (Some(left), Some(right)) = (left.as_i64(), right.as_i64()) {
+        return left.cmp(&right);
+    }
+    if let (Some(left), Some(right)) = (left.as_u64(), right.as_u64()) {
+        return left.cmp(&right);
+    }
+    let float_repr = |number: &JsonNumber| common::f64_to_u64(number.as_f64().unwrap_or(0f64));
+    float_repr(left)
+        .cmp(&float_repr(right))
+        .then_with(|| left.is_f64().cmp(&right.is_f64()))
+}
+
+/// Total order on JSON values.
+///
+/// Values of different types are ordered by type, arrays are
+/// ordered lexicographically, and objects are ordered
+/// lexicographically on their entries sorted by key.
+fn cmp_json_values(left: &JsonValue, right: &JsonValue) -> Ordering {
+    match (left, right) {
+        (&JsonValue::Bool(left), &JsonValue::Bool(right)) => left.cmp(&right),
+        (&JsonValue::Number(ref left), &JsonValue::Number(ref right)) => {
+            cmp_json_numbers(left, right)
+        }
+        (&JsonValue::String(ref left), &JsonValue::String(ref right)) => left.cmp(right),
+        (&JsonValue::Array(ref left), &JsonValue::Array(ref right)) => {
+            for (left_val, right_val) in left.iter().zip(right.iter()) {
+                let ord = cmp_json_values(left_val, right_val);
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+            }
+            left.len().cmp(&right.len())
+        }
+        (&JsonValue::Object(ref left), &JsonValue::Object(ref right)) => {
+            cmp_json_objects(left, right)
+        }
+        _ => json_type_rank(left).cmp(&json_type_rank(right)),
+    }
 }
 
+fn cmp_json_objects(left: &JsonObject, right: &JsonObject) -> Ordering {
+    // `JsonObject` iterates over its entries sorted by key.
+    for ((left_key, left_val), (right_key, right_val)) in left.iter().zip(right.iter()) {
+        let ord = left_key
+            .cmp(right_key)
+            .then_with(|| cmp_json_values(left_val, right_val));
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    left.len().cmp(&right.len())
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Value) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Value {}
+
 impl Serialize for Value {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where S: Serializer {
@@ -21,6 +170,12 @@ impl Serialize for Value {
             Value::Str(ref v) => serializer.serialize_str(v),
             Value::U64(u) => serializer.serialize_u64(u),
             Value::I64(u) => serializer.serialize_i64(u),
+            Value::F64(u) => serializer.serialize_f64(u),
+            Value::Date(ref date) => serializer.serialize_str(&date.to_rfc3339()),
+            Value::Facet(ref facet) => facet.serialize(serializer),
+            Value::Bytes(ref bytes) => serializer.serialize_str(&base64::encode(bytes)),
+            Value::Ip(ip) => serializer.serialize_str(&ipv6_to_ip(ip).to_string()),
+            Value::JsonObject(ref json_object) => json_object.serialize(serializer),
         }
     }
 }
@@ -45,6 +200,10 @@ impl<'de> Deserialize<'de> for Value {
                 Ok(Value::I64(v))
             }
 
+            fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+                Ok(Value::F64(v))
+            }
+
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
                 Ok(Value::Str(v.to_owned()))
             }
@@ -91,6 +250,74 @@ impl Value {
             _ => panic!("This is not a text field."),
         }
     }
+
+    /// Returns the f64-value, provided the value is of the `F64` type.
+    ///
+    /// # Panics
+    /// If the value is not of type `F64`
+    pub fn f64_value(&self) -> f64 {
+        match *self {
+            Value::F64(ref value) => *value,
+            _ => panic!("This is not a f64 field."),
+        }
+    }
+
+    /// Returns the date, provided the value is of the `Date` type.
+    ///
+    /// # Panics
+    /// If the value is not of type `Date`
+    pub fn date_value(&self) -> &DateTime {
+        match *self {
+            Value::Date(ref date) => date,
+            _ => panic!("This is not a date field."),
+        }
+    }
+
+    /// Returns the bytes, provided the value is of the `Bytes` type.
+    ///
+    /// # Panics
+    /// If the value is not of type `Bytes`
+    pub fn bytes_value(&self) -> &[u8] {
+        match *self {
+            Value::Bytes(ref bytes) => bytes,
+            _ => panic!("This is not a bytes field."),
+        }
+    }
+
+    /// Returns the IP address, provided the value is of the `Ip` type.
+    ///
+    /// IPv4 addresses are returned mapped into IPv6.
+    ///
+    /// # Panics
+    /// If the value is not of type `Ip`
+    pub fn ip_value(&self) -> Ipv6Addr {
+        match *self {
+            Value::Ip(ip) => ip,
+            _ => panic!("This is not an IP field."),
+        }
+    }
+
+    /// Returns the JSON object, provided the value is of the `JsonObject` type.
+    ///
+    /// # Panics
+    /// If the value is not of type `JsonObject`
+    pub fn json_object(&self) -> &JsonObject {
+        match *self {
+            Value::JsonObject(ref json_object) => json_object,
+            _ => panic!("This is not a JSON object field."),
+        }
+    }
+
+    /// Returns the facet, provided the value is of the `Facet` type.
+    ///
+    /// # Panics
+    /// If the value is not of type `Facet`
+    pub fn facet(&self) -> &Facet {
+        match *self {
+            Value::Facet(ref facet) => facet,
+            _ => panic!("This is not a facet field."),
+        }
+    }
 }
 
 impl From<String> for Value {
@@ -111,20 +338,66 @@ impl From<i64> for Value {
     }
 }
 
+impl From<f64> for Value {
+    fn from(v: f64) -> Value {
+        Value::F64(v)
+    }
+}
+
+impl From<DateTime> for Value {
+    fn from(date: DateTime) -> Value {
+        Value::Date(date)
+    }
+}
+
 impl<'a> From<&'a str> for Value {
     fn from(s: &'a str) -> Value {
         Value::Str(s.to_string())
     }
 }
 
+impl From<Facet> for Value {
+    fn from(facet: Facet) -> Value {
+        Value::Facet(facet)
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    fn from(bytes: Vec<u8>) -> Value {
+        Value::Bytes(bytes)
+    }
+}
+
+impl From<Ipv6Addr> for Value {
+    fn from(ip: Ipv6Addr) -> Value {
+        Value::Ip(ip)
+    }
+}
+
+impl From<JsonObject> for Value {
+    fn from(json_object: JsonObject) -> Value {
+        Value::JsonObject(json_object)
+    }
+}
+
 mod binary_serialize {
     use super::Value;
-    use common::BinarySerializable;
+    use chrono::{TimeZone, Utc};
+    use common::{self, BinarySerializable};
+    use schema::Facet;
+    use serde_json;
     use std::io::{self, Read, Write};
+    use std::net::Ipv6Addr;
 
     const TEXT_CODE: u8 = 0;
     const U64_CODE: u8 = 1;
     const I64_CODE: u8 = 2;
+    const FACET_CODE: u8 = 3;
+    const F64_CODE: u8 = 4;
+    const DATE_CODE: u8 = 5;
+    const BYTES_CODE: u8 = 6;
+    const IP_CODE: u8 = 7;
+    const JSON_OBJECT_CODE: u8 = 8;
 
     impl BinarySerializable for Value {
         fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
@@ -141,6 +414,31 @@ mod binary_serialize {
                     I64_CODE.serialize(writer)?;
                     val.serialize(writer)
                 }
+                Value::F64(ref val) => {
+                    F64_CODE.serialize(writer)?;
+                    common::f64_to_u64(*val).serialize(writer)
+                }
+                Value::Date(ref date) => {
+                    DATE_CODE.serialize(writer)?;
+                    date.timestamp().serialize(writer)?;
+                    date.timestamp_subsec_nanos().serialize(writer)
+                }
+                Value::Facet(ref facet) => {
+                    FACET_CODE.serialize(writer)?;
+                    facet.serialize(writer)
+                }
+                Value::Bytes(ref bytes) => {
+                    BYTES_CODE.serialize(writer)?;
+                    bytes.serialize(writer)
+                }
+                Value::Ip(ref ip) => {
+                    IP_CODE.serialize(writer)?;
+                    writer.write_all(&ip.octets())
+                }
+                Value::JsonObject(ref json_object) => {
+                    JSON_OBJECT_CODE.serialize(writer)?;
+                    serde_json::to_string(json_object)?.serialize(writer)
+                }
             }
         }
         fn deserialize<R: Read>(reader: &mut R) -> io::Result<Self> {
@@ -158,6 +456,41 @@ mod binary_serialize {
                     let value = i64::deserialize(reader)?;
                     Ok(Value::I64(value))
                 }
+                F64_CODE => {
+                    let value = common::u64_to_f64(u64::deserialize(reader)?);
+                    Ok(Value::F64(value))
+                }
+                DATE_CODE => {
+                    let secs = i64::deserialize(reader)?;
+                    let nanos = u32::deserialize(reader)?;
+                    Utc.timestamp_opt(secs, nanos)
+                        .single()
+                        .map(Value::Date)
+                        .ok_or_else(|| {
+                            io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                format!("Invalid date timestamp {:?}", (secs, nanos)),
+                            )
+                        })
+                }
+                FACET_CODE => {
+                    let facet = Facet::deserialize(reader)?;
+                    Ok(Value::Facet(facet))
+                }
+                BYTES_CODE => {
+                    let bytes = Vec::<u8>::deserialize(reader)?;
+                    Ok(Value::Bytes(bytes))
+                }
+                IP_CODE => {
+                    let mut octets = [0u8; 16];
+                    reader.read_exact(&mut octets)?;
+                    Ok(Value::Ip(Ipv6Addr::from(octets)))
+                }
+                JSON_OBJECT_CODE => {
+                    let json = String::deserialize(reader)?;
+                    let json_object = serde_json::from_str(&json)?;
+                    Ok(Value::JsonObject(json_object))
+                }
                 _ => Err(io::Error::new(
                     io::ErrorKind::InvalidData,
                     format!("No field type is associated with code {:?}", type_code),
@@ -166,3 +499,31 @@ mod binary_serialize {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::Value;
+    use serde_json;
+
+    fn json_value(json: &str) -> Value {
+        Value::JsonObject(serde_json::from_str(json).unwrap())
+    }
+
+    #[test]
+    fn test_json_object_order() {
+        assert_eq!(json_value(r#"{"a": 1, "b": [1, 2]}"#), json_value(r#"{"b": [1, 2], "a": 1}"#));
+        assert!(json_value(r#"{"a": 1}"#) < json_value(r#"{"a": 1, "b": null}"#));
+        assert!(json_value(r#"{"a": 2}"#) < json_value(r#"{"a": 10}"#));
+        assert!(json_value(r#"{"a": -1}"#) < json_value(r#"{"a": 18446744073709551615}"#));
+        assert!(json_value(r#"{"a": 2}"#) < json_value(r#"{"a": 2.0}"#));
+        assert!(json_value(r#"{"a": 2.0}"#) < json_value(r#"{"a": 3}"#));
+        assert_ne!(
+            json_value(r#"{"a": 9007199254740993}"#),
+            json_value(r#"{"a": 9007199254740992}"#)
+        );
+        assert!(json_value(r#"{"a": [1, 2]}"#) < json_value(r#"{"a": [1, 2, 0]}"#));
+        assert!(json_value(r#"{"a": null}"#) < json_value(r#"{"a": false}"#));
+        assert!(json_value(r#"{"a": {"b": 1}}"#) < json_value(r#"{"a": {"c": 0}}"#));
+    }
+}
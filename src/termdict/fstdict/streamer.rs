@@ -2,7 +2,7 @@ use super::TermDictionaryImpl;
 use fst::{IntoStreamer, Streamer};
 use fst::map::{Stream, StreamBuilder};
 use postings::TermInfo;
-use termdict::{TermStreamer, TermStreamerBuilder};
+use termdict::{TermOrdinal, TermStreamer, TermStreamerBuilder};
 
 /// See [`TermStreamerBuilder`](./trait.TermStreamerBuilder.html)
 pub struct TermStreamerBuilderImpl<'a> {
@@ -84,4 +84,8 @@ impl<'a> TermStreamer for TermStreamerImpl<'a> {
     fn value(&self) -> &TermInfo {
         &self.current_value
     }
+
+    fn term_ord(&self) -> TermOrdinal {
+        TermDictionaryImpl::offset_to_ord(self.offset)
+    }
 }
@@ -6,7 +6,7 @@ use fst::raw::Fst;
 use postings::TermInfo;
 use schema::FieldType;
 use std::io::{self, Write};
-use termdict::{TermDictionary, TermDictionaryBuilder};
+use termdict::{TermDictionary, TermDictionaryBuilder, TermOrdinal};
 
 fn convert_fst_error(e: fst::Error) -> io::Error {
     io::Error::new(io::ErrorKind::Other, e)
@@ -91,6 +91,15 @@ pub struct TermDictionaryImpl {
 }
 
 impl TermDictionaryImpl {
+    /// Returns the ordinal of the term whose value is stored
+    /// at the address `offset`.
+    ///
+    /// `TermInfo`s have a fixed size and are stored in the same order
+    /// as the terms.
+    pub(crate) fn offset_to_ord(offset: u64) -> TermOrdinal {
+        offset / (TermInfo::SIZE_IN_BYTES as u64)
+    }
+
     /// Deserialize and returns the value at address `offset`
     pub(crate) fn read_value(&self, offset: u64) -> io::Result<TermInfo> {
         let buffer = self.values_mmap.as_slice();
@@ -127,6 +136,36 @@ impl<'a> TermDictionary<'a> for TermDictionaryImpl {
         })
     }
 
+    fn term_ord<K: AsRef<[u8]>>(&self, key: K) -> Option<TermOrdinal> {
+        self.fst_index.get(key).map(TermDictionaryImpl::offset_to_ord)
+    }
+
+    fn ord_to_term(&self, term_ord: TermOrdinal, bytes: &mut Vec<u8>) -> bool {
+        // The fst outputs are increasing with the keys.
+        // At each node, the transition to follow is therefore the last one
+        // whose output does not exceed the remaining target.
+        bytes.clear();
+        let mut remaining = term_ord * (TermInfo::SIZE_IN_BYTES as u64);
+        let fst = self.fst_index.as_fst();
+        let mut node = fst.root();
+        while remaining != 0 || !node.is_final() {
+            let transition_opt = node.transitions()
+                .take_while(|transition| transition.out.value() <= remaining)
+                .last();
+            match transition_opt {
+                Some(transition) => {
+                    remaining -= transition.out.value();
+                    bytes.push(transition.inp);
+                    node = fst.node(transition.addr);
+                }
+                None => {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
     fn range(&self) -> TermStreamerBuilderImpl {
         TermStreamerBuilderImpl::new(self, self.fst_index.range())
     }
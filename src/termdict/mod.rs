@@ -67,6 +67,10 @@ pub use self::streamdict::{TermDictionaryBuilderImpl, TermDictionaryImpl, TermSt
 mod merger;
 use std::io;
 
+/// Position of a term in the sorted list of terms of a
+/// given field in a segment.
+pub type TermOrdinal = u64;
+
 /// Dictionary associating sorted `&[u8]` to values
 pub trait TermDictionary<'a>
 where Self: Sized {
@@ -79,9 +83,30 @@ where Self: Sized {
     /// Opens a `TermDictionary` given a data source.
     fn from_source(source: ReadOnlySource) -> Self;
 
+    /// Creates an empty term dictionary, which contains no terms.
+    fn empty(field_type: FieldType) -> Self {
+        let term_dictionary_data: Vec<u8> =
+            TermDictionaryBuilderImpl::new(Vec::<u8>::new(), field_type)
+                .expect("Creating a TermDictionaryBuilder in a Vec<u8> should never fail")
+                .finish()
+                .expect("Writing in a Vec<u8> should never fail");
+        let source = ReadOnlySource::from(term_dictionary_data);
+        Self::from_source(source)
+    }
+
     /// Lookups the value corresponding to the key.
     fn get<K: AsRef<[u8]>>(&self, target_key: K) -> Option<TermInfo>;
 
+    /// Returns the ordinal associated to a given term,
+    /// or `None` if the term is not in the dictionary.
+    fn term_ord<K: AsRef<[u8]>>(&self, target_key: K) -> Option<TermOrdinal>;
+
+    /// Fills `bytes` with the term associated to the given ordinal.
+    ///
+    /// Returns false if the ordinal is out of bounds,
+    /// in which case `bytes` is left in an unspecified state.
+    fn ord_to_term(&self, term_ord: TermOrdinal, bytes: &mut Vec<u8>) -> bool;
+
     /// Returns a range builder, to stream all of the terms
     /// within an interval.
     fn range(&'a self) -> Self::StreamBuilder;
@@ -150,6 +175,13 @@ pub trait TermStreamer: Sized {
     /// `V::default()`.
     fn value(&self) -> &TermInfo;
 
+    /// Returns the ordinal of the current term.
+    ///
+    /// Ordinals are consecutive integers starting at 0,
+    /// and follow the order of the terms within the
+    /// term dictionary (not within the stream).
+    fn term_ord(&self) -> TermOrdinal;
+
     /// Return the next `(key, value)` pair.
     fn next(&mut self) -> Option<(Term<&[u8]>, &TermInfo)> {
         if self.advance() {
@@ -251,6 +283,41 @@ mod tests {
         assert!(!stream.advance());
     }
 
+    #[test]
+    fn test_term_ordinals() {
+        let mut directory = RAMDirectory::create();
+        let path = PathBuf::from("TermDictionary");
+        let keys: Vec<String> = (0..BLOCK_SIZE)
+            .map(|i| format!("{:05}", i * 7))
+            .chain(vec!["a".to_string(), "ab".to_string(), "abc".to_string()])
+            .collect();
+        {
+            let write = directory.open_write(&path).unwrap();
+            let field_type = FieldType::Str(TEXT);
+            let mut term_dictionary_builder =
+                TermDictionaryBuilderImpl::new(write, field_type).unwrap();
+            for (i, key) in keys.iter().enumerate() {
+                term_dictionary_builder
+                    .insert(key.as_bytes(), &make_term_info(i as u64))
+                    .unwrap();
+            }
+            term_dictionary_builder.finish().unwrap();
+        }
+        let source = directory.open_read(&path).unwrap();
+        let term_dict: TermDictionaryImpl = TermDictionaryImpl::from_source(source);
+        let mut buffer = Vec::new();
+        for (term_ord, key) in keys.iter().enumerate() {
+            assert_eq!(term_dict.term_ord(key.as_bytes()), Some(term_ord as u64));
+            assert!(term_dict.ord_to_term(term_ord as u64, &mut buffer));
+            assert_eq!(&buffer[..], key.as_bytes());
+        }
+        assert_eq!(term_dict.term_ord("b".as_bytes()), None);
+        assert!(!term_dict.ord_to_term(keys.len() as u64, &mut buffer));
+        let mut stream = term_dict.range().ge("a".as_bytes()).into_stream();
+        assert!(stream.advance());
+        assert_eq!(stream.term_ord(), BLOCK_SIZE as u64);
+    }
+
     #[test]
     fn test_term_iterator() {
         let mut schema_builder = SchemaBuilder::default();
@@ -4,7 +4,7 @@ use super::TermDictionaryImpl;
 use super::delta_encoder::{TermDeltaDecoder, TermInfoDeltaDecoder};
 use postings::TermInfo;
 use std::cmp::max;
-use termdict::{TermStreamer, TermStreamerBuilder};
+use termdict::{TermOrdinal, TermStreamer, TermStreamerBuilder};
 
 fn stream_before<'a>(
     term_dictionary: &'a TermDictionaryImpl,
@@ -12,12 +12,14 @@ fn stream_before<'a>(
     has_positions: bool,
 ) -> TermStreamerImpl<'a>
 {
-    let (prev_key, checkpoint) = term_dictionary.strictly_previous_key(target_key.as_ref());
+    let (prev_key, checkpoint, term_ord) =
+        term_dictionary.strictly_previous_key(target_key.as_ref());
     let stream_data: &'a [u8] = &term_dictionary.stream_data()[checkpoint.stream_offset as usize..];
     TermStreamerImpl {
         cursor: stream_data,
         term_delta_decoder: TermDeltaDecoder::with_previous_term(prev_key),
         term_info_decoder: TermInfoDeltaDecoder::from_checkpoint(&checkpoint, has_positions),
+        next_term_ord: term_ord,
     }
 }
 
@@ -27,6 +29,7 @@ pub struct TermStreamerBuilderImpl<'a> {
     origin: usize,
     offset_from: usize,
     offset_to: usize,
+    term_ord_from: TermOrdinal,
     current_key: Vec<u8>,
     term_info: TermInfo,
     has_positions: bool,
@@ -44,10 +47,12 @@ impl<'a> TermStreamerBuilder for TermStreamerBuilderImpl<'a> {
             self.has_positions,
         );
         let smaller_than = |k: &[u8]| k.lt(target_key);
-        let (offset_before, current_key, term_info) = get_offset(smaller_than, streamer);
+        let (offset_before, current_key, term_info, term_ord) =
+            get_offset(smaller_than, streamer);
         self.current_key = current_key;
         self.term_info = term_info;
         self.offset_from = offset_before - self.origin;
+        self.term_ord_from = term_ord;
         self
     }
 
@@ -60,10 +65,12 @@ impl<'a> TermStreamerBuilder for TermStreamerBuilderImpl<'a> {
             self.has_positions,
         );
         let smaller_than = |k: &[u8]| k.le(target_key);
-        let (offset_before, current_key, term_info) = get_offset(smaller_than, streamer);
+        let (offset_before, current_key, term_info, term_ord) =
+            get_offset(smaller_than, streamer);
         self.current_key = current_key;
         self.term_info = term_info;
         self.offset_from = offset_before - self.origin;
+        self.term_ord_from = term_ord;
         self
     }
 
@@ -76,7 +83,7 @@ impl<'a> TermStreamerBuilder for TermStreamerBuilderImpl<'a> {
             self.has_positions,
         );
         let smaller_than = |k: &[u8]| k.lt(target_key);
-        let (offset_before, _, _) = get_offset(smaller_than, streamer);
+        let (offset_before, _, _, _) = get_offset(smaller_than, streamer);
         self.offset_to = offset_before - self.origin;
         self
     }
@@ -90,7 +97,7 @@ impl<'a> TermStreamerBuilder for TermStreamerBuilderImpl<'a> {
             self.has_positions,
         );
         let smaller_than = |k: &[u8]| k.le(target_key);
-        let (offset_before, _, _) = get_offset(smaller_than, streamer);
+        let (offset_before, _, _, _) = get_offset(smaller_than, streamer);
         self.offset_to = offset_before - self.origin;
         self
     }
@@ -107,6 +114,7 @@ impl<'a> TermStreamerBuilder for TermStreamerBuilderImpl<'a> {
             cursor: &data[start..stop],
             term_delta_decoder,
             term_info_decoder,
+            next_term_ord: self.term_ord_from,
         }
     }
 }
@@ -118,26 +126,29 @@ impl<'a> TermStreamerBuilder for TermStreamerBuilderImpl<'a> {
 ///     - the block start
 ///     - the index within this block
 ///     - the term_buffer state to initialize the block)
+///     - the ordinal of the first term of the stream
 fn get_offset<'a, P: Fn(&[u8]) -> bool>(
     predicate: P,
     mut streamer: TermStreamerImpl<'a>,
-) -> (usize, Vec<u8>, TermInfo)
+) -> (usize, Vec<u8>, TermInfo, TermOrdinal)
 {
     let mut prev: &[u8] = streamer.cursor;
 
     let mut term_info = streamer.value().clone();
     let mut prev_data: Vec<u8> = Vec::from(streamer.term_delta_decoder.term());
+    let mut term_ord = streamer.next_term_ord;
 
     while let Some((iter_key, iter_term_info)) = streamer.next() {
         if !predicate(iter_key.as_ref()) {
-            return (prev.as_ptr() as usize, prev_data, term_info);
+            return (prev.as_ptr() as usize, prev_data, term_info, term_ord);
         }
         prev = streamer.cursor;
         prev_data.clear();
         prev_data.extend_from_slice(iter_key.as_ref());
         term_info = iter_term_info.clone();
+        term_ord += 1;
     }
-    (prev.as_ptr() as usize, prev_data, term_info)
+    (prev.as_ptr() as usize, prev_data, term_info, term_ord)
 }
 
 impl<'a> TermStreamerBuilderImpl<'a> {
@@ -150,6 +161,7 @@ impl<'a> TermStreamerBuilderImpl<'a> {
             origin,
             offset_from: 0,
             offset_to: data.len(),
+            term_ord_from: 0,
             current_key: Vec::with_capacity(300),
             has_positions,
         }
@@ -161,6 +173,7 @@ pub struct TermStreamerImpl<'a> {
     cursor: &'a [u8],
     term_delta_decoder: TermDeltaDecoder,
     term_info_decoder: TermInfoDeltaDecoder,
+    next_term_ord: TermOrdinal,
 }
 
 impl<'a> TermStreamer for TermStreamerImpl<'a> {
@@ -173,6 +186,7 @@ impl<'a> TermStreamer for TermStreamerImpl<'a> {
         cursor = self.term_delta_decoder.decode(code, &cursor[1..]);
         cursor = self.term_info_decoder.decode(code, cursor);
         self.cursor = cursor;
+        self.next_term_ord += 1;
         true
     }
 
@@ -183,4 +197,8 @@ impl<'a> TermStreamer for TermStreamerImpl<'a> {
     fn value(&self) -> &TermInfo {
         &self.term_info_decoder.term_info()
     }
+
+    fn term_ord(&self) -> TermOrdinal {
+        self.next_term_ord - 1
+    }
 }
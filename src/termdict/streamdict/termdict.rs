@@ -14,11 +14,13 @@ use fst::raw::Node;
 use postings::TermInfo;
 use schema::FieldType;
 use std::mem::transmute;
-use termdict::{TermDictionary, TermDictionaryBuilder, TermStreamer};
+use termdict::{TermDictionary, TermDictionaryBuilder, TermOrdinal, TermStreamer};
 use termdict::TermStreamerBuilder;
 
 const PADDING_SIZE: usize = 4;
 const INDEX_INTERVAL: usize = 1024;
+/// Number of bytes used to serialize a `CheckPoint`.
+const CHECKPOINT_NUM_BYTES: usize = 4 + 4 + 4;
 
 fn convert_fst_error(e: fst::Error) -> io::Error {
     io::Error::new(io::ErrorKind::Other, e)
@@ -231,12 +233,20 @@ impl TermDictionaryImpl {
         self.stream_data.as_slice()
     }
 
-    pub(crate) fn strictly_previous_key(&self, key: &[u8]) -> (Vec<u8>, CheckPoint) {
+    /// Returns the last checkpoint strictly before the given key,
+    /// as well as the ordinal of the first term following
+    /// this checkpoint.
+    pub(crate) fn strictly_previous_key(
+        &self,
+        key: &[u8],
+    ) -> (Vec<u8>, CheckPoint, TermOrdinal) {
         let (term, checkpoint_offset) = self.strictly_previous_key_checkpoint_offset(key);
         let mut checkpoint_data = &self.checkpoints_data.as_slice()[checkpoint_offset..];
         let checkpoint =
             CheckPoint::deserialize(&mut checkpoint_data).expect("Checkpoint data is corrupted");
-        (term, checkpoint)
+        // checkpoints are added every `INDEX_INTERVAL` terms.
+        let term_ord = (checkpoint_offset / CHECKPOINT_NUM_BYTES * INDEX_INTERVAL) as TermOrdinal;
+        (term, checkpoint, term_ord)
     }
 
     fn strictly_previous_key_checkpoint_offset(&self, key: &[u8]) -> (Vec<u8>, usize) {
@@ -329,6 +339,29 @@ impl<'a> TermDictionary<'a> for TermDictionaryImpl {
         }
     }
 
+    fn term_ord<K: AsRef<[u8]>>(&self, target_key: K) -> Option<TermOrdinal> {
+        let mut streamer = self.range().ge(&target_key).into_stream();
+        if streamer.advance() && streamer.key() == target_key.as_ref() {
+            Some(streamer.term_ord())
+        } else {
+            None
+        }
+    }
+
+    fn ord_to_term(&self, term_ord: TermOrdinal, bytes: &mut Vec<u8>) -> bool {
+        // The stream dictionary does not support random access.
+        // We need to stream through the terms.
+        let mut streamer = self.stream();
+        while streamer.advance() {
+            if streamer.term_ord() == term_ord {
+                bytes.clear();
+                bytes.extend_from_slice(streamer.key());
+                return true;
+            }
+        }
+        false
+    }
+
     /// Returns a range builder, to stream all of the terms
     /// within an interval.
     fn range(&'a self) -> Self::StreamBuilder {
@@ -0,0 +1,198 @@
+use super::{Token, TokenFilter, TokenStream};
+use std::mem;
+use unicode_normalization::UnicodeNormalization;
+
+/// `AsciiFoldingFilter` normalizes tokens so that different
+/// spellings of a same word end up as the same term.
+///
+/// The token text is normalized in the Unicode NFKC form
+/// (e.g. full-width `ｃａｆｅ` becomes `cafe`, and composed and
+/// decomposed characters are unified) and diacritics are
+/// removed from latin characters, folding them to ASCII where
+/// possible (e.g. `café` becomes `cafe`, `straße` becomes `strasse`).
+///
+/// It does not lowercase the text, and is usually used after
+/// the `LowerCaser`.
+#[derive(Clone, Default)]
+pub struct AsciiFoldingFilter {
+    preserve_original: bool,
+}
+
+impl AsciiFoldingFilter {
+    /// Creates an `AsciiFoldingFilter` that emits both the folded
+    /// token and, if it differs, the original token at the same position.
+    ///
+    /// Exact spellings can then be favored at search time.
+    pub fn preserving_original() -> AsciiFoldingFilter {
+        AsciiFoldingFilter {
+            preserve_original: true,
+        }
+    }
+}
+
+impl<TailTokenStream> TokenFilter<TailTokenStream> for AsciiFoldingFilter
+where TailTokenStream: TokenStream {
+    type ResultTokenStream = AsciiFoldingFilterStream<TailTokenStream>;
+
+    fn name(&self) -> &'static str {
+        "ascii_folding"
+    }
+
+    fn transform(&self, token_stream: TailTokenStream) -> Self::ResultTokenStream {
+        AsciiFoldingFilterStream::wrap(self.preserve_original, token_stream)
+    }
+}
+
+pub struct AsciiFoldingFilterStream<TailTokenStream>
+where TailTokenStream: TokenStream {
+    preserve_original: bool,
+    tail: TailTokenStream,
+    buffer: String,
+    // true if the original text of the current token, stored
+    // in the buffer, remains to be emitted.
+    original_pending: bool,
+}
+
+impl<TailTokenStream> AsciiFoldingFilterStream<TailTokenStream>
+where TailTokenStream: TokenStream {
+    fn wrap(
+        preserve_original: bool,
+        tail: TailTokenStream,
+    ) -> AsciiFoldingFilterStream<TailTokenStream>
+    {
+        AsciiFoldingFilterStream {
+            preserve_original,
+            tail,
+            buffer: String::new(),
+            original_pending: false,
+        }
+    }
+}
+
+impl<TailTokenStream> TokenStream for AsciiFoldingFilterStream<TailTokenStream>
+where TailTokenStream: TokenStream {
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+
+    fn advance(&mut self) -> bool {
+        if self.original_pending {
+            self.original_pending = false;
+            mem::swap(&mut self.tail.token_mut().text, &mut self.buffer);
+            return true;
+        }
+        if !self.tail.advance() {
+            return false;
+        }
+        if self.tail.token().text.is_ascii() {
+            return true;
+        }
+        fold(&self.tail.token().text, &mut self.buffer);
+        if self.buffer != self.tail.token().text {
+            mem::swap(&mut self.tail.token_mut().text, &mut self.buffer);
+            self.original_pending = self.preserve_original;
+        }
+        true
+    }
+}
+
+/// Normalizes `text` in the NFKC form, removing its diacritics, and
+/// writes the result in `output`.
+fn fold(text: &str, output: &mut String) {
+    let without_diacritics: String = text.nfkd()
+        .filter(|&c| !is_diacritic(c))
+        .flat_map(|c| {
+            let (first, second) = fold_char(c);
+            first.into_iter().chain(second)
+        })
+        .collect();
+    output.clear();
+    output.extend(without_diacritics.nfkc());
+}
+
+/// Returns true iff `c` is a combining diacritical mark.
+///
+/// Other combining marks, like the japanese (semi-)voiced sound marks,
+/// are part of the character identity and are kept.
+fn is_diacritic(c: char) -> bool {
+    match c {
+        '\u{300}'...'\u{36F}'
+        | '\u{1AB0}'...'\u{1AFF}'
+        | '\u{1DC0}'...'\u{1DFF}'
+        | '\u{20D0}'...'\u{20FF}'
+        | '\u{FE20}'...'\u{FE2F}' => true,
+        _ => false,
+    }
+}
+
+/// Folds the latin letters that have no canonical decomposition.
+fn fold_char(c: char) -> (Option<char>, Option<char>) {
+    match c {
+        'ß' => (Some('s'), Some('s')),
+        'Æ' => (Some('A'), Some('E')),
+        'æ' => (Some('a'), Some('e')),
+        'Œ' => (Some('O'), Some('E')),
+        'œ' => (Some('o'), Some('e')),
+        'Þ' => (Some('T'), Some('H')),
+        'þ' => (Some('t'), Some('h')),
+        'Ø' => (Some('O'), None),
+        'ø' => (Some('o'), None),
+        'Đ' | 'Ð' => (Some('D'), None),
+        'đ' | 'ð' => (Some('d'), None),
+        'Ł' => (Some('L'), None),
+        'ł' => (Some('l'), None),
+        'Ħ' => (Some('H'), None),
+        'ħ' => (Some('h'), None),
+        'ı' => (Some('i'), None),
+        _ => (Some(c), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AsciiFoldingFilter;
+    use tokenizer::{LowerCaser, SimpleTokenizer, Token, TokenStream, Tokenizer};
+
+    fn folded_tokens(filter: AsciiFoldingFilter, text: &str) -> Vec<(String, usize)> {
+        let tokenizer = SimpleTokenizer.filter(LowerCaser).filter(filter);
+        let mut tokens = vec![];
+        {
+            let mut add_token = |token: &Token| {
+                tokens.push((token.text.clone(), token.position));
+            };
+            tokenizer.token_stream(text).process(&mut add_token);
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_ascii_folding_filter() {
+        let tokens = folded_tokens(
+            AsciiFoldingFilter::default(),
+            "Café Straße ｃａｆｅ łódź 한국 が",
+        );
+        let texts: Vec<&str> = tokens.iter().map(|&(ref text, _)| text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec!["cafe", "strasse", "cafe", "lodz", "한국", "が"]
+        );
+    }
+
+    #[test]
+    fn test_ascii_folding_filter_preserving_original() {
+        let tokens = folded_tokens(AsciiFoldingFilter::preserving_original(), "le café noir");
+        assert_eq!(
+            tokens,
+            vec![
+                ("le".to_string(), 0),
+                ("cafe".to_string(), 1),
+                ("café".to_string(), 1),
+                ("noir".to_string(), 2),
+            ]
+        );
+    }
+}
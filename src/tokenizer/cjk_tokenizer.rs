@@ -0,0 +1,164 @@
+use super::{Token, TokenStream, Tokenizer};
+
+/// Tokenizer for Chinese, Japanese and Korean text.
+///
+/// Runs of CJK characters (Han, Hiragana, Katakana and Hangul)
+/// are chopped into their characters, interleaved with the overlapping
+/// bigrams, e.g. `中华人` becomes `中`, `中华`, `华`, `华人`, `人`.
+/// Single characters make it possible to search for one-character
+/// words, while bigrams make longer words more selective.
+///
+/// Other runs of alphanumeric characters are emitted as words,
+/// and the remaining characters are considered as separators.
+///
+/// Characters and bigrams have consecutive positions, so that phrase
+/// queries can match CJK words of any length.
+#[derive(Clone)]
+pub struct CjkTokenizer;
+
+/// Returns true iff the char belongs to one of the CJK scripts.
+fn is_cjk(c: char) -> bool {
+    match c {
+        '\u{1100}'...'\u{11FF}'   // Hangul Jamo
+        | '\u{3040}'...'\u{309F}' // Hiragana
+        | '\u{30A0}'...'\u{30FF}' // Katakana
+        | '\u{3130}'...'\u{318F}' // Hangul Compatibility Jamo
+        | '\u{31F0}'...'\u{31FF}' // Katakana Phonetic Extensions
+        | '\u{3400}'...'\u{4DBF}' // CJK Unified Ideographs Extension A
+        | '\u{4E00}'...'\u{9FFF}' // CJK Unified Ideographs
+        | '\u{AC00}'...'\u{D7AF}' // Hangul Syllables
+        | '\u{F900}'...'\u{FAFF}' // CJK Compatibility Ideographs
+        | '\u{FF66}'...'\u{FF9F}' // Halfwidth Katakana
+        | '\u{20000}'...'\u{2FA1F}' => true, // CJK Unified Ideographs Extension B to F
+        _ => false,
+    }
+}
+
+pub struct CjkTokenStream<'a> {
+    text: &'a str,
+    // byte offsets of the tokens. Their position is their ordinal.
+    spans: Vec<(usize, usize)>,
+    cursor: usize,
+    token: Token,
+}
+
+impl<'a> Tokenizer<'a> for CjkTokenizer {
+    type TokenStreamImpl = CjkTokenStream<'a>;
+
+    fn name(&self) -> &'static str {
+        "cjk"
+    }
+
+    fn token_stream(&self, text: &'a str) -> Self::TokenStreamImpl {
+        let mut char_offsets: Vec<usize> = text.char_indices().map(|(offset, _)| offset).collect();
+        char_offsets.push(text.len());
+        let chars: Vec<char> = text.chars().collect();
+        let mut spans = vec![];
+        let mut start = 0;
+        while start < chars.len() {
+            let c = chars[start];
+            if is_cjk(c) {
+                let mut end = start + 1;
+                while end < chars.len() && is_cjk(chars[end]) {
+                    end += 1;
+                }
+                for char_ord in start..end {
+                    spans.push((char_offsets[char_ord], char_offsets[char_ord + 1]));
+                    if char_ord + 1 < end {
+                        spans.push((char_offsets[char_ord], char_offsets[char_ord + 2]));
+                    }
+                }
+                start = end;
+            } else if c.is_alphanumeric() {
+                let mut end = start + 1;
+                while end < chars.len() && chars[end].is_alphanumeric() && !is_cjk(chars[end]) {
+                    end += 1;
+                }
+                spans.push((char_offsets[start], char_offsets[end]));
+                start = end;
+            } else {
+                start += 1;
+            }
+        }
+        CjkTokenStream {
+            text,
+            spans,
+            cursor: 0,
+            token: Token::default(),
+        }
+    }
+}
+
+impl<'a> TokenStream for CjkTokenStream<'a> {
+    fn advance(&mut self) -> bool {
+        if self.cursor >= self.spans.len() {
+            return false;
+        }
+        let (offset_from, offset_to) = self.spans[self.cursor];
+        self.token.offset_from = offset_from;
+        self.token.offset_to = offset_to;
+        self.token.position = self.cursor;
+        self.token.text.clear();
+        self.token.text.push_str(&self.text[offset_from..offset_to]);
+        self.cursor += 1;
+        true
+    }
+
+    fn token(&self) -> &Token {
+        &self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CjkTokenizer;
+    use tokenizer::{Token, TokenStream, Tokenizer};
+
+    fn tokens(text: &str) -> Vec<(String, usize, usize, usize)> {
+        let mut tokens = vec![];
+        {
+            let mut add_token = |token: &Token| {
+                tokens.push((
+                    token.text.clone(),
+                    token.offset_from,
+                    token.offset_to,
+                    token.position,
+                ));
+            };
+            CjkTokenizer.token_stream(text).process(&mut add_token);
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_cjk_tokenizer() {
+        assert_eq!(
+            tokens("中华人 v2.0 한국"),
+            vec![
+                ("中".to_string(), 0, 3, 0),
+                ("中华".to_string(), 0, 6, 1),
+                ("华".to_string(), 3, 6, 2),
+                ("华人".to_string(), 3, 9, 3),
+                ("人".to_string(), 6, 9, 4),
+                ("v2".to_string(), 10, 12, 5),
+                ("0".to_string(), 13, 14, 6),
+                ("한".to_string(), 15, 18, 7),
+                ("한국".to_string(), 15, 21, 8),
+                ("국".to_string(), 18, 21, 9),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cjk_tokenizer_mixed_scripts() {
+        let texts: Vec<String> = tokens("我爱Rust!书")
+            .into_iter()
+            .map(|(text, _, _, _)| text)
+            .collect();
+        assert_eq!(texts, vec!["我", "我爱", "爱", "Rust", "书"]);
+    }
+}
@@ -0,0 +1,155 @@
+use super::{Token, TokenFilter, TokenStream};
+use std::cmp;
+
+/// `EdgeNgramFilter` replaces each token by its prefixes
+/// of `min_gram` to `max_gram` characters.
+///
+/// Combined with a `SimpleTokenizer`, it makes it possible to
+/// match partial words, e.g. for search-as-you-type.
+/// Tokens shorter than `min_gram` are removed.
+///
+/// All of the grams of a token keep its position and its `offset_from`.
+#[derive(Clone)]
+pub struct EdgeNgramFilter {
+    min_gram: usize,
+    max_gram: usize,
+}
+
+impl EdgeNgramFilter {
+    /// Creates a new `EdgeNgramFilter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_gram` is 0 or greater than `max_gram`.
+    pub fn new(min_gram: usize, max_gram: usize) -> EdgeNgramFilter {
+        assert!(min_gram > 0, "min_gram must be greater than 0");
+        assert!(
+            min_gram <= max_gram,
+            "min_gram must not be greater than max_gram"
+        );
+        EdgeNgramFilter { min_gram, max_gram }
+    }
+}
+
+impl<TailTokenStream> TokenFilter<TailTokenStream> for EdgeNgramFilter
+where TailTokenStream: TokenStream {
+    type ResultTokenStream = EdgeNgramFilterStream<TailTokenStream>;
+
+    fn name(&self) -> &'static str {
+        "edge_ngram"
+    }
+
+    fn transform(&self, token_stream: TailTokenStream) -> Self::ResultTokenStream {
+        EdgeNgramFilterStream::wrap(self.min_gram, self.max_gram, token_stream)
+    }
+}
+
+pub struct EdgeNgramFilterStream<TailTokenStream>
+where TailTokenStream: TokenStream {
+    min_gram: usize,
+    max_gram: usize,
+    tail: TailTokenStream,
+    // byte offsets of the end of each char of the tail token.
+    char_ends: Vec<usize>,
+    gram_len: usize,
+    token: Token,
+}
+
+impl<TailTokenStream> EdgeNgramFilterStream<TailTokenStream>
+where TailTokenStream: TokenStream {
+    fn wrap(
+        min_gram: usize,
+        max_gram: usize,
+        tail: TailTokenStream,
+    ) -> EdgeNgramFilterStream<TailTokenStream>
+    {
+        EdgeNgramFilterStream {
+            min_gram,
+            max_gram,
+            tail,
+            char_ends: Vec::new(),
+            gram_len: 0,
+            token: Token::default(),
+        }
+    }
+}
+
+impl<TailTokenStream> TokenStream for EdgeNgramFilterStream<TailTokenStream>
+where TailTokenStream: TokenStream {
+    fn token(&self) -> &Token {
+        &self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.token
+    }
+
+    fn advance(&mut self) -> bool {
+        loop {
+            if self.gram_len > 0 && self.gram_len <= cmp::min(self.max_gram, self.char_ends.len())
+            {
+                let gram_end = self.char_ends[self.gram_len - 1];
+                let tail_token = self.tail.token();
+                self.token.text.clear();
+                self.token.text.push_str(&tail_token.text[..gram_end]);
+                self.token.offset_from = tail_token.offset_from;
+                // Filters upstream may have changed the length of the text,
+                // so the gram offsets are kept within the original token.
+                self.token.offset_to =
+                    cmp::min(tail_token.offset_from + gram_end, tail_token.offset_to);
+                self.token.position = tail_token.position;
+                self.gram_len += 1;
+                return true;
+            }
+            if !self.tail.advance() {
+                return false;
+            }
+            self.char_ends.clear();
+            self.char_ends.extend(
+                self.tail
+                    .token()
+                    .text
+                    .char_indices()
+                    .map(|(offset, c)| offset + c.len_utf8()),
+            );
+            self.gram_len = self.min_gram;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EdgeNgramFilter;
+    use tokenizer::{LowerCaser, SimpleTokenizer, Token, TokenStream, Tokenizer};
+
+    #[test]
+    fn test_edge_ngram_filter() {
+        let tokenizer = SimpleTokenizer
+            .filter(LowerCaser)
+            .filter(EdgeNgramFilter::new(2, 4));
+        let mut tokens: Vec<(String, usize, usize, usize)> = vec![];
+        {
+            let mut add_token = |token: &Token| {
+                tokens.push((
+                    token.text.clone(),
+                    token.offset_from,
+                    token.offset_to,
+                    token.position,
+                ));
+            };
+            tokenizer
+                .token_stream("A Quick fox")
+                .process(&mut add_token);
+        }
+        assert_eq!(
+            tokens,
+            vec![
+                ("qu".to_string(), 2, 4, 1),
+                ("qui".to_string(), 2, 5, 1),
+                ("quic".to_string(), 2, 6, 1),
+                ("fo".to_string(), 8, 10, 2),
+                ("fox".to_string(), 8, 11, 2),
+            ]
+        );
+    }
+}
@@ -20,6 +20,10 @@ pub struct JapaneseTokenizerStream {
 impl<'a> Tokenizer<'a> for JapaneseTokenizer {
     type TokenStreamImpl = JapaneseTokenizerStream;
 
+    fn name(&self) -> &'static str {
+        "japanese"
+    }
+
     fn token_stream(&self, text: &'a str) -> Self::TokenStreamImpl {
         let mut tokens = vec![];
         let mut offset_from;
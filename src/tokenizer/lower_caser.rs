@@ -8,6 +8,10 @@ impl<TailTokenStream> TokenFilter<TailTokenStream> for LowerCaser
 where TailTokenStream: TokenStream {
     type ResultTokenStream = LowerCaserTokenStream<TailTokenStream>;
 
+    fn name(&self) -> &'static str {
+        "lower_caser"
+    }
+
     fn transform(&self, token_stream: TailTokenStream) -> Self::ResultTokenStream {
         LowerCaserTokenStream::wrap(token_stream)
     }
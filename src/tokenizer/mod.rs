@@ -44,6 +44,11 @@
 //! It will chop your text on punctuation and whitespaces,
 //! removes tokens that are longer than 40 chars, and lowercase your text.
 //!
+//! ## `unicode`
+//!
+//! Like `default`, but chops the text according to the Unicode word
+//! boundary rules. For instance, `don't` or `3.14` are kept as single tokens.
+//!
 //! ## `raw`
 //! Does not actual tokenizer your text. It keeps it entirely unprocessed.
 //! It can be useful to index uuids, or urls for instance.
@@ -55,6 +60,22 @@
 //! remove their inflection. This tokenizer is slower than the default one,
 //! but is recommended to improve recall.
 //!
+//! ## `fr_stem`, `de_stem`, `es_stem`, ...
+//!
+//! Stemming tokenizers for other languages. On top of stemming, they
+//! remove the stop words of their language (e.g. `le`, `la`, `de` in french).
+//! See [`TokenizerManager`](./struct.TokenizerManager.html) for the complete list.
+//!
+//! ## `ja`
+//!
+//! Segments japanese text using the `tinysegmenter` crate.
+//!
+//! ## `cjk`
+//!
+//! Chops runs of Chinese, Japanese or Korean characters into single
+//! characters and overlapping bigrams, and other runs of alphanumeric
+//! characters into words.
+//! Tokens are lowercased.
 //!
 //! # Custom tokenizers
 //!
@@ -77,6 +98,42 @@
 //! # }
 //! ```
 //!
+//! For autocompletion or substring search, [`NgramTokenizer`](./struct.NgramTokenizer.html)
+//! and [`EdgeNgramFilter`](./struct.EdgeNgramFilter.html) emit partial words.
+//!
+//! ```rust
+//! # extern crate tantivy;
+//!
+//! use tantivy::tokenizer::*;
+//!
+//! # fn main() {
+//! let autocomplete = SimpleTokenizer
+//!     .filter(LowerCaser)
+//!     .filter(EdgeNgramFilter::new(2, 10));
+//! let sku = NgramTokenizer::new(3, 3, false)
+//!     .filter(LowerCaser);
+//! # }
+//! ```
+//!
+//! Log messages or code identifiers may require a custom splitting.
+//! [`RegexTokenizer`](./struct.RegexTokenizer.html) chops the text with a regular
+//! expression, and [`WordDelimiterFilter`](./struct.WordDelimiterFilter.html) splits
+//! tokens on case changes, letter-digit transitions and non-alphanumeric characters
+//! (e.g. `getHTTPResponse2` becomes `get`, `HTTP`, `Response`, `2`).
+//!
+//! ```rust
+//! # extern crate tantivy;
+//!
+//! use tantivy::tokenizer::*;
+//!
+//! # fn main() {
+//! let code = RegexTokenizer::new(r"[\w-]+")
+//!     .unwrap()
+//!     .filter(WordDelimiterFilter::default().preserve_original())
+//!     .filter(LowerCaser);
+//! # }
+//! ```
+//!
 //! Once your tokenizer is defined, you need to
 //! register it with a name in your index's [`TokenizerManager`](./struct.TokenizerManager.html).
 //!
@@ -94,6 +151,33 @@
 //! # }
 //! ```
 //!
+//! Tokenizers registered in code need to be registered again every time
+//! the index is opened. Alternatively, tokenizers can be described with a
+//! [`TokenizerDefinition`](./struct.TokenizerDefinition.html). Such tokenizers
+//! are saved with the index on commit, and registered automatically when
+//! the index is opened.
+//!
+//! ```
+//! # extern crate tantivy;
+//! # use tantivy::schema::SchemaBuilder;
+//! # use tantivy::tokenizer::*;
+//! # use tantivy::Index;
+//! # fn main() {
+//! # let schema = SchemaBuilder::new().build();
+//! let index = Index::create_in_ram(schema);
+//! let definition = TokenizerDefinition {
+//!     tokenizer: BaseTokenizerDefinition::Simple,
+//!     filters: vec![
+//!         TokenFilterDefinition::LowerCaser,
+//!         TokenFilterDefinition::Stemmer { language: Language::German },
+//!     ],
+//! };
+//! index.tokenizers()
+//!      .register_definition("custom_de", definition)
+//!      .unwrap();
+//! # }
+//! ```
+//!
 //! If you built your schema programmatically, a complete example
 //! could like this for instance.
 //!
@@ -133,21 +217,42 @@ mod simple_tokenizer;
 mod lower_caser;
 mod remove_long;
 mod stemmer;
+mod stop_word_filter;
 mod tokenizer_manager;
 mod japanese_tokenizer;
 mod token_stream_chain;
 mod raw_tokenizer;
+mod ngram_tokenizer;
+mod edge_ngram_filter;
+mod ascii_folding_filter;
+mod unicode_tokenizer;
+mod cjk_tokenizer;
+mod synonym_filter;
+mod tokenizer_definition;
+mod regex_tokenizer;
+mod word_delimiter_filter;
 
+pub use self::ascii_folding_filter::AsciiFoldingFilter;
+pub use self::cjk_tokenizer::CjkTokenizer;
+pub use self::edge_ngram_filter::EdgeNgramFilter;
 pub use self::japanese_tokenizer::JapaneseTokenizer;
 pub use self::lower_caser::LowerCaser;
+pub use self::ngram_tokenizer::NgramTokenizer;
 pub use self::raw_tokenizer::RawTokenizer;
+pub use self::regex_tokenizer::RegexTokenizer;
 pub use self::remove_long::RemoveLongFilter;
 pub use self::simple_tokenizer::SimpleTokenizer;
-pub use self::stemmer::Stemmer;
+pub use self::stemmer::{Language, Stemmer};
+pub use self::stop_word_filter::StopWordFilter;
+pub use self::synonym_filter::SynonymFilter;
 pub(crate) use self::token_stream_chain::TokenStreamChain;
 pub use self::tokenizer::{Token, TokenFilter, TokenStream, Tokenizer};
 pub use self::tokenizer::BoxedTokenizer;
-pub use self::tokenizer_manager::TokenizerManager;
+pub use self::tokenizer_definition::{BaseTokenizerDefinition, TokenFilterDefinition,
+                                     TokenizerDefinition};
+pub use self::tokenizer_manager::{Analysis, TokenizerManager};
+pub use self::unicode_tokenizer::UnicodeTokenizer;
+pub use self::word_delimiter_filter::WordDelimiterFilter;
 
 #[cfg(test)]
 mod test {
@@ -192,6 +297,25 @@ mod test {
         assert_eq!(&tokens[3], "payer");
     }
 
+    #[test]
+    fn test_fr_tokenizer() {
+        let tokenizer_manager = TokenizerManager::default();
+        let fr_tokenizer = tokenizer_manager.get("fr_stem").unwrap();
+        let mut tokens: Vec<(String, usize)> = vec![];
+        {
+            let mut add_token = |token: &Token| {
+                tokens.push((token.text.clone(), token.position));
+            };
+            fr_tokenizer
+                .token_stream("Les chevaux mangeaient de l'herbe")
+                .process(&mut add_token);
+        }
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[0], ("cheval".to_string(), 1));
+        assert_eq!(tokens[1], ("mang".to_string(), 2));
+        assert_eq!(tokens[2], ("herb".to_string(), 5));
+    }
+
     #[test]
     fn test_jp_tokenizer() {
         let tokenizer_manager = TokenizerManager::default();
@@ -214,6 +338,54 @@ mod test {
         assert_eq!(&tokens[4], "やばい");
     }
 
+    #[test]
+    fn test_cjk_tokenizer() {
+        let tokenizer_manager = TokenizerManager::default();
+        let cjk_tokenizer = tokenizer_manager.get("cjk").unwrap();
+        let mut tokens: Vec<String> = vec![];
+        {
+            let mut add_token = |token: &Token| {
+                tokens.push(token.text.clone());
+            };
+            cjk_tokenizer
+                .token_stream("Tantivy 搜索")
+                .process(&mut add_token);
+        }
+        assert_eq!(tokens, vec!["tantivy", "搜", "搜索", "索"]);
+    }
+
+    #[test]
+    fn test_analyze() {
+        let tokenizer_manager = TokenizerManager::default();
+        assert!(
+            tokenizer_manager
+                .analyze("en_doesnotexist", "Happy", false)
+                .is_none()
+        );
+        let analysis = tokenizer_manager.analyze("en_stem", "Happy", false).unwrap();
+        assert_eq!(analysis.tokens.len(), 1);
+        assert_eq!(&analysis.tokens[0].text, "happi");
+        assert!(analysis.stages.is_none());
+
+        let analysis = tokenizer_manager.analyze("en_stem", "Happy", true).unwrap();
+        let stages = analysis.stages.unwrap();
+        let stage_texts: Vec<(&str, &str)> = stages
+            .iter()
+            .map(|&(ref name, ref tokens)| (name.as_str(), tokens[0].text.as_str()))
+            .collect();
+        assert_eq!(
+            stage_texts,
+            vec![
+                ("simple", "Happy"),
+                ("remove_long", "Happy"),
+                ("lower_caser", "happy"),
+                ("stemmer", "happi"),
+            ]
+        );
+        assert_eq!(stages[3].1, analysis.tokens);
+        assert_eq!(analysis.tokens[0].offset_to, 5);
+    }
+
     #[test]
     fn test_tokenizer_empty() {
         let tokenizer_manager = TokenizerManager::default();
@@ -0,0 +1,169 @@
+use super::{Token, TokenStream, Tokenizer};
+
+/// Tokenize the text by emitting all of its n-grams, that is all
+/// of its substrings of `min_gram` to `max_gram` characters.
+///
+/// This is useful to search for substrings, e.g. within product codes.
+/// In `prefix_only` mode, only the n-grams starting at the beginning
+/// of the text are emitted, which is handy for autocompletion.
+///
+/// The position of a gram is the index of its first character,
+/// and grams are emitted by increasing position then length.
+///
+/// With `min_gram = 2`, `max_gram = 3`, `hello` is tokenized into
+/// `he`, `hel`, `el`, `ell`, `ll`, `llo`, `lo`.
+#[derive(Clone)]
+pub struct NgramTokenizer {
+    min_gram: usize,
+    max_gram: usize,
+    prefix_only: bool,
+}
+
+impl NgramTokenizer {
+    /// Creates a new `NgramTokenizer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_gram` is 0 or greater than `max_gram`.
+    pub fn new(min_gram: usize, max_gram: usize, prefix_only: bool) -> NgramTokenizer {
+        assert!(min_gram > 0, "min_gram must be greater than 0");
+        assert!(
+            min_gram <= max_gram,
+            "min_gram must not be greater than max_gram"
+        );
+        NgramTokenizer {
+            min_gram,
+            max_gram,
+            prefix_only,
+        }
+    }
+}
+
+pub struct NgramTokenStream<'a> {
+    text: &'a str,
+    // byte offsets of the char boundaries of the text,
+    // including the end of the text.
+    char_offsets: Vec<usize>,
+    min_gram: usize,
+    max_gram: usize,
+    prefix_only: bool,
+    gram_start: usize,
+    gram_len: usize,
+    token: Token,
+}
+
+impl<'a> Tokenizer<'a> for NgramTokenizer {
+    type TokenStreamImpl = NgramTokenStream<'a>;
+
+    fn name(&self) -> &'static str {
+        "ngram"
+    }
+
+    fn token_stream(&self, text: &'a str) -> Self::TokenStreamImpl {
+        let mut char_offsets: Vec<usize> = text.char_indices().map(|(offset, _)| offset).collect();
+        char_offsets.push(text.len());
+        NgramTokenStream {
+            text,
+            char_offsets,
+            min_gram: self.min_gram,
+            max_gram: self.max_gram,
+            prefix_only: self.prefix_only,
+            gram_start: 0,
+            gram_len: self.min_gram,
+            token: Token::default(),
+        }
+    }
+}
+
+impl<'a> TokenStream for NgramTokenStream<'a> {
+    fn advance(&mut self) -> bool {
+        let num_chars = self.char_offsets.len() - 1;
+        loop {
+            let gram_end = self.gram_start + self.gram_len;
+            if self.gram_len <= self.max_gram && gram_end <= num_chars {
+                let offset_from = self.char_offsets[self.gram_start];
+                let offset_to = self.char_offsets[gram_end];
+                self.token.offset_from = offset_from;
+                self.token.offset_to = offset_to;
+                self.token.position = self.gram_start;
+                self.token.text.clear();
+                self.token.text.push_str(&self.text[offset_from..offset_to]);
+                self.gram_len += 1;
+                return true;
+            }
+            if self.prefix_only || self.gram_start + self.min_gram >= num_chars {
+                return false;
+            }
+            self.gram_start += 1;
+            self.gram_len = self.min_gram;
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NgramTokenizer;
+    use tokenizer::{LowerCaser, Token, TokenStream, Tokenizer};
+
+    // (text, offset_from, offset_to, position)
+    fn tokens<'a, T: Tokenizer<'a>>(
+        tokenizer: &T,
+        text: &'a str,
+    ) -> Vec<(String, usize, usize, usize)>
+    {
+        let mut tokens = vec![];
+        tokenizer.token_stream(text).process(&mut |token: &Token| {
+            tokens.push((
+                token.text.clone(),
+                token.offset_from,
+                token.offset_to,
+                token.position,
+            ))
+        });
+        tokens
+    }
+
+    #[test]
+    fn test_ngram_tokenizer() {
+        let texts: Vec<String> = tokens(&NgramTokenizer::new(2, 3, false), "hello")
+            .into_iter()
+            .map(|(text, _, _, _)| text)
+            .collect();
+        assert_eq!(texts, vec!["he", "hel", "el", "ell", "ll", "llo", "lo"]);
+    }
+
+    #[test]
+    fn test_ngram_tokenizer_prefix_only() {
+        let tokenizer = NgramTokenizer::new(1, 3, true).filter(LowerCaser);
+        assert_eq!(
+            tokens(&tokenizer, "Eclair"),
+            vec![
+                ("e".to_string(), 0, 1, 0),
+                ("ec".to_string(), 0, 2, 0),
+                ("ecl".to_string(), 0, 3, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ngram_tokenizer_offsets() {
+        assert_eq!(
+            tokens(&NgramTokenizer::new(2, 2, false), "aéb"),
+            vec![("aé".to_string(), 0, 3, 0), ("éb".to_string(), 1, 4, 1)]
+        );
+    }
+
+    #[test]
+    fn test_ngram_tokenizer_short_text() {
+        assert!(tokens(&NgramTokenizer::new(2, 3, false), "a").is_empty());
+        assert!(tokens(&NgramTokenizer::new(2, 3, false), "").is_empty());
+    }
+}
@@ -12,6 +12,10 @@ pub struct RawTokenStream {
 impl<'a> Tokenizer<'a> for RawTokenizer {
     type TokenStreamImpl = RawTokenStream;
 
+    fn name(&self) -> &'static str {
+        "raw"
+    }
+
     fn token_stream(&self, text: &'a str) -> Self::TokenStreamImpl {
         let token = Token {
             offset_from: 0,
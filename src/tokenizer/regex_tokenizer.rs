@@ -0,0 +1,166 @@
+use super::{Token, TokenStream, Tokenizer};
+use Result;
+use error::ErrorKind;
+use regex::Regex;
+
+/// Tokenize the text with a regular expression.
+///
+/// By default, each match of the regular expression is a token.
+/// For instance, `RegexTokenizer::new(r"\w+")` is roughly equivalent
+/// to the `SimpleTokenizer`.
+///
+/// Alternatively, `RegexTokenizer::split_on` builds a tokenizer for which
+/// the regular expression matches the separators between the tokens.
+/// Empty tokens are dropped.
+#[derive(Clone)]
+pub struct RegexTokenizer {
+    regex: Regex,
+    split: bool,
+}
+
+fn compile(pattern: &str) -> Result<Regex> {
+    match Regex::new(pattern) {
+        Ok(regex) => Ok(regex),
+        Err(err) => bail!(ErrorKind::InvalidArgument(format!(
+            "Invalid tokenizer pattern {:?}: {}",
+            pattern, err
+        ))),
+    }
+}
+
+impl RegexTokenizer {
+    /// Creates a `RegexTokenizer` emitting the matches of `pattern`.
+    ///
+    /// Returns an error if `pattern` is not a valid regular expression.
+    pub fn new(pattern: &str) -> Result<RegexTokenizer> {
+        Ok(RegexTokenizer {
+            regex: compile(pattern)?,
+            split: false,
+        })
+    }
+
+    /// Creates a `RegexTokenizer` splitting the text on the matches of `pattern`.
+    ///
+    /// Returns an error if `pattern` is not a valid regular expression.
+    pub fn split_on(pattern: &str) -> Result<RegexTokenizer> {
+        Ok(RegexTokenizer {
+            regex: compile(pattern)?,
+            split: true,
+        })
+    }
+}
+
+pub struct RegexTokenStream<'a> {
+    text: &'a str,
+    spans: Vec<(usize, usize)>,
+    cursor: usize,
+    token: Token,
+}
+
+impl<'a> Tokenizer<'a> for RegexTokenizer {
+    type TokenStreamImpl = RegexTokenStream<'a>;
+
+    fn name(&self) -> &'static str {
+        "regex"
+    }
+
+    fn token_stream(&self, text: &'a str) -> Self::TokenStreamImpl {
+        let mut spans = vec![];
+        if self.split {
+            let mut offset = 0;
+            for regex_match in self.regex.find_iter(text) {
+                spans.push((offset, regex_match.start()));
+                offset = regex_match.end();
+            }
+            spans.push((offset, text.len()));
+        } else {
+            for regex_match in self.regex.find_iter(text) {
+                spans.push((regex_match.start(), regex_match.end()));
+            }
+        }
+        spans.retain(|&(offset_from, offset_to)| offset_from < offset_to);
+        RegexTokenStream {
+            text,
+            spans,
+            cursor: 0,
+            token: Token::default(),
+        }
+    }
+}
+
+impl<'a> TokenStream for RegexTokenStream<'a> {
+    fn advance(&mut self) -> bool {
+        if self.cursor >= self.spans.len() {
+            return false;
+        }
+        let (offset_from, offset_to) = self.spans[self.cursor];
+        self.token.offset_from = offset_from;
+        self.token.offset_to = offset_to;
+        self.token.position = self.cursor;
+        self.token.text.clear();
+        self.token.text.push_str(&self.text[offset_from..offset_to]);
+        self.cursor += 1;
+        true
+    }
+
+    fn token(&self) -> &Token {
+        &self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RegexTokenizer;
+    use tokenizer::{Token, TokenStream, Tokenizer};
+
+    fn tokens(tokenizer: &RegexTokenizer, text: &str) -> Vec<(String, usize, usize, usize)> {
+        let mut tokens = vec![];
+        {
+            let mut add_token = |token: &Token| {
+                tokens.push((
+                    token.text.clone(),
+                    token.offset_from,
+                    token.offset_to,
+                    token.position,
+                ));
+            };
+            tokenizer.token_stream(text).process(&mut add_token);
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_regex_tokenizer() {
+        let tokenizer = RegexTokenizer::new(r"[A-Z]+-\d+|\w+").unwrap();
+        assert_eq!(
+            tokens(&tokenizer, "Fixed JIRA-123, see logs"),
+            vec![
+                ("Fixed".to_string(), 0, 5, 0),
+                ("JIRA-123".to_string(), 6, 14, 1),
+                ("see".to_string(), 16, 19, 2),
+                ("logs".to_string(), 20, 24, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_regex_tokenizer_split_on() {
+        let tokenizer = RegexTokenizer::split_on(r"\s*\|\s*").unwrap();
+        assert_eq!(
+            tokens(&tokenizer, "ERROR | | db timeout|"),
+            vec![
+                ("ERROR".to_string(), 0, 5, 0),
+                ("db timeout".to_string(), 10, 20, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_regex_tokenizer_invalid_pattern() {
+        assert!(RegexTokenizer::new("(").is_err());
+    }
+}
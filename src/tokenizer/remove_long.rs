@@ -39,6 +39,10 @@ impl<TailTokenStream> TokenFilter<TailTokenStream> for RemoveLongFilter
 where TailTokenStream: TokenStream {
     type ResultTokenStream = RemoveLongFilterStream<TailTokenStream>;
 
+    fn name(&self) -> &'static str {
+        "remove_long"
+    }
+
     fn transform(&self, token_stream: TailTokenStream) -> Self::ResultTokenStream {
         RemoveLongFilterStream::wrap(self.length_limit, token_stream)
     }
@@ -14,6 +14,10 @@ pub struct SimpleTokenStream<'a> {
 impl<'a> Tokenizer<'a> for SimpleTokenizer {
     type TokenStreamImpl = SimpleTokenStream<'a>;
 
+    fn name(&self) -> &'static str {
+        "simple"
+    }
+
     fn token_stream(&self, text: &'a str) -> Self::TokenStreamImpl {
         SimpleTokenStream {
             text,
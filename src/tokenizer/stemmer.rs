@@ -1,17 +1,61 @@
 use super::{Token, TokenFilter, TokenStream};
 use rust_stemmers::{self, Algorithm};
-use std::sync::Arc;
 
+/// Languages supported by the `Stemmer` and the `StopWordFilter`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub enum Language {
+    English,
+    French,
+    German,
+    Italian,
+    Portuguese,
+    Romanian,
+    Russian,
+    Spanish,
+}
+
+impl Language {
+    fn algorithm(&self) -> Algorithm {
+        match *self {
+            Language::English => Algorithm::English,
+            Language::French => Algorithm::French,
+            Language::German => Algorithm::German,
+            Language::Italian => Algorithm::Italian,
+            Language::Portuguese => Algorithm::Portuguese,
+            Language::Romanian => Algorithm::Romanian,
+            Language::Russian => Algorithm::Russian,
+            Language::Spanish => Algorithm::Spanish,
+        }
+    }
+}
+
+/// `Stemmer` token filter.
+///
+/// Stemming consists in trimming words to remove their inflection,
+/// using the snowball stemming algorithm of the given `Language`.
+/// Tokens are expected to be lowercased beforehand.
 #[derive(Clone)]
 pub struct Stemmer {
-    stemmer_algorithm: Arc<Algorithm>,
+    language: Language,
 }
 
 impl Stemmer {
+    /// Creates a new english `Stemmer`.
     pub fn new() -> Stemmer {
-        Stemmer {
-            stemmer_algorithm: Arc::new(Algorithm::English),
-        }
+        Stemmer::for_language(Language::English)
+    }
+
+    /// Creates a new `Stemmer` for the given language.
+    pub fn for_language(language: Language) -> Stemmer {
+        Stemmer { language }
+    }
+}
+
+impl Default for Stemmer {
+    /// Creates an english `Stemmer`.
+    fn default() -> Stemmer {
+        Stemmer::new()
     }
 }
 
@@ -19,8 +63,12 @@ impl<TailTokenStream> TokenFilter<TailTokenStream> for Stemmer
 where TailTokenStream: TokenStream {
     type ResultTokenStream = StemmerTokenStream<TailTokenStream>;
 
+    fn name(&self) -> &'static str {
+        "stemmer"
+    }
+
     fn transform(&self, token_stream: TailTokenStream) -> Self::ResultTokenStream {
-        let inner_stemmer = rust_stemmers::Stemmer::create(Algorithm::English);
+        let inner_stemmer = rust_stemmers::Stemmer::create(self.language.algorithm());
         StemmerTokenStream::wrap(inner_stemmer, token_stream)
     }
 }
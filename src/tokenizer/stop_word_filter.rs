@@ -0,0 +1,202 @@
+use super::{Token, TokenFilter, TokenStream};
+use super::Language;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// `StopWordFilter` removes tokens that belong to a list of
+/// stop words, e.g. very common words like `the` or `of`.
+///
+/// Stop words are matched against the token text as is, so
+/// this filter should come after the `LowerCaser`.
+///
+/// Removed tokens still leave a gap in the token positions,
+/// so that phrase queries keep matching the right sequence of words.
+#[derive(Clone)]
+pub struct StopWordFilter {
+    words: Arc<HashSet<String>>,
+}
+
+impl StopWordFilter {
+    /// Creates a `StopWordFilter` removing the given words.
+    pub fn remove(words: Vec<String>) -> StopWordFilter {
+        StopWordFilter {
+            words: Arc::new(words.into_iter().collect()),
+        }
+    }
+
+    /// Creates a `StopWordFilter` using the built-in list of
+    /// stop words for the given language.
+    pub fn for_language(language: Language) -> StopWordFilter {
+        let words = stop_words(language)
+            .iter()
+            .map(|word| word.to_string())
+            .collect();
+        StopWordFilter::remove(words)
+    }
+}
+
+impl<TailTokenStream> TokenFilter<TailTokenStream> for StopWordFilter
+where TailTokenStream: TokenStream {
+    type ResultTokenStream = StopWordFilterStream<TailTokenStream>;
+
+    fn name(&self) -> &'static str {
+        "stop_words"
+    }
+
+    fn transform(&self, token_stream: TailTokenStream) -> Self::ResultTokenStream {
+        StopWordFilterStream::wrap(self.words.clone(), token_stream)
+    }
+}
+
+pub struct StopWordFilterStream<TailTokenStream>
+where TailTokenStream: TokenStream {
+    words: Arc<HashSet<String>>,
+    tail: TailTokenStream,
+}
+
+impl<TailTokenStream> StopWordFilterStream<TailTokenStream>
+where TailTokenStream: TokenStream {
+    fn predicate(&self, token: &Token) -> bool {
+        !self.words.contains(&token.text)
+    }
+
+    fn wrap(
+        words: Arc<HashSet<String>>,
+        tail: TailTokenStream,
+    ) -> StopWordFilterStream<TailTokenStream>
+    {
+        StopWordFilterStream { words, tail }
+    }
+}
+
+impl<TailTokenStream> TokenStream for StopWordFilterStream<TailTokenStream>
+where TailTokenStream: TokenStream {
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+
+    fn advance(&mut self) -> bool {
+        loop {
+            if self.tail.advance() {
+                if self.predicate(self.tail.token()) {
+                    return true;
+                }
+            } else {
+                return false;
+            }
+        }
+    }
+}
+
+/// Returns the built-in list of stop words for a language.
+///
+/// These lists are deliberately short: they only contain
+/// articles, pronouns, prepositions and auxiliaries.
+fn stop_words(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::English => ENGLISH,
+        Language::French => FRENCH,
+        Language::German => GERMAN,
+        Language::Italian => ITALIAN,
+        Language::Portuguese => PORTUGUESE,
+        Language::Romanian => ROMANIAN,
+        Language::Russian => RUSSIAN,
+        Language::Spanish => SPANISH,
+    }
+}
+
+const ENGLISH: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "if", "in", "into", "is",
+    "it", "no", "not", "of", "on", "or", "such", "that", "the", "their", "then", "there",
+    "these", "they", "this", "to", "was", "will", "with",
+];
+
+const FRENCH: &[&str] = &[
+    "au", "aux", "avec", "ce", "ces", "dans", "de", "des", "du", "elle", "en", "et", "eux",
+    "il", "je", "la", "le", "les", "leur", "lui", "ma", "mais", "me", "même", "mes", "moi",
+    "mon", "ne", "nos", "notre", "nous", "on", "ou", "par", "pas", "pour", "qu", "que", "qui",
+    "sa", "se", "ses", "son", "sur", "ta", "te", "tes", "toi", "ton", "tu", "un", "une", "vos",
+    "votre", "vous", "c", "d", "j", "l", "m", "n", "s", "t", "y", "à", "est", "sont",
+];
+
+const GERMAN: &[&str] = &[
+    "aber", "als", "am", "an", "auch", "auf", "aus", "bei", "bin", "bis", "das", "dass", "dem",
+    "den", "der", "des", "die", "du", "ein", "eine", "einem", "einen", "einer", "eines", "er",
+    "es", "für", "hat", "ich", "ihr", "im", "in", "ist", "mit", "nach", "nicht", "noch", "oder",
+    "sie", "sind", "so", "und", "von", "vor", "war", "wie", "wir", "zu", "zum", "zur",
+];
+
+const ITALIAN: &[&str] = &[
+    "a", "al", "alla", "anche", "che", "ci", "come", "con", "da", "dei", "del", "della", "di",
+    "e", "è", "gli", "ha", "i", "il", "in", "io", "la", "le", "lo", "ma", "mi", "ne", "nel",
+    "non", "per", "più", "se", "si", "sono", "su", "tu", "un", "una", "uno",
+];
+
+const PORTUGUESE: &[&str] = &[
+    "a", "ao", "as", "com", "como", "da", "das", "de", "do", "dos", "e", "é", "ele", "em",
+    "era", "eu", "foi", "isso", "mas", "na", "nas", "no", "nos", "não", "o", "os", "ou",
+    "para", "por", "que", "se", "sem", "seu", "sua", "um", "uma",
+];
+
+const ROMANIAN: &[&str] = &[
+    "a", "al", "ale", "ca", "cu", "de", "din", "după", "este", "eu", "ei", "el", "ea", "în",
+    "la", "mai", "nu", "o", "pe", "pentru", "sau", "se", "și", "un", "una",
+];
+
+const RUSSIAN: &[&str] = &[
+    "а", "без", "был", "была", "были", "было", "в", "вы", "да", "для", "до", "его", "ее",
+    "если", "же", "за", "и", "из", "или", "к", "как", "мы", "на", "не", "но", "о", "он",
+    "она", "они", "от", "по", "с", "так", "то", "у", "что", "это", "я",
+];
+
+const SPANISH: &[&str] = &[
+    "a", "al", "como", "con", "de", "del", "el", "ella", "en", "es", "esta", "este", "ha",
+    "la", "las", "le", "lo", "los", "me", "mi", "no", "o", "para", "pero", "por", "que", "se",
+    "su", "sus", "te", "tu", "un", "una", "y", "yo",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::StopWordFilter;
+    use tokenizer::{Language, LowerCaser, SimpleTokenizer, Token, TokenStream, Tokenizer};
+
+    #[test]
+    fn test_stop_word_filter() {
+        let tokenizer = SimpleTokenizer
+            .filter(LowerCaser)
+            .filter(StopWordFilter::for_language(Language::English));
+        let mut tokens: Vec<(String, usize)> = vec![];
+        {
+            let mut add_token = |token: &Token| {
+                tokens.push((token.text.clone(), token.position));
+            };
+            tokenizer
+                .token_stream("The lord of the rings")
+                .process(&mut add_token);
+        }
+        assert_eq!(
+            tokens,
+            vec![("lord".to_string(), 1), ("rings".to_string(), 4)]
+        );
+    }
+
+    #[test]
+    fn test_custom_stop_word_filter() {
+        let stop_words = vec!["hello".to_string(), "tax".to_string()];
+        let tokenizer = SimpleTokenizer.filter(StopWordFilter::remove(stop_words));
+        let mut tokens: Vec<String> = vec![];
+        {
+            let mut add_token = |token: &Token| {
+                tokens.push(token.text.clone());
+            };
+            tokenizer
+                .token_stream("hello happy tax payer")
+                .process(&mut add_token);
+        }
+        assert_eq!(tokens, vec!["happy".to_string(), "payer".to_string()]);
+    }
+}
@@ -0,0 +1,286 @@
+use super::{Token, TokenFilter, TokenStream};
+use Result;
+use error::ErrorKind;
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// `SynonymFilter` emits the synonyms of the tokens it encounters
+/// at the same position as the original token.
+///
+/// The synonym map associates a word, or a sequence of words separated
+/// by spaces, to its synonyms. For instance with the map
+/// `{"tv": ["television"], "new york": ["big apple"]}`, the text
+/// `tv in new york` is tokenized into `tv`, `television` (at the
+/// same position as `tv`), `in`, `new`, `big` (at the same position
+/// as `new`), `york` and `apple` (at the same position as `york`).
+///
+/// The words of a synonym are stacked on the words of the sequence
+/// it replaces. A synonym must therefore have as many words as the
+/// sequence it replaces, so that the positions of the following tokens,
+/// and hence phrase queries, are not affected.
+///
+/// Words are matched against the token text as is, so this filter
+/// should usually come after the `LowerCaser`.
+///
+/// Applied at query time, the `QueryParser` searches the tokens
+/// stacked at a same position as alternatives.
+#[derive(Clone)]
+pub struct SynonymFilter {
+    synonyms: Arc<HashMap<Vec<String>, Vec<Vec<String>>>>,
+    max_num_words: usize,
+}
+
+fn split_words(text: &str) -> Vec<String> {
+    text.split_whitespace().map(|word| word.to_string()).collect()
+}
+
+impl SynonymFilter {
+    /// Creates a `SynonymFilter` given a map associating words,
+    /// or sequences of words separated by spaces, to their synonyms.
+    ///
+    /// Returns an error if a synonym does not have the same number
+    /// of words as the sequence it replaces.
+    pub fn new(synonyms: HashMap<String, Vec<String>>) -> Result<SynonymFilter> {
+        let mut synonym_map: HashMap<Vec<String>, Vec<Vec<String>>> = HashMap::new();
+        for (words, synonyms) in synonyms {
+            let words = split_words(&words);
+            if words.is_empty() {
+                continue;
+            }
+            let mut word_synonyms = Vec::with_capacity(synonyms.len());
+            for synonym in &synonyms {
+                let synonym = split_words(synonym);
+                if synonym.len() != words.len() {
+                    bail!(ErrorKind::InvalidArgument(format!(
+                        "The synonym {:?} of {:?} does not have the same number of words",
+                        synonym.join(" "),
+                        words.join(" ")
+                    )));
+                }
+                if synonym != words {
+                    word_synonyms.push(synonym);
+                }
+            }
+            synonym_map.insert(words, word_synonyms);
+        }
+        let max_num_words = synonym_map.keys().map(|words| words.len()).max().unwrap_or(0);
+        Ok(SynonymFilter {
+            synonyms: Arc::new(synonym_map),
+            max_num_words,
+        })
+    }
+}
+
+impl<TailTokenStream> TokenFilter<TailTokenStream> for SynonymFilter
+where TailTokenStream: TokenStream {
+    type ResultTokenStream = SynonymFilterStream<TailTokenStream>;
+
+    fn name(&self) -> &'static str {
+        "synonyms"
+    }
+
+    fn transform(&self, token_stream: TailTokenStream) -> Self::ResultTokenStream {
+        SynonymFilterStream::wrap(self.clone(), token_stream)
+    }
+}
+
+/// Since synonyms may span over several tokens, the
+/// `SynonymFilterStream` consumes all of the tokens of its tail
+/// on the first call to `advance`.
+pub struct SynonymFilterStream<TailTokenStream>
+where TailTokenStream: TokenStream {
+    filter: SynonymFilter,
+    tail: TailTokenStream,
+    started: bool,
+    // remaining tokens, in reverse order.
+    tokens: Vec<Token>,
+    token: Token,
+}
+
+impl<TailTokenStream> SynonymFilterStream<TailTokenStream>
+where TailTokenStream: TokenStream {
+    fn wrap(
+        filter: SynonymFilter,
+        tail: TailTokenStream,
+    ) -> SynonymFilterStream<TailTokenStream>
+    {
+        SynonymFilterStream {
+            filter,
+            tail,
+            started: false,
+            tokens: Vec::new(),
+            token: Token::default(),
+        }
+    }
+
+    fn expand_synonyms(&mut self) {
+        let mut input_tokens: Vec<Token> = Vec::new();
+        while self.tail.advance() {
+            let token = self.tail.token();
+            input_tokens.push(Token {
+                offset_from: token.offset_from,
+                offset_to: token.offset_to,
+                position: token.position,
+                text: token.text.clone(),
+            });
+        }
+        let words: Vec<String> = input_tokens
+            .iter()
+            .map(|token| token.text.clone())
+            .collect();
+        let mut synonym_tokens: Vec<Token> = Vec::new();
+        for start in 0..words.len() {
+            let max_num_words = cmp::min(self.filter.max_num_words, words.len() - start);
+            // the longest sequence of words wins.
+            for num_words in (1..max_num_words + 1).rev() {
+                if let Some(synonyms) = self.filter.synonyms.get(&words[start..start + num_words]) {
+                    let replaced_tokens = &input_tokens[start..start + num_words];
+                    for synonym in synonyms {
+                        for (word, token) in synonym.iter().zip(replaced_tokens) {
+                            synonym_tokens.push(Token {
+                                offset_from: token.offset_from,
+                                offset_to: token.offset_to,
+                                position: token.position,
+                                text: word.clone(),
+                            });
+                        }
+                    }
+                    break;
+                }
+            }
+        }
+        // Original tokens come first, then synonyms.
+        // The sort being stable, sorting by position keeps the
+        // original token first among the tokens of a given position.
+        let mut tokens = input_tokens;
+        tokens.extend(synonym_tokens);
+        tokens.sort_by_key(|token| token.position);
+        let mut emitted: HashSet<(usize, String)> = HashSet::new();
+        tokens.retain(|token| emitted.insert((token.position, token.text.clone())));
+        tokens.reverse();
+        self.tokens = tokens;
+    }
+}
+
+impl<TailTokenStream> TokenStream for SynonymFilterStream<TailTokenStream>
+where TailTokenStream: TokenStream {
+    fn token(&self) -> &Token {
+        &self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.token
+    }
+
+    fn advance(&mut self) -> bool {
+        if !self.started {
+            self.started = true;
+            self.expand_synonyms();
+        }
+        if let Some(token) = self.tokens.pop() {
+            self.token = token;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SynonymFilter;
+    use collector::tests::TestCollector;
+    use query::QueryParser;
+    use schema::{IndexRecordOption, SchemaBuilder, TextFieldIndexing, TextOptions};
+    use std::collections::HashMap;
+    use tokenizer::{LowerCaser, SimpleTokenizer, Token, TokenStream, Tokenizer};
+    use Index;
+
+    #[test]
+    fn test_synonym_filter() {
+        let mut synonyms = HashMap::new();
+        synonyms.insert("tv".to_string(), vec!["television".to_string()]);
+        synonyms.insert(
+            "new york".to_string(),
+            vec!["new york".to_string(), "big apple".to_string()],
+        );
+        let tokenizer = SimpleTokenizer
+            .filter(LowerCaser)
+            .filter(SynonymFilter::new(synonyms).unwrap());
+        let mut tokens: Vec<(String, usize, usize, usize)> = vec![];
+        {
+            let mut add_token = |token: &Token| {
+                tokens.push((
+                    token.text.clone(),
+                    token.offset_from,
+                    token.offset_to,
+                    token.position,
+                ));
+            };
+            tokenizer
+                .token_stream("TV in New York")
+                .process(&mut add_token);
+        }
+        assert_eq!(
+            tokens,
+            vec![
+                ("tv".to_string(), 0, 2, 0),
+                ("television".to_string(), 0, 2, 0),
+                ("in".to_string(), 3, 5, 1),
+                ("new".to_string(), 6, 9, 2),
+                ("big".to_string(), 6, 9, 2),
+                ("york".to_string(), 10, 14, 3),
+                ("apple".to_string(), 10, 14, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_synonym_filter_num_words() {
+        let mut synonyms = HashMap::new();
+        synonyms.insert("united states".to_string(), vec!["usa".to_string()]);
+        assert!(SynonymFilter::new(synonyms).is_err());
+    }
+
+    #[test]
+    fn test_synonym_filter_phrase_query() {
+        let mut synonyms = HashMap::new();
+        synonyms.insert("new york".to_string(), vec!["big apple".to_string()]);
+        let mut schema_builder = SchemaBuilder::default();
+        let text_field_indexing = TextFieldIndexing::default()
+            .set_tokenizer("en_synonyms")
+            .set_index_option(IndexRecordOption::WithFreqsAndPositions);
+        let text_options = TextOptions::default().set_indexing_options(text_field_indexing);
+        let text_field = schema_builder.add_text_field("text", text_options);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        index.tokenizers().register(
+            "en_synonyms",
+            SimpleTokenizer
+                .filter(LowerCaser)
+                .filter(SynonymFilter::new(synonyms).unwrap()),
+        );
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 40_000_000).unwrap();
+            index_writer.add_document(doc!(text_field=>"Flights from New York to Paris"));
+            index_writer.add_document(doc!(text_field=>"New flights to York"));
+            assert!(index_writer.commit().is_ok());
+        }
+        index.load_searchers().unwrap();
+        let searcher = index.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![text_field]);
+        let test_query = |query_str: &str| {
+            let query = query_parser.parse_query(query_str).unwrap();
+            let mut test_collector = TestCollector::default();
+            searcher.search(&*query, &mut test_collector).unwrap();
+            test_collector.docs()
+        };
+        assert_eq!(test_query("\"big apple to paris\""), vec![0]);
+        assert_eq!(test_query("\"new york to\""), vec![0]);
+        assert_eq!(test_query("\"from big york\""), vec![0]);
+        assert_eq!(test_query("\"apple to\""), vec![0]);
+        assert_eq!(test_query("\"big apple\""), vec![0]);
+        assert_eq!(test_query("\"to york\""), vec![1]);
+    }
+}
@@ -4,6 +4,7 @@ use std::borrow::{Borrow, BorrowMut};
 use tokenizer::TokenStreamChain;
 
 /// Token
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Token {
     /// Offset (byte index) of the first character of the token.
     /// Offsets shall not be modified by token filters.
@@ -44,6 +45,22 @@ pub trait Tokenizer<'a>: Sized + Clone {
     /// Creates a token stream for a given `str`.
     fn token_stream(&self, text: &'a str) -> Self::TokenStreamImpl;
 
+    /// Name of the tokenizer, used to label its stage
+    /// in `token_stages`.
+    fn name(&self) -> &'static str {
+        "tokenizer"
+    }
+
+    /// Returns the tokens emitted at each stage of the tokenizer,
+    /// labelled with the name of the tokenizer or filter of the stage.
+    ///
+    /// The first stage is the output of the tokenizer itself,
+    /// followed by the output of each of its filters, in order.
+    /// This is mostly useful for debugging.
+    fn token_stages(&self, text: &'a str) -> Vec<(String, Vec<Token>)> {
+        vec![(self.name().to_string(), collect_tokens(self.token_stream(text)))]
+    }
+
     /// Appends a token filter to the current tokenizer.
     ///
     /// The method consumes the current `TokenStream` and returns a
@@ -85,6 +102,11 @@ pub trait BoxedTokenizer: Send + Sync {
     /// to prevent accidental `PhraseQuery` to match accross two terms.
     fn token_stream_texts<'b>(&self, texts: &'b [&'b str]) -> Box<TokenStream + 'b>;
 
+    /// Returns the tokens emitted at each stage of the tokenizer.
+    ///
+    /// See [`Tokenizer::token_stages`](./trait.Tokenizer.html#method.token_stages).
+    fn token_stages(&self, text: &str) -> Vec<(String, Vec<Token>)>;
+
     /// Return a boxed clone of the tokenizer
     fn boxed_clone(&self) -> Box<BoxedTokenizer>;
 }
@@ -116,6 +138,10 @@ where A: 'static + Send + Sync + for<'a> Tokenizer<'a> {
         }
     }
 
+    fn token_stages(&self, text: &str) -> Vec<(String, Vec<Token>)> {
+        self.0.token_stages(text)
+    }
+
     fn boxed_clone(&self) -> Box<BoxedTokenizer> {
         box self.clone()
     }
@@ -236,11 +262,31 @@ where
         let tail_token_stream = self.tail.token_stream(text);
         self.head.transform(tail_token_stream)
     }
+
+    fn token_stages(&self, text: &'a str) -> Vec<(String, Vec<Token>)> {
+        let mut stages = self.tail.token_stages(text);
+        stages.push((self.head.name().to_string(), collect_tokens(self.token_stream(text))));
+        stages
+    }
+}
+
+pub(crate) fn collect_tokens<TStream: TokenStream>(mut token_stream: TStream) -> Vec<Token> {
+    let mut tokens = vec![];
+    while let Some(token) = token_stream.next() {
+        tokens.push(token.clone());
+    }
+    tokens
 }
 
 pub trait TokenFilter<TailTokenStream: TokenStream>: Clone {
     type ResultTokenStream: TokenStream;
 
+    /// Name of the filter, used to label its stage
+    /// in `Tokenizer::token_stages`.
+    fn name(&self) -> &'static str {
+        "filter"
+    }
+
     /// Wraps a token stream and returns the modified one.
     fn transform(&self, token_stream: TailTokenStream) -> Self::ResultTokenStream;
 }
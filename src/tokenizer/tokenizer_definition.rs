@@ -0,0 +1,449 @@
+use super::tokenizer::collect_tokens;
+use super::{AsciiFoldingFilter, CjkTokenizer, EdgeNgramFilter, JapaneseTokenizer, Language,
+            LowerCaser, NgramTokenizer, RawTokenizer, RegexTokenizer, RemoveLongFilter,
+            SimpleTokenizer, Stemmer, StopWordFilter, SynonymFilter, Token, TokenFilter,
+            TokenStream, Tokenizer, UnicodeTokenizer, WordDelimiterFilter};
+use Result;
+use error::ErrorKind;
+use std::collections::BTreeMap;
+
+/// Declarative description of a tokenizer pipeline: a base
+/// tokenizer followed by a list of token filters.
+///
+/// Unlike tokenizers registered in code, tokenizers registered from
+/// their definition with `TokenizerManager::register_definition`
+/// are saved in the index `meta.json` file, and registered
+/// again automatically when the index is opened.
+///
+/// Definitions serialize to JSON as follows.
+///
+/// ```json
+/// {
+///   "tokenizer": {"type": "simple"},
+///   "filters": [
+///     {"type": "remove_long", "limit": 40},
+///     {"type": "lower_caser"},
+///     {"type": "stemmer", "language": "French"}
+///   ]
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TokenizerDefinition {
+    /// Tokenizer chopping the text into tokens.
+    pub tokenizer: BaseTokenizerDefinition,
+    /// Filters applied, in order, to the tokens.
+    #[serde(default)]
+    pub filters: Vec<TokenFilterDefinition>,
+}
+
+/// Definition of the base tokenizer of a `TokenizerDefinition`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BaseTokenizerDefinition {
+    /// See `SimpleTokenizer`.
+    Simple,
+    /// See `RawTokenizer`.
+    Raw,
+    /// See `UnicodeTokenizer`.
+    Unicode {
+        /// Keep URLs as single tokens.
+        #[serde(default)]
+        keep_urls: bool,
+        /// Keep email addresses as single tokens.
+        #[serde(default)]
+        keep_emails: bool,
+        /// Keep numbers with separators as single tokens.
+        #[serde(default)]
+        keep_numbers: bool,
+    },
+    /// See `JapaneseTokenizer`.
+    Japanese,
+    /// See `CjkTokenizer`.
+    Cjk,
+    /// See `NgramTokenizer`.
+    Ngram {
+        /// Minimum number of chars of a gram.
+        min_gram: usize,
+        /// Maximum number of chars of a gram.
+        max_gram: usize,
+        /// Only emit the grams starting at the beginning of the text.
+        #[serde(default)]
+        prefix_only: bool,
+    },
+    /// See `RegexTokenizer`.
+    Regex {
+        /// Regular expression.
+        pattern: String,
+        /// The regular expression matches the separators
+        /// rather than the tokens.
+        #[serde(default)]
+        split: bool,
+    },
+}
+
+/// Definition of a token filter of a `TokenizerDefinition`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TokenFilterDefinition {
+    /// See `LowerCaser`.
+    LowerCaser,
+    /// See `RemoveLongFilter`.
+    RemoveLong {
+        /// Length limit, in bytes.
+        limit: usize,
+    },
+    /// See `Stemmer`.
+    Stemmer {
+        /// Language of the stemmer.
+        language: Language,
+    },
+    /// `StopWordFilter` with the built-in stop words of a language.
+    StopWords {
+        /// Language of the stop words.
+        language: Language,
+    },
+    /// `StopWordFilter` with a custom list of stop words.
+    CustomStopWords {
+        /// Stop words.
+        words: Vec<String>,
+    },
+    /// See `AsciiFoldingFilter`.
+    AsciiFolding {
+        /// Also emit the original token.
+        #[serde(default)]
+        preserve_original: bool,
+    },
+    /// See `EdgeNgramFilter`.
+    EdgeNgram {
+        /// Minimum number of chars of a gram.
+        min_gram: usize,
+        /// Maximum number of chars of a gram.
+        max_gram: usize,
+    },
+    /// See `SynonymFilter`.
+    Synonyms {
+        /// Synonym map.
+        synonyms: BTreeMap<String, Vec<String>>,
+    },
+    /// See `WordDelimiterFilter`.
+    WordDelimiter {
+        /// Also emit the original token.
+        #[serde(default)]
+        preserve_original: bool,
+        /// Also emit the catenation of the subwords.
+        #[serde(default)]
+        catenate_all: bool,
+    },
+}
+
+impl BaseTokenizerDefinition {
+    fn name(&self) -> &'static str {
+        match *self {
+            BaseTokenizerDefinition::Simple => "simple",
+            BaseTokenizerDefinition::Raw => "raw",
+            BaseTokenizerDefinition::Unicode { .. } => "unicode",
+            BaseTokenizerDefinition::Japanese => "japanese",
+            BaseTokenizerDefinition::Cjk => "cjk",
+            BaseTokenizerDefinition::Ngram { .. } => "ngram",
+            BaseTokenizerDefinition::Regex { .. } => "regex",
+        }
+    }
+}
+
+impl TokenFilterDefinition {
+    fn name(&self) -> &'static str {
+        match *self {
+            TokenFilterDefinition::LowerCaser => "lower_caser",
+            TokenFilterDefinition::RemoveLong { .. } => "remove_long",
+            TokenFilterDefinition::Stemmer { .. } => "stemmer",
+            TokenFilterDefinition::StopWords { .. } => "stop_words",
+            TokenFilterDefinition::CustomStopWords { .. } => "custom_stop_words",
+            TokenFilterDefinition::AsciiFolding { .. } => "ascii_folding",
+            TokenFilterDefinition::EdgeNgram { .. } => "edge_ngram",
+            TokenFilterDefinition::Synonyms { .. } => "synonyms",
+            TokenFilterDefinition::WordDelimiter { .. } => "word_delimiter",
+        }
+    }
+}
+
+fn check_gram_range(min_gram: usize, max_gram: usize) -> Result<()> {
+    if min_gram == 0 || min_gram > max_gram {
+        bail!(ErrorKind::InvalidArgument(format!(
+            "Invalid gram range [{}, {}]",
+            min_gram, max_gram
+        )));
+    }
+    Ok(())
+}
+
+impl TokenizerDefinition {
+    pub(crate) fn build(&self) -> Result<PipelineTokenizer> {
+        let tokenizer = match self.tokenizer {
+            BaseTokenizerDefinition::Simple => BaseTokenizer::Simple(SimpleTokenizer),
+            BaseTokenizerDefinition::Raw => BaseTokenizer::Raw(RawTokenizer),
+            BaseTokenizerDefinition::Unicode {
+                keep_urls,
+                keep_emails,
+                keep_numbers,
+            } => {
+                let mut tokenizer = UnicodeTokenizer::default();
+                if keep_urls {
+                    tokenizer = tokenizer.keep_urls();
+                }
+                if keep_emails {
+                    tokenizer = tokenizer.keep_emails();
+                }
+                if keep_numbers {
+                    tokenizer = tokenizer.keep_numbers();
+                }
+                BaseTokenizer::Unicode(tokenizer)
+            }
+            BaseTokenizerDefinition::Japanese => BaseTokenizer::Japanese(JapaneseTokenizer),
+            BaseTokenizerDefinition::Cjk => BaseTokenizer::Cjk(CjkTokenizer),
+            BaseTokenizerDefinition::Ngram {
+                min_gram,
+                max_gram,
+                prefix_only,
+            } => {
+                check_gram_range(min_gram, max_gram)?;
+                BaseTokenizer::Ngram(NgramTokenizer::new(min_gram, max_gram, prefix_only))
+            }
+            BaseTokenizerDefinition::Regex { ref pattern, split } => {
+                if split {
+                    BaseTokenizer::Regex(RegexTokenizer::split_on(pattern)?)
+                } else {
+                    BaseTokenizer::Regex(RegexTokenizer::new(pattern)?)
+                }
+            }
+        };
+        let mut filters = Vec::with_capacity(self.filters.len());
+        for filter_definition in &self.filters {
+            let filter = match *filter_definition {
+                TokenFilterDefinition::LowerCaser => Filter::LowerCaser(LowerCaser),
+                TokenFilterDefinition::RemoveLong { limit } => {
+                    Filter::RemoveLong(RemoveLongFilter::limit(limit))
+                }
+                TokenFilterDefinition::Stemmer { language } => {
+                    Filter::Stemmer(Stemmer::for_language(language))
+                }
+                TokenFilterDefinition::StopWords { language } => {
+                    Filter::StopWords(StopWordFilter::for_language(language))
+                }
+                TokenFilterDefinition::CustomStopWords { ref words } => {
+                    Filter::StopWords(StopWordFilter::remove(words.clone()))
+                }
+                TokenFilterDefinition::AsciiFolding { preserve_original } => {
+                    if preserve_original {
+                        Filter::AsciiFolding(AsciiFoldingFilter::preserving_original())
+                    } else {
+                        Filter::AsciiFolding(AsciiFoldingFilter::default())
+                    }
+                }
+                TokenFilterDefinition::EdgeNgram { min_gram, max_gram } => {
+                    check_gram_range(min_gram, max_gram)?;
+                    Filter::EdgeNgram(EdgeNgramFilter::new(min_gram, max_gram))
+                }
+                TokenFilterDefinition::Synonyms { ref synonyms } => {
+                    let synonyms = synonyms
+                        .iter()
+                        .map(|(words, synonyms)| (words.clone(), synonyms.clone()))
+                        .collect();
+                    Filter::Synonyms(SynonymFilter::new(synonyms)?)
+                }
+                TokenFilterDefinition::WordDelimiter {
+                    preserve_original,
+                    catenate_all,
+                } => {
+                    let mut filter = WordDelimiterFilter::default();
+                    if preserve_original {
+                        filter = filter.preserve_original();
+                    }
+                    if catenate_all {
+                        filter = filter.catenate_all();
+                    }
+                    Filter::WordDelimiter(filter)
+                }
+            };
+            filters.push(filter);
+        }
+        let mut stage_names = vec![self.tokenizer.name()];
+        stage_names.extend(self.filters.iter().map(|filter| filter.name()));
+        Ok(PipelineTokenizer {
+            tokenizer,
+            filters,
+            stage_names,
+        })
+    }
+}
+
+#[derive(Clone)]
+enum BaseTokenizer {
+    Simple(SimpleTokenizer),
+    Raw(RawTokenizer),
+    Unicode(UnicodeTokenizer),
+    Japanese(JapaneseTokenizer),
+    Cjk(CjkTokenizer),
+    Ngram(NgramTokenizer),
+    Regex(RegexTokenizer),
+}
+
+#[derive(Clone)]
+enum Filter {
+    LowerCaser(LowerCaser),
+    RemoveLong(RemoveLongFilter),
+    Stemmer(Stemmer),
+    StopWords(StopWordFilter),
+    AsciiFolding(AsciiFoldingFilter),
+    EdgeNgram(EdgeNgramFilter),
+    Synonyms(SynonymFilter),
+    WordDelimiter(WordDelimiterFilter),
+}
+
+impl Filter {
+    fn transform<'a>(&self, token_stream: Box<TokenStream + 'a>) -> Box<TokenStream + 'a> {
+        match *self {
+            Filter::LowerCaser(ref filter) => box filter.transform(token_stream),
+            Filter::RemoveLong(ref filter) => box filter.transform(token_stream),
+            Filter::Stemmer(ref filter) => box filter.transform(token_stream),
+            Filter::StopWords(ref filter) => box filter.transform(token_stream),
+            Filter::AsciiFolding(ref filter) => box filter.transform(token_stream),
+            Filter::EdgeNgram(ref filter) => box filter.transform(token_stream),
+            Filter::Synonyms(ref filter) => box filter.transform(token_stream),
+            Filter::WordDelimiter(ref filter) => box filter.transform(token_stream),
+        }
+    }
+}
+
+/// Tokenizer built from a `TokenizerDefinition`.
+#[derive(Clone)]
+pub(crate) struct PipelineTokenizer {
+    tokenizer: BaseTokenizer,
+    filters: Vec<Filter>,
+    // names of the tokenizer and of the filters in the definition.
+    stage_names: Vec<&'static str>,
+}
+
+impl PipelineTokenizer {
+    // Returns the token stream obtained by applying
+    // the `num_filters` first filters.
+    fn partial_token_stream<'a>(
+        &self,
+        text: &'a str,
+        num_filters: usize,
+    ) -> Box<TokenStream + 'a>
+    {
+        let mut token_stream: Box<TokenStream + 'a> = match self.tokenizer {
+            BaseTokenizer::Simple(ref tokenizer) => box tokenizer.token_stream(text),
+            BaseTokenizer::Raw(ref tokenizer) => box tokenizer.token_stream(text),
+            BaseTokenizer::Unicode(ref tokenizer) => box tokenizer.token_stream(text),
+            BaseTokenizer::Japanese(ref tokenizer) => box tokenizer.token_stream(text),
+            BaseTokenizer::Cjk(ref tokenizer) => box tokenizer.token_stream(text),
+            BaseTokenizer::Ngram(ref tokenizer) => box tokenizer.token_stream(text),
+            BaseTokenizer::Regex(ref tokenizer) => box tokenizer.token_stream(text),
+        };
+        for filter in &self.filters[..num_filters] {
+            token_stream = filter.transform(token_stream);
+        }
+        token_stream
+    }
+}
+
+impl<'a> Tokenizer<'a> for PipelineTokenizer {
+    type TokenStreamImpl = Box<TokenStream + 'a>;
+
+    fn token_stream(&self, text: &'a str) -> Self::TokenStreamImpl {
+        self.partial_token_stream(text, self.filters.len())
+    }
+
+    fn token_stages(&self, text: &'a str) -> Vec<(String, Vec<Token>)> {
+        self.stage_names
+            .iter()
+            .enumerate()
+            .map(|(num_filters, name)| {
+                let tokens = collect_tokens(self.partial_token_stream(text, num_filters));
+                (name.to_string(), tokens)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BaseTokenizerDefinition, TokenFilterDefinition, TokenizerDefinition};
+    use serde_json;
+    use tokenizer::{Language, Token, TokenStream, Tokenizer};
+
+    #[test]
+    fn test_tokenizer_definition() {
+        let json = r#"{
+            "tokenizer": {"type": "simple"},
+            "filters": [
+                {"type": "lower_caser"},
+                {"type": "stop_words", "language": "French"},
+                {"type": "stemmer", "language": "French"}
+            ]
+        }"#;
+        let definition: TokenizerDefinition = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            definition,
+            TokenizerDefinition {
+                tokenizer: BaseTokenizerDefinition::Simple,
+                filters: vec![
+                    TokenFilterDefinition::LowerCaser,
+                    TokenFilterDefinition::StopWords {
+                        language: Language::French,
+                    },
+                    TokenFilterDefinition::Stemmer {
+                        language: Language::French,
+                    },
+                ],
+            }
+        );
+        let tokenizer = definition.build().unwrap();
+        let mut tokens: Vec<String> = vec![];
+        {
+            let mut add_token = |token: &Token| {
+                tokens.push(token.text.clone());
+            };
+            tokenizer
+                .token_stream("Les chevaux")
+                .process(&mut add_token);
+        }
+        assert_eq!(tokens, vec!["cheval"]);
+        let stages: Vec<(String, Vec<String>)> = tokenizer
+            .token_stages("Les chevaux")
+            .into_iter()
+            .map(|(name, tokens)| (name, tokens.into_iter().map(|token| token.text).collect()))
+            .collect();
+        assert_eq!(
+            stages,
+            vec![
+                ("simple".to_string(), vec!["Les".to_string(), "chevaux".to_string()]),
+                ("lower_caser".to_string(), vec!["les".to_string(), "chevaux".to_string()]),
+                ("stop_words".to_string(), vec!["chevaux".to_string()]),
+                ("stemmer".to_string(), vec!["cheval".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenizer_definition_invalid() {
+        let definition = TokenizerDefinition {
+            tokenizer: BaseTokenizerDefinition::Ngram {
+                min_gram: 3,
+                max_gram: 2,
+                prefix_only: false,
+            },
+            filters: vec![],
+        };
+        assert!(definition.build().is_err());
+        let definition = TokenizerDefinition {
+            tokenizer: BaseTokenizerDefinition::Regex {
+                pattern: "(".to_string(),
+                split: false,
+            },
+            filters: vec![],
+        };
+        assert!(definition.build().is_err());
+    }
+}
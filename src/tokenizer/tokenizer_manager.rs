@@ -1,15 +1,32 @@
-use std::collections::HashMap;
+use Result;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, RwLock};
 use tokenizer::BoxedTokenizer;
+use tokenizer::CjkTokenizer;
 use tokenizer::JapaneseTokenizer;
+use tokenizer::Language;
 use tokenizer::LowerCaser;
 use tokenizer::RawTokenizer;
 use tokenizer::RemoveLongFilter;
 use tokenizer::SimpleTokenizer;
 use tokenizer::Stemmer;
+use tokenizer::StopWordFilter;
+use tokenizer::Token;
 use tokenizer::Tokenizer;
+use tokenizer::TokenizerDefinition;
+use tokenizer::UnicodeTokenizer;
 use tokenizer::tokenizer::box_tokenizer;
 
+const LANGUAGE_ANALYZERS: &[(&str, Language)] = &[
+    ("de_stem", Language::German),
+    ("es_stem", Language::Spanish),
+    ("fr_stem", Language::French),
+    ("it_stem", Language::Italian),
+    ("pt_stem", Language::Portuguese),
+    ("ro_stem", Language::Romanian),
+    ("ru_stem", Language::Russian),
+];
+
 /// The tokenizer manager serves as a store for
 /// all of the pre-configured tokenizer pipelines.
 ///
@@ -19,24 +36,74 @@ use tokenizer::tokenizer::box_tokenizer;
 ///  * `default` : Chops the text on according to whitespace and
 ///  punctuation, removes tokens that are too long, and lowercases
 ///  tokens
+///  * `unicode` : Like `default`, but chops the text according to the
+///  Unicode word boundary rules (UAX #29).
 ///  * `en_stem` : Like `default`, but also applies stemming on the
 ///  resulting tokens. Stemming can improve the recall of your
 ///  search engine.
+///  * `de_stem`, `es_stem`, `fr_stem`, `it_stem`, `pt_stem`, `ro_stem`,
+///  `ru_stem` : Like `en_stem`, for the other supported languages.
+///  These analyzers also remove the language stop words.
+///  * `ja` : Segments japanese text.
+///  * `cjk` : Chops chinese, japanese and korean text into single
+///  characters and overlapping bigrams, and lowercases the other tokens.
+///
+/// Tokenizers registered from a `TokenizerDefinition` are
+/// persisted with the index.
 #[derive(Clone)]
 pub struct TokenizerManager {
     tokenizers: Arc<RwLock<HashMap<String, Box<BoxedTokenizer>>>>,
+    definitions: Arc<RwLock<BTreeMap<String, TokenizerDefinition>>>,
 }
 
 impl TokenizerManager {
     pub fn register<A>(&self, tokenizer_name: &str, tokenizer: A)
     where A: 'static + Send + Sync + for<'a> Tokenizer<'a> {
-        let boxed_tokenizer = box_tokenizer(tokenizer);
+        self.definitions
+            .write()
+            .expect("Acquiring the lock should never fail")
+            .remove(tokenizer_name);
+        self.register_boxed(tokenizer_name, box_tokenizer(tokenizer));
+    }
+
+    fn register_boxed(&self, tokenizer_name: &str, boxed_tokenizer: Box<BoxedTokenizer>) {
         self.tokenizers
             .write()
             .expect("Acquiring the lock should never fail")
             .insert(tokenizer_name.to_string(), boxed_tokenizer);
     }
 
+    /// Registers the tokenizer described by `definition`.
+    ///
+    /// Unlike tokenizers registered with `.register(...)`, its definition
+    /// is saved in the index `meta.json` file on the next commit, and the
+    /// tokenizer is registered automatically when the index is opened.
+    ///
+    /// Returns an error if the definition is invalid.
+    pub fn register_definition(
+        &self,
+        tokenizer_name: &str,
+        definition: TokenizerDefinition,
+    ) -> Result<()>
+    {
+        let boxed_tokenizer = box_tokenizer(definition.build()?);
+        self.definitions
+            .write()
+            .expect("Acquiring the lock should never fail")
+            .insert(tokenizer_name.to_string(), definition);
+        self.register_boxed(tokenizer_name, boxed_tokenizer);
+        Ok(())
+    }
+
+    /// Returns the definitions of the tokenizers registered
+    /// with `.register_definition(...)`.
+    pub fn definitions(&self) -> BTreeMap<String, TokenizerDefinition> {
+        self.definitions
+            .read()
+            .expect("Acquiring the lock should never fail")
+            .clone()
+    }
+
     pub fn get(&self, tokenizer_name: &str) -> Option<Box<BoxedTokenizer>> {
         self.tokenizers
             .read()
@@ -44,17 +111,67 @@ impl TokenizerManager {
             .get(tokenizer_name)
             .map(|boxed_tokenizer| boxed_tokenizer.boxed_clone())
     }
+
+    /// Runs the tokenizer registered under `tokenizer_name` on `text`,
+    /// and returns the resulting tokens.
+    ///
+    /// If `with_stages` is true, the tokens emitted by each stage of
+    /// the tokenizer are returned as well.
+    ///
+    /// Returns `None` if no tokenizer is registered under this name.
+    pub fn analyze(&self, tokenizer_name: &str, text: &str, with_stages: bool) -> Option<Analysis> {
+        self.get(tokenizer_name).map(|tokenizer| {
+            if with_stages {
+                let stages = tokenizer.token_stages(text);
+                let tokens = stages
+                    .last()
+                    .map(|&(_, ref tokens)| tokens.clone())
+                    .unwrap_or_else(Vec::new);
+                Analysis {
+                    tokens,
+                    stages: Some(stages),
+                }
+            } else {
+                let mut tokens = vec![];
+                tokenizer
+                    .token_stream(text)
+                    .process(&mut |token| tokens.push(token.clone()));
+                Analysis {
+                    tokens,
+                    stages: None,
+                }
+            }
+        })
+    }
+}
+
+/// Tokens produced by a tokenizer for a given text.
+///
+/// See [`TokenizerManager::analyze`](./struct.TokenizerManager.html#method.analyze).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Analysis {
+    /// Tokens emitted by the tokenizer.
+    pub tokens: Vec<Token>,
+    /// Tokens emitted by each stage of the tokenizer, if they were requested,
+    /// labelled with the name of the tokenizer or filter of the stage.
+    /// The first stage is the tokenizer itself, followed by its filters, in order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stages: Option<Vec<(String, Vec<Token>)>>,
 }
 
 impl Default for TokenizerManager {
     /// Creates an `TokenizerManager` prepopulated with
     /// the default pre-configured tokenizers of `tantivy`.
-    /// - simple
-    /// - en_stem
+    /// - raw
+    /// - default
+    /// - unicode
+    /// - en_stem, and the other language stemming analyzers
     /// - ja
+    /// - cjk
     fn default() -> TokenizerManager {
         let manager = TokenizerManager {
             tokenizers: Arc::new(RwLock::new(HashMap::new())),
+            definitions: Arc::new(RwLock::new(BTreeMap::new())),
         };
         manager.register("raw", RawTokenizer);
         manager.register(
@@ -63,6 +180,12 @@ impl Default for TokenizerManager {
                 .filter(RemoveLongFilter::limit(40))
                 .filter(LowerCaser),
         );
+        manager.register(
+            "unicode",
+            UnicodeTokenizer::default()
+                .filter(RemoveLongFilter::limit(40))
+                .filter(LowerCaser),
+        );
         manager.register(
             "en_stem",
             SimpleTokenizer
@@ -70,7 +193,23 @@ impl Default for TokenizerManager {
                 .filter(LowerCaser)
                 .filter(Stemmer::new()),
         );
+        for &(tokenizer_name, language) in LANGUAGE_ANALYZERS {
+            manager.register(
+                tokenizer_name,
+                SimpleTokenizer
+                    .filter(RemoveLongFilter::limit(40))
+                    .filter(LowerCaser)
+                    .filter(StopWordFilter::for_language(language))
+                    .filter(Stemmer::for_language(language)),
+            );
+        }
         manager.register("ja", JapaneseTokenizer.filter(RemoveLongFilter::limit(40)));
+        manager.register(
+            "cjk",
+            CjkTokenizer
+                .filter(RemoveLongFilter::limit(40))
+                .filter(LowerCaser),
+        );
         manager
     }
 }
@@ -0,0 +1,195 @@
+use super::{Token, TokenStream, Tokenizer};
+use regex::Regex;
+use unicode_segmentation::UnicodeSegmentation;
+
+const URL_PATTERN: &str = r#"(?:https?|ftp)://[^\s<>"]*[^\s<>"'.,;:!?)\]]"#;
+const EMAIL_PATTERN: &str = r"[\w.+-]+@\w[\w-]*(?:\.\w[\w-]*)+";
+const NUMBER_PATTERN: &str = r"\d+(?:[-./:]\d+)+";
+
+/// Tokenize the text by following the Unicode word boundary
+/// rules ([UAX #29](http://www.unicode.org/reports/tr29/)).
+///
+/// Unlike the `SimpleTokenizer`, words like `don't` and decimal
+/// numbers like `3.14` or `1,000` are kept as single tokens.
+/// Segments that do not contain any alphanumeric character
+/// (whitespaces, punctuation) are dropped.
+///
+/// Optionally, URLs, emails, and numbers containing separators
+/// (phone numbers, dates...) can be kept as single tokens.
+///
+/// Note that scripts that do not separate words with spaces
+/// (e.g. Thai or Japanese) require a dictionary-based tokenizer.
+#[derive(Clone, Default)]
+pub struct UnicodeTokenizer {
+    keep_urls: bool,
+    keep_emails: bool,
+    keep_numbers: bool,
+    // regex matching the segments that should be kept as single tokens.
+    regex: Option<Regex>,
+}
+
+impl UnicodeTokenizer {
+    /// Keep URLs (e.g. `https://example.com/index.html`) as single tokens.
+    pub fn keep_urls(mut self) -> UnicodeTokenizer {
+        self.keep_urls = true;
+        self.build_regex()
+    }
+
+    /// Keep email addresses (e.g. `john.doe@example.com`) as single tokens.
+    pub fn keep_emails(mut self) -> UnicodeTokenizer {
+        self.keep_emails = true;
+        self.build_regex()
+    }
+
+    /// Keep numbers with separators (e.g. `555-1234`, `2018/01/30`)
+    /// as single tokens.
+    pub fn keep_numbers(mut self) -> UnicodeTokenizer {
+        self.keep_numbers = true;
+        self.build_regex()
+    }
+
+    fn build_regex(mut self) -> UnicodeTokenizer {
+        let mut patterns = vec![];
+        if self.keep_urls {
+            patterns.push(URL_PATTERN);
+        }
+        if self.keep_emails {
+            patterns.push(EMAIL_PATTERN);
+        }
+        if self.keep_numbers {
+            patterns.push(NUMBER_PATTERN);
+        }
+        let pattern = patterns.join("|");
+        self.regex = Some(Regex::new(&pattern).expect("Tokenizer patterns should be valid"));
+        self
+    }
+}
+
+fn push_words(text: &str, offset: usize, spans: &mut Vec<(usize, usize)>) {
+    for (word_offset, word) in text.split_word_bound_indices() {
+        if word.chars().any(char::is_alphanumeric) {
+            let offset_from = offset + word_offset;
+            spans.push((offset_from, offset_from + word.len()));
+        }
+    }
+}
+
+pub struct UnicodeTokenStream<'a> {
+    text: &'a str,
+    spans: Vec<(usize, usize)>,
+    cursor: usize,
+    token: Token,
+}
+
+impl<'a> Tokenizer<'a> for UnicodeTokenizer {
+    type TokenStreamImpl = UnicodeTokenStream<'a>;
+
+    fn name(&self) -> &'static str {
+        "unicode"
+    }
+
+    fn token_stream(&self, text: &'a str) -> Self::TokenStreamImpl {
+        let mut spans = vec![];
+        let mut offset = 0;
+        if let Some(ref regex) = self.regex {
+            for regex_match in regex.find_iter(text) {
+                push_words(&text[offset..regex_match.start()], offset, &mut spans);
+                spans.push((regex_match.start(), regex_match.end()));
+                offset = regex_match.end();
+            }
+        }
+        push_words(&text[offset..], offset, &mut spans);
+        UnicodeTokenStream {
+            text,
+            spans,
+            cursor: 0,
+            token: Token::default(),
+        }
+    }
+}
+
+impl<'a> TokenStream for UnicodeTokenStream<'a> {
+    fn advance(&mut self) -> bool {
+        if self.cursor >= self.spans.len() {
+            return false;
+        }
+        let (offset_from, offset_to) = self.spans[self.cursor];
+        self.token.offset_from = offset_from;
+        self.token.offset_to = offset_to;
+        self.token.position = self.cursor;
+        self.token.text.clear();
+        self.token.text.push_str(&self.text[offset_from..offset_to]);
+        self.cursor += 1;
+        true
+    }
+
+    fn token(&self) -> &Token {
+        &self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UnicodeTokenizer;
+    use tokenizer::{Token, TokenStream, Tokenizer};
+
+    fn token_texts(tokenizer: &UnicodeTokenizer, text: &str) -> Vec<String> {
+        let mut tokens = vec![];
+        {
+            let mut add_token = |token: &Token| {
+                tokens.push(token.text.clone());
+            };
+            tokenizer.token_stream(text).process(&mut add_token);
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_unicode_tokenizer() {
+        let tokenizer = UnicodeTokenizer::default();
+        assert_eq!(
+            token_texts(&tokenizer, "I don't pay 3.14 €, nor 1,000.5!"),
+            vec!["I", "don't", "pay", "3.14", "nor", "1,000.5"]
+        );
+        assert_eq!(
+            token_texts(&tokenizer, "mail john.doe@example.com"),
+            vec!["mail", "john.doe", "example.com"]
+        );
+        assert!(token_texts(&tokenizer, " ... ").is_empty());
+    }
+
+    #[test]
+    fn test_unicode_tokenizer_keep_urls_and_emails() {
+        let tokenizer = UnicodeTokenizer::default().keep_urls().keep_emails();
+        assert_eq!(
+            token_texts(
+                &tokenizer,
+                "Contact john.doe@example.com (see https://example.com/a?b=1).",
+            ),
+            vec!["Contact", "john.doe@example.com", "see", "https://example.com/a?b=1"]
+        );
+    }
+
+    #[test]
+    fn test_unicode_tokenizer_keep_numbers() {
+        let tokenizer = UnicodeTokenizer::default().keep_numbers();
+        let mut token_stream = tokenizer.token_stream("Call 555-1234 on 2018/01/30");
+        let mut tokens = vec![];
+        while let Some(token) = token_stream.next() {
+            tokens.push((token.text.clone(), token.offset_from, token.position));
+        }
+        assert_eq!(
+            tokens,
+            vec![
+                ("Call".to_string(), 0, 0),
+                ("555-1234".to_string(), 5, 1),
+                ("on".to_string(), 14, 2),
+                ("2018/01/30".to_string(), 17, 3),
+            ]
+        );
+    }
+}
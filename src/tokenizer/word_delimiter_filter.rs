@@ -0,0 +1,288 @@
+use super::{Token, TokenFilter, TokenStream};
+
+/// `WordDelimiterFilter` splits tokens into their subwords.
+///
+/// Tokens are split
+/// * on non-alphanumeric characters (e.g. `log_level`, `wi-fi`),
+/// * on case changes (e.g. `getResponse`, `HTTPResponse`),
+/// * on letter-digit transitions (e.g. `utf8`, `2nd`).
+///
+/// For instance, `getHTTPResponse2` is split into `get`, `HTTP`,
+/// `Response` and `2`.
+///
+/// The subwords of a token are emitted at consecutive positions, and the
+/// positions of the following tokens are shifted accordingly, so that
+/// phrase queries can match subwords. For this reason, this filter should
+/// come right after the tokenizer.
+///
+/// Optionally, the original token and the catenation of its subwords
+/// can be emitted as well, at the position of its first subword.
+#[derive(Clone, Default)]
+pub struct WordDelimiterFilter {
+    preserve_original: bool,
+    catenate_all: bool,
+}
+
+impl WordDelimiterFilter {
+    /// Also emit the original token (e.g. `getHTTPResponse2`),
+    /// if it was split.
+    pub fn preserve_original(mut self) -> WordDelimiterFilter {
+        self.preserve_original = true;
+        self
+    }
+
+    /// Also emit the catenation of the subwords of a token
+    /// (e.g. `wifi` for `wi-fi`), if it was split.
+    pub fn catenate_all(mut self) -> WordDelimiterFilter {
+        self.catenate_all = true;
+        self
+    }
+}
+
+impl<TailTokenStream> TokenFilter<TailTokenStream> for WordDelimiterFilter
+where TailTokenStream: TokenStream {
+    type ResultTokenStream = WordDelimiterFilterStream<TailTokenStream>;
+
+    fn name(&self) -> &'static str {
+        "word_delimiter"
+    }
+
+    fn transform(&self, token_stream: TailTokenStream) -> Self::ResultTokenStream {
+        WordDelimiterFilterStream::wrap(self.clone(), token_stream)
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum CharClass {
+    Upper,
+    // lowercase letters, and letters without case.
+    Lower,
+    Digit,
+    Delimiter,
+}
+
+fn char_class(c: char) -> CharClass {
+    if c.is_uppercase() {
+        CharClass::Upper
+    } else if c.is_numeric() {
+        CharClass::Digit
+    } else if c.is_alphabetic() {
+        CharClass::Lower
+    } else {
+        CharClass::Delimiter
+    }
+}
+
+/// Returns the byte ranges of the subwords of `text`.
+fn split_subwords(text: &str) -> Vec<(usize, usize)> {
+    let chars: Vec<(usize, CharClass)> = text.char_indices()
+        .map(|(offset, c)| (offset, char_class(c)))
+        .collect();
+    let mut subwords = vec![];
+    let mut start_opt: Option<usize> = None;
+    for (ord, &(offset, class)) in chars.iter().enumerate() {
+        if class == CharClass::Delimiter {
+            if let Some(start) = start_opt.take() {
+                subwords.push((start, offset));
+            }
+            continue;
+        }
+        if let Some(start) = start_opt {
+            let previous_class = chars[ord - 1].1;
+            let next_is_lower = chars
+                .get(ord + 1)
+                .map(|&(_, next_class)| next_class == CharClass::Lower)
+                .unwrap_or(false);
+            let is_boundary = match (previous_class, class) {
+                (CharClass::Digit, CharClass::Digit) => false,
+                (CharClass::Digit, _) | (_, CharClass::Digit) => true,
+                (CharClass::Lower, CharClass::Upper) => true,
+                // `HTTPResponse` is split before `R`.
+                (CharClass::Upper, CharClass::Upper) => next_is_lower,
+                _ => false,
+            };
+            if is_boundary {
+                subwords.push((start, offset));
+                start_opt = Some(offset);
+            }
+        } else {
+            start_opt = Some(offset);
+        }
+    }
+    if let Some(start) = start_opt {
+        subwords.push((start, text.len()));
+    }
+    subwords
+}
+
+pub struct WordDelimiterFilterStream<TailTokenStream>
+where TailTokenStream: TokenStream {
+    filter: WordDelimiterFilter,
+    tail: TailTokenStream,
+    // number of positions added by the subwords emitted so far.
+    position_shift: usize,
+    // remaining tokens for the current tail token, in reverse order.
+    tokens: Vec<Token>,
+    token: Token,
+}
+
+impl<TailTokenStream> WordDelimiterFilterStream<TailTokenStream>
+where TailTokenStream: TokenStream {
+    fn wrap(
+        filter: WordDelimiterFilter,
+        tail: TailTokenStream,
+    ) -> WordDelimiterFilterStream<TailTokenStream>
+    {
+        WordDelimiterFilterStream {
+            filter,
+            tail,
+            position_shift: 0,
+            tokens: Vec::new(),
+            token: Token::default(),
+        }
+    }
+
+    fn split_token(&mut self) {
+        let token = self.tail.token();
+        let position = token.position + self.position_shift;
+        let subwords = split_subwords(&token.text);
+        let original = Token {
+            offset_from: token.offset_from,
+            offset_to: token.offset_to,
+            position,
+            text: token.text.clone(),
+        };
+        if subwords.len() == 1 && subwords[0] == (0, token.text.len()) {
+            self.tokens.push(original);
+            return;
+        }
+        // offsets of the subwords can only be computed if the
+        // text was not modified by a previous filter.
+        let has_exact_offsets = token.text.len() == token.offset_to - token.offset_from;
+        let mut tokens = vec![];
+        for (ord, &(start, end)) in subwords.iter().enumerate() {
+            let (offset_from, offset_to) = if has_exact_offsets {
+                (token.offset_from + start, token.offset_from + end)
+            } else {
+                (token.offset_from, token.offset_to)
+            };
+            tokens.push(Token {
+                offset_from,
+                offset_to,
+                position: position + ord,
+                text: token.text[start..end].to_string(),
+            });
+            if ord > 0 {
+                continue;
+            }
+            if self.filter.preserve_original {
+                tokens.push(original.clone());
+            }
+            let catenated: String = subwords
+                .iter()
+                .map(|&(start, end)| &token.text[start..end])
+                .collect();
+            let is_new_catenation = subwords.len() > 1
+                && (catenated != token.text || !self.filter.preserve_original);
+            if self.filter.catenate_all && is_new_catenation {
+                tokens.push(Token {
+                    text: catenated,
+                    ..original.clone()
+                });
+            }
+        }
+        if subwords.is_empty() && self.filter.preserve_original {
+            tokens.push(original);
+        }
+        if !subwords.is_empty() {
+            self.position_shift += subwords.len() - 1;
+        }
+        tokens.reverse();
+        self.tokens = tokens;
+    }
+}
+
+impl<TailTokenStream> TokenStream for WordDelimiterFilterStream<TailTokenStream>
+where TailTokenStream: TokenStream {
+    fn token(&self) -> &Token {
+        &self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.token
+    }
+
+    fn advance(&mut self) -> bool {
+        loop {
+            if let Some(token) = self.tokens.pop() {
+                self.token = token;
+                return true;
+            }
+            if !self.tail.advance() {
+                return false;
+            }
+            self.split_token();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WordDelimiterFilter;
+    use tokenizer::{LowerCaser, RegexTokenizer, Token, TokenStream, Tokenizer};
+
+    fn tokens(filter: WordDelimiterFilter, text: &str) -> Vec<(String, usize, usize, usize)> {
+        let tokenizer = RegexTokenizer::split_on(r"\s+")
+            .unwrap()
+            .filter(filter)
+            .filter(LowerCaser);
+        let mut tokens = vec![];
+        {
+            let mut add_token = |token: &Token| {
+                tokens.push((
+                    token.text.clone(),
+                    token.offset_from,
+                    token.offset_to,
+                    token.position,
+                ));
+            };
+            tokenizer.token_stream(text).process(&mut add_token);
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_word_delimiter_filter() {
+        assert_eq!(
+            tokens(WordDelimiterFilter::default(), "getHTTPResponse2 log_level -- ok"),
+            vec![
+                ("get".to_string(), 0, 3, 0),
+                ("http".to_string(), 3, 7, 1),
+                ("response".to_string(), 7, 15, 2),
+                ("2".to_string(), 15, 16, 3),
+                ("log".to_string(), 17, 20, 4),
+                ("level".to_string(), 21, 26, 5),
+                ("ok".to_string(), 30, 32, 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_word_delimiter_filter_preserve_original() {
+        let filter = WordDelimiterFilter::default()
+            .preserve_original()
+            .catenate_all();
+        assert_eq!(
+            tokens(filter, "getResponse wi-fi"),
+            vec![
+                ("get".to_string(), 0, 3, 0),
+                ("getresponse".to_string(), 0, 11, 0),
+                ("response".to_string(), 3, 11, 1),
+                ("wi".to_string(), 12, 14, 2),
+                ("wi-fi".to_string(), 12, 17, 2),
+                ("wifi".to_string(), 12, 17, 2),
+                ("fi".to_string(), 15, 17, 3),
+            ]
+        );
+    }
+}